@@ -15,7 +15,86 @@ pub enum OutputFormat {
     Csv,
 }
 
+/// I/O backend options
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum IoBackendArg {
+    /// Blocking read/write calls
+    #[default]
+    Sync,
+    /// Linux io_uring (requires the `io-uring` build feature)
+    IoUring,
+}
+
+/// I/O engine options for the sync backend
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum IoEngineArg {
+    /// Positioned pread/pwrite
+    #[default]
+    Pread,
+    /// Vectored preadv/pwritev
+    Preadv,
+    /// Memory-mapped file (always buffered)
+    Mmap,
+}
+
+/// Flush options for the sync write test
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum SyncMethodArg {
+    /// fsync after every write
+    Fsync,
+    /// fdatasync after every write
+    #[default]
+    Fdatasync,
+    /// Writes to a file opened with O_DSYNC (write-through on Windows)
+    Dsync,
+}
+
+/// Data pattern options for the write tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum DataPatternArg {
+    /// Repeating 0x00..0xFF byte sequence (compresses well)
+    #[default]
+    Sequential,
+    /// All zeros
+    Zeros,
+    /// Random, incompressible data
+    Random,
+    /// Random data mixed with zeros to reach --compression-ratio
+    Compressible,
+    /// Random data with every sector stamped with its offset and a sequence number
+    Unique,
+}
+
+/// Benchmark test selection options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TestArg {
+    /// Sequential write
+    SeqWrite,
+    /// Sequential read
+    SeqRead,
+    /// Random write
+    RandWrite,
+    /// Random read
+    RandRead,
+    /// Mixed random read/write
+    Mixed,
+    /// Small writes each followed by a flush
+    SyncWrite,
+    /// File create, stat, rename, list and delete rates (not run by default)
+    Metadata,
+    /// Writing and reading back a tree of small files (not run by default)
+    SmallFiles,
+    /// Memory copy bandwidth across working-set sizes
+    Memory,
+    /// STREAM copy, scale, add and triad kernels on many threads (not run by default)
+    MemoryStream,
+    /// Long sequential write tracking throughput over time (not run by default)
+    SustainedWrite,
+}
+
 /// CLI commands
+// Parsed once at startup, so the size of the benchmark options does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     /// List available storage devices
@@ -38,10 +117,134 @@ pub enum Commands {
         #[arg(long, short = 'd', value_name = "SECONDS")]
         duration: Option<u64>,
 
+        /// Stop each test after moving this much data instead of after the duration
+        #[arg(long, value_name = "SIZE", conflicts_with_all = ["operations", "passes"])]
+        bytes: Option<String>,
+
+        /// Stop each test after this many operations instead of after the duration
+        #[arg(long, value_name = "COUNT", conflicts_with = "passes")]
+        operations: Option<u64>,
+
+        /// Stop each test after this many full passes over the test file
+        #[arg(long, value_name = "COUNT")]
+        passes: Option<u64>,
+
+        /// Longest a test stopped by --bytes, --operations or --passes may run, in seconds
+        #[arg(long, value_name = "SECONDS")]
+        time_cap: Option<u64>,
+
+        /// Duration of the sustained write test in seconds (default: 300)
+        #[arg(long, value_name = "SECONDS")]
+        sustained_duration: Option<u64>,
+
+        /// Warm-up in seconds before each test is measured (default: 0)
+        #[arg(long, value_name = "SECONDS")]
+        warmup: Option<u64>,
+
+        /// Run the selected tests this many times and report their spread (default: 1)
+        #[arg(long, value_name = "COUNT")]
+        repetitions: Option<usize>,
+
         /// Test file size (default: 1GB)
         #[arg(long, value_name = "SIZE")]
         file_size: Option<String>,
 
+        /// Outstanding I/O requests per thread for random tests (default: 1)
+        #[arg(long, short = 'q', value_name = "DEPTH")]
+        queue_depth: Option<usize>,
+
+        /// Worker threads for random tests (default: 1)
+        #[arg(long, short = 't', value_name = "COUNT")]
+        threads: Option<usize>,
+
+        /// Share of reads in the mixed random test, in percent (default: 70)
+        #[arg(long, value_name = "PCT", value_parser = clap::value_parser!(u8).range(0..=100))]
+        mixed_read_percentage: Option<u8>,
+
+        /// Block size for the mixed random test (default: 8KB)
+        #[arg(long, value_name = "SIZE")]
+        mixed_block_size: Option<String>,
+
+        /// Record size for the sync write test (default: 4KB)
+        #[arg(long, value_name = "SIZE")]
+        sync_size: Option<String>,
+
+        /// How the sync write test makes each record durable
+        #[arg(long, value_enum, default_value_t = SyncMethodArg::Fdatasync)]
+        sync_method: SyncMethodArg,
+
+        /// Append the sync write test's records instead of overwriting a preallocated log
+        #[arg(long)]
+        sync_append: bool,
+
+        /// Number of files the metadata test works on (default: 10000)
+        #[arg(long, value_name = "COUNT")]
+        metadata_files: Option<usize>,
+
+        /// Number of directories the metadata test spreads its files across (default: 100)
+        #[arg(long, value_name = "COUNT")]
+        metadata_fanout: Option<usize>,
+
+        /// Number of files the small-file test writes and reads back (default: 1000)
+        #[arg(long, value_name = "COUNT")]
+        small_files: Option<usize>,
+
+        /// Comma-separated small file sizes with optional weights, e.g. 4KB:60,64KB:30,1MB:10
+        /// (default: 4KB:40,16KB:25,64KB:20,256KB:10,1MB:5)
+        #[arg(long, value_name = "SIZES")]
+        small_file_sizes: Option<String>,
+
+        /// Smallest working set the memory test copies within (default: 4KB)
+        #[arg(long, value_name = "SIZE")]
+        memory_min_size: Option<String>,
+
+        /// Largest working set the memory test copies within (default: 512MB)
+        #[arg(long, value_name = "SIZE")]
+        memory_max_size: Option<String>,
+
+        /// Threads the memory stream test runs its kernels on (default: one per CPU)
+        #[arg(long, value_name = "COUNT")]
+        memory_threads: Option<usize>,
+
+        /// CPUs to pin the memory stream threads to, round-robin, e.g. 0-7,16-23
+        #[arg(long, value_name = "LIST", conflicts_with = "memory_nodes")]
+        memory_cpus: Option<String>,
+
+        /// NUMA nodes to pin the memory stream threads and their memory to, round-robin
+        #[arg(long, value_name = "LIST")]
+        memory_nodes: Option<String>,
+
+        /// Comma-separated tests to run, in order (default: all but metadata, small-files,
+        /// memory-stream and sustained-write)
+        #[arg(long, value_enum, value_delimiter = ',', value_name = "TESTS")]
+        tests: Vec<TestArg>,
+
+        /// Data written by the write tests
+        #[arg(long, value_enum, default_value_t = DataPatternArg::Sequential)]
+        data_pattern: DataPatternArg,
+
+        /// Target compression ratio for the compressible data pattern (default: 2.0)
+        #[arg(long, value_name = "RATIO")]
+        compression_ratio: Option<f64>,
+
+        /// I/O backend used for disk tests
+        #[arg(long, value_enum, default_value_t = IoBackendArg::Sync)]
+        io_backend: IoBackendArg,
+
+        /// I/O engine used by the sync backend
+        #[arg(long, value_enum, default_value_t = IoEngineArg::Pread)]
+        io_engine: IoEngineArg,
+
+        /// Stamp every written block and check every block read back; corrupted
+        /// data fails the run with exit code 6
+        #[arg(long)]
+        verify: bool,
+
+        /// Include each test's throughput, operations and mean latency per 100 ms
+        /// interval in JSON and CSV output
+        #[arg(long)]
+        include_samples: bool,
+
         /// Enable OS caching (default: disabled for accurate results)
         #[arg(long)]
         enable_cache: bool,
@@ -56,6 +259,37 @@ pub enum Commands {
         #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Table)]
         output_format: OutputFormat,
     },
+
+    /// Check a drive for fake capacity by filling its free space and reading it back
+    CapacityCheck {
+        /// Directory on the drive to check, usually its mount point
+        target_path: PathBuf,
+
+        /// Most data to write (default: all free space)
+        #[arg(long, value_name = "SIZE")]
+        size: Option<String>,
+
+        /// Size of each fill file; progress is saved after every file (default: 1GB)
+        #[arg(long, value_name = "SIZE")]
+        file_size: Option<String>,
+
+        /// Size of each read and write (default: 1MB)
+        #[arg(long, value_name = "SIZE")]
+        block_size: Option<String>,
+
+        /// Keep the fill files after the check instead of deleting them
+        #[arg(long)]
+        keep_files: bool,
+
+        /// Disable direct I/O operations (use buffered I/O instead)
+        /// Reads may then be served from the OS cache instead of the drive
+        #[arg(long)]
+        disable_direct_io: bool,
+
+        /// Output format
+        #[arg(long, short = 'o', value_enum, default_value_t = OutputFormat::Table)]
+        output_format: OutputFormat,
+    },
 }
 
 /// Cross-platform disk speed testing utility
@@ -124,6 +358,28 @@ pub fn parse_size(size_str: &str) -> Result<usize, String> {
     Ok(result as usize)
 }
 
+/// Parse a file size distribution like "4KB:60,64KB:30,1MB:10" into sizes and weights
+///
+/// A size without a weight has weight 1.
+pub fn parse_file_sizes(sizes_str: &str) -> Result<Vec<(usize, u32)>, String> {
+    sizes_str
+        .split(',')
+        .map(|entry| {
+            let (size, weight) = match entry.split_once(':') {
+                Some((size, weight)) => {
+                    let weight = weight
+                        .trim()
+                        .parse::<u32>()
+                        .map_err(|_| format!("Invalid weight in file size: {}", entry))?;
+                    (size, weight)
+                }
+                None => (entry, 1),
+            };
+            Ok((parse_size(size)?, weight))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,4 +406,21 @@ mod tests {
         assert!(parse_size("1XB").is_err());
         assert!(parse_size("").is_err());
     }
+
+    #[test]
+    fn test_parse_file_sizes() {
+        assert_eq!(
+            parse_file_sizes("4KB:60, 64KB:30,1MB:10").unwrap(),
+            vec![(4 * 1024, 60), (64 * 1024, 30), (1024 * 1024, 10)]
+        );
+        assert_eq!(
+            parse_file_sizes("512,8KB").unwrap(),
+            vec![(512, 1), (8 * 1024, 1)]
+        );
+
+        assert!(parse_file_sizes("4KB:x").is_err());
+        assert!(parse_file_sizes("4KB:-1").is_err());
+        assert!(parse_file_sizes("4XB:1").is_err());
+        assert!(parse_file_sizes("").is_err());
+    }
 }
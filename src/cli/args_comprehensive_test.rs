@@ -116,6 +116,7 @@ mod tests {
                 enable_cache,
                 disable_direct_io: _,
                 output_format,
+                ..
             } => {
                 assert_eq!(target_path, PathBuf::from("/tmp/test"));
                 assert_eq!(sequential_block_size, Some("8MB".to_string()));
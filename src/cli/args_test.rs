@@ -2,7 +2,9 @@
 
 #[cfg(test)]
 mod tests {
-    use super::super::args::{parse_size, Cli, Commands, OutputFormat};
+    use super::super::args::{
+        parse_size, Cli, Commands, DataPatternArg, IoEngineArg, OutputFormat, TestArg,
+    };
     use clap::Parser;
     use std::path::PathBuf;
 
@@ -62,6 +64,7 @@ mod tests {
                 enable_cache,
                 disable_direct_io: _,
                 output_format,
+                ..
             } => {
                 assert_eq!(target_path, PathBuf::from("/tmp"));
                 assert_eq!(sequential_block_size, Some("8MB".to_string()));
@@ -101,6 +104,169 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_benchmark_command_queue_depth_and_threads() {
+        let args = vec![
+            "disk-speed-test",
+            "benchmark",
+            "/tmp",
+            "--queue-depth",
+            "32",
+            "-t",
+            "16",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        match cli.command {
+            Commands::Benchmark {
+                queue_depth,
+                threads,
+                ..
+            } => {
+                assert_eq!(queue_depth, Some(32));
+                assert_eq!(threads, Some(16));
+            }
+            _ => panic!("Expected Benchmark command"),
+        }
+    }
+
+    #[test]
+    fn test_benchmark_command_io_engine() {
+        let cli = Cli::try_parse_from(vec!["disk-speed-test", "benchmark", "/tmp"]).unwrap();
+        match cli.command {
+            Commands::Benchmark { io_engine, .. } => {
+                assert!(matches!(io_engine, IoEngineArg::Pread));
+            }
+            _ => panic!("Expected Benchmark command"),
+        }
+
+        let args = vec![
+            "disk-speed-test",
+            "benchmark",
+            "/tmp",
+            "--io-engine",
+            "mmap",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Benchmark { io_engine, .. } => {
+                assert!(matches!(io_engine, IoEngineArg::Mmap));
+            }
+            _ => panic!("Expected Benchmark command"),
+        }
+
+        let args = vec!["disk-speed-test", "benchmark", "/tmp", "--io-engine", "aio"];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn test_benchmark_command_mixed_workload() {
+        let args = vec![
+            "disk-speed-test",
+            "benchmark",
+            "/tmp",
+            "--mixed-read-percentage",
+            "30",
+            "--mixed-block-size",
+            "16KB",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Benchmark {
+                mixed_read_percentage,
+                mixed_block_size,
+                ..
+            } => {
+                assert_eq!(mixed_read_percentage, Some(30));
+                assert_eq!(mixed_block_size, Some("16KB".to_string()));
+            }
+            _ => panic!("Expected Benchmark command"),
+        }
+
+        let args = vec![
+            "disk-speed-test",
+            "benchmark",
+            "/tmp",
+            "--mixed-read-percentage",
+            "101",
+        ];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn test_benchmark_command_tests() {
+        let cli = Cli::try_parse_from(vec!["disk-speed-test", "benchmark", "/tmp"]).unwrap();
+        match cli.command {
+            Commands::Benchmark { tests, .. } => assert!(tests.is_empty()),
+            _ => panic!("Expected Benchmark command"),
+        }
+
+        let args = vec![
+            "disk-speed-test",
+            "benchmark",
+            "/tmp",
+            "--tests",
+            "rand-read,seq-write",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Benchmark { tests, .. } => {
+                assert_eq!(tests, vec![TestArg::RandRead, TestArg::SeqWrite]);
+            }
+            _ => panic!("Expected Benchmark command"),
+        }
+
+        let args = vec!["disk-speed-test", "benchmark", "/tmp", "--tests", "latency"];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn test_benchmark_command_data_pattern() {
+        let cli = Cli::try_parse_from(vec!["disk-speed-test", "benchmark", "/tmp"]).unwrap();
+        match cli.command {
+            Commands::Benchmark {
+                data_pattern,
+                compression_ratio,
+                ..
+            } => {
+                assert_eq!(data_pattern, DataPatternArg::Sequential);
+                assert_eq!(compression_ratio, None);
+            }
+            _ => panic!("Expected Benchmark command"),
+        }
+
+        let args = vec![
+            "disk-speed-test",
+            "benchmark",
+            "/tmp",
+            "--data-pattern",
+            "compressible",
+            "--compression-ratio",
+            "3.5",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+        match cli.command {
+            Commands::Benchmark {
+                data_pattern,
+                compression_ratio,
+                ..
+            } => {
+                assert_eq!(data_pattern, DataPatternArg::Compressible);
+                assert_eq!(compression_ratio, Some(3.5));
+            }
+            _ => panic!("Expected Benchmark command"),
+        }
+
+        let args = vec![
+            "disk-speed-test",
+            "benchmark",
+            "/tmp",
+            "--data-pattern",
+            "ones",
+        ];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+
     #[test]
     fn test_help_generation() {
         // Test that help can be generated without panicking
@@ -296,6 +462,7 @@ mod tests {
                 enable_cache,
                 disable_direct_io: _,
                 output_format,
+                ..
             } => {
                 assert_eq!(target_path, PathBuf::from("/tmp/test"));
                 assert_eq!(sequential_block_size, Some("8MB".to_string()));
@@ -0,0 +1,287 @@
+//! Fake-capacity check command for CLI
+
+use anyhow::Result;
+use disk_speed_test::core::capacity::CAPACITY_CHECK_DIR;
+use disk_speed_test::{
+    platform, run_capacity_check, BenchmarkError, CapacityCheckConfig, CapacityCheckResults,
+};
+use std::time::SystemTime;
+
+use super::args::{parse_size, OutputFormat};
+use super::display::CliProgressCallback;
+
+/// Options for running a capacity check
+#[derive(Debug)]
+pub struct CapacityCheckOptions {
+    pub target_path: std::path::PathBuf,
+    pub size: Option<String>,
+    pub file_size: Option<String>,
+    pub block_size: Option<String>,
+    pub keep_files: bool,
+    pub disable_direct_io: bool,
+    pub output_format: OutputFormat,
+}
+
+/// Run the capacity-check command with the specified parameters
+pub fn capacity_check_command(options: CapacityCheckOptions) -> Result<()> {
+    let CapacityCheckOptions {
+        target_path,
+        size,
+        file_size,
+        block_size,
+        keep_files,
+        disable_direct_io,
+        output_format,
+    } = options;
+    let mut config = CapacityCheckConfig::new(target_path);
+
+    if let Some(size_str) = size {
+        let size_bytes =
+            parse_size(&size_str).map_err(|e| anyhow::anyhow!("Invalid fill size: {}", e))?;
+        config.max_bytes = Some(size_bytes as u64);
+    }
+
+    if let Some(size_str) = file_size {
+        let size_bytes =
+            parse_size(&size_str).map_err(|e| anyhow::anyhow!("Invalid file size: {}", e))?;
+        config.file_size = size_bytes as u64;
+    }
+
+    if let Some(size_str) = block_size {
+        config.block_size =
+            parse_size(&size_str).map_err(|e| anyhow::anyhow!("Invalid block size: {}", e))?;
+    }
+
+    config.keep_files = keep_files;
+    config.disable_direct_io = disable_direct_io;
+
+    if let Err(e) = config.validate() {
+        return Err(anyhow::anyhow!("Configuration validation failed: {}", e));
+    }
+
+    // Without the device's free space, only --size keeps the fill from running
+    // into whatever shares the filesystem
+    let device = platform::storage_device_for_path(&config.target_path)
+        .ok()
+        .flatten();
+    if device.is_none() && config.max_bytes.is_none() {
+        return Err(anyhow::anyhow!(
+            "{} is not on a listed storage device; pass --size to set how much to fill",
+            config.target_path.display()
+        ));
+    }
+
+    if matches!(output_format, OutputFormat::Table) {
+        println!("Capacity Check Configuration:");
+        println!("  Target path: {}", config.target_path.display());
+        if let Some(device) = &device {
+            println!(
+                "  Device: {} - {} ({} available / {} total)",
+                device.name,
+                device.mount_point.display(),
+                format_bytes(device.available_space),
+                format_bytes(device.total_space)
+            );
+        }
+        if let Some(max_bytes) = config.max_bytes {
+            println!("  Fill size: at most {}", format_bytes(max_bytes));
+        }
+        println!(
+            "  File size: {} ({} blocks)",
+            format_bytes(config.file_size),
+            format_bytes(config.block_size as u64)
+        );
+        if config.target_path.join(CAPACITY_CHECK_DIR).exists() {
+            println!("  Resuming the check left in {}", CAPACITY_CHECK_DIR);
+        }
+        println!("\nStarting capacity check...\n");
+    }
+
+    // Ctrl+C stops the check; its files are kept so that it can be resumed
+    super::cancel_on_interrupt(config.cancellation.clone());
+
+    let progress_callback = CliProgressCallback::new(output_format.clone());
+    let results = run_capacity_check(config, Some(Box::new(progress_callback)))
+        .map_err(|e| anyhow::anyhow!("Capacity check failed: {}", e))?;
+
+    match output_format {
+        OutputFormat::Table => display_capacity_table(&results),
+        OutputFormat::Json => println!("{}", format_capacity_json(&results)?),
+        OutputFormat::Csv => print!("{}", format_capacity_csv(&results)),
+    }
+
+    if results.interrupted {
+        return Err(BenchmarkError::TestInterrupted(
+            "capacity check cancelled by user; run it again on the same path to resume".to_string(),
+        )
+        .into());
+    }
+    if results.has_corruption() {
+        return Err(BenchmarkError::DataCorruption(format!(
+            "only {} of the {} written read back intact",
+            format_bytes(results.usable_bytes),
+            format_bytes(results.written_bytes)
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Format a byte count in GB, or MB below 1 GB
+fn format_bytes(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    if bytes >= 1024 * 1024 * 1024 {
+        format!("{:.2} GB", bytes as f64 / (MB * 1024.0))
+    } else {
+        format!("{:.2} MB", bytes as f64 / MB)
+    }
+}
+
+/// Display the outcome of a capacity check with the speed of every fill file
+fn display_capacity_table(results: &CapacityCheckResults) {
+    let callback = CliProgressCallback::new(OutputFormat::Table);
+    let speed = |speed_mbps: Option<f64>| {
+        speed_mbps.map_or_else(|| "-".to_string(), |speed| callback.format_speed(speed))
+    };
+
+    println!("\n{}", callback.colorize("Capacity Check Results", "1;36"));
+    if let Some(free) = results.reported_free_bytes {
+        println!("  Reported free space: {}", format_bytes(free));
+    }
+    println!(
+        "  Written:             {}",
+        format_bytes(results.written_bytes)
+    );
+    println!(
+        "  Read back intact:    {}",
+        format_bytes(results.verified_bytes)
+    );
+    println!(
+        "  Corrupted:           {}",
+        format_bytes(results.corrupted_bytes)
+    );
+    println!(
+        "  Usable capacity:     {}",
+        callback.colorize(&format_bytes(results.usable_bytes), "1")
+    );
+    if let Some(event) = results.first_corruption {
+        println!(
+            "  First bad offset:    {} ({} bytes): {}",
+            format_bytes(event.offset),
+            event.offset,
+            event.kind
+        );
+    }
+    // Only the blocks moved in this session are timed
+    println!(
+        "  Write speed:         {}",
+        speed((results.write.total_bytes > 0).then_some(results.write.avg_speed_mbps))
+    );
+    println!(
+        "  Read speed:          {}",
+        speed((results.read.total_bytes > 0).then_some(results.read.avg_speed_mbps))
+    );
+
+    if !results.samples.is_empty() {
+        println!("\n  Speed across the fill:");
+        for sample in &results.samples {
+            println!(
+                "    {:>10} - {:>10}: write {:>12}, read {:>12}",
+                format_bytes(sample.offset),
+                format_bytes(sample.offset + sample.bytes),
+                speed(Some(sample.write_speed_mbps)),
+                speed(sample.read_speed_mbps)
+            );
+        }
+    }
+
+    println!();
+    if results.interrupted {
+        println!(
+            "  {} Check interrupted; run it again on the same path to resume",
+            callback.colorize("⚠", "1;33")
+        );
+    } else if results.has_corruption() {
+        println!(
+            "  {} The drive holds less than it reports: only the first {} are usable",
+            callback.colorize("✗", "1;31"),
+            format_bytes(results.usable_bytes)
+        );
+    } else {
+        println!(
+            "  {} All {} written read back intact",
+            callback.colorize("✓", "1;32"),
+            format_bytes(results.written_bytes)
+        );
+    }
+    if results.resumed {
+        println!("  (resumed an earlier check)");
+    }
+}
+
+/// Format capacity check results as JSON
+pub fn format_capacity_json(results: &CapacityCheckResults) -> Result<String, serde_json::Error> {
+    let samples: Vec<serde_json::Value> = results
+        .samples
+        .iter()
+        .map(|sample| {
+            serde_json::json!({
+                "offset": sample.offset,
+                "bytes": sample.bytes,
+                "write_speed_mbps": sample.write_speed_mbps,
+                "read_speed_mbps": sample.read_speed_mbps,
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&serde_json::json!({
+        "timestamp": SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        "version": env!("CARGO_PKG_VERSION"),
+        "reported_free_bytes": results.reported_free_bytes,
+        "written_bytes": results.written_bytes,
+        "verified_bytes": results.verified_bytes,
+        "corrupted_bytes": results.corrupted_bytes,
+        "usable_bytes": results.usable_bytes,
+        "first_bad_offset": results.first_bad_offset(),
+        "first_corruption": results.first_corruption,
+        "write_speed_mbps": results.write.avg_speed_mbps,
+        "read_speed_mbps": results.read.avg_speed_mbps,
+        "samples": samples,
+        "resumed": results.resumed,
+        "interrupted": results.interrupted,
+    }))
+}
+
+/// Format capacity check results as CSV, one row per fill file
+pub fn format_capacity_csv(results: &CapacityCheckResults) -> String {
+    let mut csv = String::from("Offset,Bytes,Write (MB/s),Read (MB/s)\n");
+    for sample in &results.samples {
+        csv.push_str(&format!(
+            "{},{},{:.2},{}\n",
+            sample.offset,
+            sample.bytes,
+            sample.write_speed_mbps,
+            sample
+                .read_speed_mbps
+                .map_or_else(String::new, |speed| format!("{:.2}", speed))
+        ));
+    }
+
+    csv.push_str(&format!(
+        "# Written: {} bytes, intact: {} bytes, corrupted: {} bytes, usable: {} bytes\n",
+        results.written_bytes,
+        results.verified_bytes,
+        results.corrupted_bytes,
+        results.usable_bytes
+    ));
+    if let Some(event) = results.first_corruption {
+        csv.push_str(&format!(
+            "# First bad offset: {} ({})\n",
+            event.offset, event.kind
+        ));
+    }
+    csv
+}
@@ -1,8 +1,14 @@
 //! Display and output formatting for CLI
 
 use crate::cli::args::OutputFormat;
+use crate::cli::format_file_size;
 use anyhow::Result;
-use disk_speed_test::{BenchmarkError, BenchmarkResults, ProgressCallback, TestResult};
+use disk_speed_test::{
+    AggregatedResults, BenchmarkError, BenchmarkResults, CorruptionEvent, LatencyStats,
+    MemoryBandwidthPoint, MemoryStreamResult, MetadataOperation, MetadataResult, ProgressCallback,
+    ProgressUpdate, RunStatistics, SmallFilesResult, StopReason, SustainedWriteResult, TestPhase,
+    TestResult,
+};
 use std::io::{self, Write};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -92,17 +98,33 @@ impl CliProgressCallback {
         }
     }
 
-    /// Create a progress bar string
-    #[allow(dead_code)]
+    /// Create a 20-cell progress bar string with `width` cells filled
     pub fn create_progress_bar(&self, width: usize) -> String {
         if !self.use_colors {
-            return format!("[{}]", "=".repeat(width.min(20)));
+            let filled = "=".repeat(width.min(20));
+            let empty = " ".repeat(20_usize.saturating_sub(width));
+            return format!("[{}{}]", filled, empty);
         }
 
         let filled = "█".repeat(width.min(20));
         let empty = "░".repeat(20_usize.saturating_sub(width));
         format!("[{}{}]", filled, empty)
     }
+
+    /// Show a transient status line until the next update replaces it
+    fn show_status(&self, test_name: &str, status: &str) {
+        self.clear_line();
+        print!(
+            "  {} {}: {}...",
+            self.colorize("◌", "1;34"), // Blue circle
+            self.colorize(test_name, "1;33"),
+            status
+        );
+
+        if let Ok(mut showing) = self.showing_progress.lock() {
+            *showing = true;
+        }
+    }
 }
 
 impl Default for CliProgressCallback {
@@ -112,6 +134,25 @@ impl Default for CliProgressCallback {
 }
 
 impl ProgressCallback for CliProgressCallback {
+    fn on_run_start(&self, run: usize, total_runs: usize) {
+        match self.output_format {
+            OutputFormat::Table => {
+                self.clear_line();
+                println!(
+                    "\n{}",
+                    self.colorize(&format!("══ Run {} of {} ══", run, total_runs), "1;36")
+                );
+            }
+            OutputFormat::Json | OutputFormat::Csv => {
+                if self.verbose {
+                    eprintln!("Run {} of {}", run, total_runs);
+                }
+            }
+        }
+
+        io::stdout().flush().unwrap_or(());
+    }
+
     fn on_test_start(&self, test_name: &str) {
         // Requirements 10.1, 10.2, 10.3, 10.4, 10.5: Display test name when starting
         let colored_name = self.colorize(test_name, "1;36"); // Bright cyan
@@ -144,6 +185,25 @@ impl ProgressCallback for CliProgressCallback {
         io::stdout().flush().unwrap_or(());
     }
 
+    fn on_warmup(&self, test_name: &str, warmup: Duration) {
+        let warmup_str = self.format_duration(warmup);
+
+        match self.output_format {
+            OutputFormat::Table => {
+                // Shown until the first progress update replaces it
+                let status = format!("warming up for {}", self.colorize(&warmup_str, "37"));
+                self.show_status(test_name, &status);
+            }
+            OutputFormat::Json | OutputFormat::Csv => {
+                if self.verbose {
+                    eprintln!("{}: warming up for {}", test_name, warmup_str);
+                }
+            }
+        }
+
+        io::stdout().flush().unwrap_or(());
+    }
+
     fn on_progress(&self, test_name: &str, current_speed_mbps: f64) {
         // Requirements 10.1, 10.2, 10.3, 10.4, 10.5: Show current test name and speed
 
@@ -188,6 +248,96 @@ impl ProgressCallback for CliProgressCallback {
         io::stdout().flush().unwrap_or(());
     }
 
+    fn on_progress_update(&self, update: &ProgressUpdate) {
+        match self.output_format {
+            OutputFormat::Table => {
+                self.clear_line();
+
+                let filled = (update.fraction.clamp(0.0, 1.0) * 20.0).round() as usize;
+                // Tests that move no data, like the metadata test, show their rate instead
+                let rate = if update.bytes == 0 && update.operations > 0 {
+                    format!("{:.0} ops/s", update.iops)
+                } else {
+                    self.format_speed(update.current_speed_mbps)
+                };
+                print!(
+                    "  {} {} {} {:>3.0}% {}",
+                    self.colorize("●", "1;32"), // Green dot
+                    self.colorize(&update.test_name, "1;33"),
+                    self.create_progress_bar(filled),
+                    update.fraction * 100.0,
+                    self.colorize(&rate, "1;32")
+                );
+                if let Some(eta) = update.eta {
+                    print!(
+                        " {}",
+                        self.colorize(&format!("ETA {}", self.format_duration(eta)), "37")
+                    );
+                }
+
+                if self.verbose {
+                    print!(
+                        " {}",
+                        self.colorize(
+                            &format!(
+                                "(avg {}, {:.0} IOPS)",
+                                self.format_speed(update.average_speed_mbps),
+                                update.iops
+                            ),
+                            "37"
+                        )
+                    );
+                }
+
+                if let Ok(mut showing) = self.showing_progress.lock() {
+                    *showing = true;
+                }
+            }
+            OutputFormat::Json | OutputFormat::Csv => {
+                if self.verbose {
+                    eprintln!(
+                        "{}: {:.0}% {:.2} MB/s (avg {:.2} MB/s, {:.0} IOPS)",
+                        update.test_name,
+                        update.fraction * 100.0,
+                        update.current_speed_mbps,
+                        update.average_speed_mbps,
+                        update.iops
+                    );
+                }
+            }
+        }
+
+        io::stdout().flush().unwrap_or(());
+    }
+
+    fn on_phase_change(&self, test_name: &str, phase: TestPhase) {
+        // Warm-ups are announced by on_warmup, and measuring by the progress bar
+        if !matches!(phase, TestPhase::Flush | TestPhase::Cleanup) {
+            return;
+        }
+
+        match self.output_format {
+            OutputFormat::Table => self.show_status(test_name, &phase.to_string()),
+            OutputFormat::Json | OutputFormat::Csv => {
+                if self.verbose {
+                    eprintln!("{}: {}", test_name, phase);
+                }
+            }
+        }
+
+        io::stdout().flush().unwrap_or(());
+    }
+
+    fn on_warning(&self, message: &str) {
+        self.clear_line();
+        io::stdout().flush().unwrap_or(());
+        eprintln!(
+            "  {} {}",
+            self.colorize("⚠ Warning:", "1;33"), // Yellow
+            message
+        );
+    }
+
     fn on_test_complete(&self, test_name: &str, result: &TestResult) {
         // Requirement 10.6: Show P5 (low-percentile), Max, and average (bold) speeds when test completes
 
@@ -210,20 +360,36 @@ impl ProgressCallback for CliProgressCallback {
                 };
 
                 println!("  {} {} complete", checkmark, colored_name);
-                println!(
-                    "    P5: {} | P95: {} | Avg: {}",
-                    self.colorize(&min_speed, "37"), // Light gray
-                    self.colorize(&max_speed, "37"), // Light gray
-                    bold_avg
-                );
+                if result.total_bytes == 0 && result.total_operations > 0 {
+                    println!(
+                        "    {} | Mean latency: {} | p99: {}",
+                        self.colorize(&format!("{:.0} ops/s", result.latency.iops), "1"),
+                        self.colorize(&format_latency(result.latency.mean_us), "37"),
+                        self.colorize(&format_latency(result.latency.p99_us), "37")
+                    );
+                } else {
+                    println!(
+                        "    P5: {} | P95: {} | Avg: {}",
+                        self.colorize(&min_speed, "37"), // Light gray
+                        self.colorize(&max_speed, "37"), // Light gray
+                        bold_avg
+                    );
+                }
 
                 if self.verbose {
                     let duration_str = self.format_duration(result.test_duration);
                     println!(
-                        "    {} Duration: {}, Samples: {}",
+                        "    {} Duration: {}, Samples: {}, Sample avg: {}",
                         self.colorize("ℹ", "1;34"), // Blue info
                         self.colorize(&duration_str, "37"),
-                        self.colorize(&result.sample_count.to_string(), "37")
+                        self.colorize(&result.sample_count.to_string(), "37"),
+                        self.colorize(&self.format_speed(result.sample_avg_speed_mbps), "37")
+                    );
+                    println!(
+                        "    {} Transferred: {:.2} MB in {} operations",
+                        self.colorize("ℹ", "1;34"),
+                        result.total_bytes as f64 / (1024.0 * 1024.0),
+                        result.total_operations
                     );
                 }
             }
@@ -261,6 +427,27 @@ pub fn display_results(results: &BenchmarkResults, output_format: &OutputFormat)
     Ok(())
 }
 
+/// Display the results of repeated runs in the specified format
+pub fn display_aggregated_results(
+    results: &AggregatedResults,
+    output_format: &OutputFormat,
+) -> Result<()> {
+    match output_format {
+        OutputFormat::Table => {
+            display_aggregated_table(results);
+        }
+        OutputFormat::Json => {
+            let json_output = format_aggregated_json(results)?;
+            println!("{}", json_output);
+        }
+        OutputFormat::Csv => {
+            let csv_output = format_aggregated_csv(results);
+            println!("{}", csv_output);
+        }
+    }
+    Ok(())
+}
+
 /// Display error messages with helpful diagnostic information
 /// Requirement 11.4: Report errors with helpful diagnostic information
 pub fn display_error(error: &BenchmarkError) {
@@ -363,6 +550,16 @@ pub fn display_error(error: &BenchmarkError) {
             );
             eprintln!("  Try running the test again with stable system conditions.");
         }
+        BenchmarkError::DataCorruption(msg) => {
+            eprintln!("{} Data corruption detected: {}", error_prefix, msg);
+            eprintln!(
+                "{} The device returned data that differs from what was written.",
+                info_prefix
+            );
+            eprintln!(
+                "  Check the device, its controller and cabling before trusting it with data."
+            );
+        }
     }
 }
 
@@ -405,75 +602,601 @@ fn display_results_table(results: &BenchmarkResults) {
 
     // Table header with better formatting
     println!(
-        "{:<20} {:>12} {:>12} {:>12} {:>10} {:>8}",
+        "{:<20} {:>12} {:>12} {:>12} {:>12} {:>10} {:>8}",
         colorize("Test", "1;37"),       // Bold white
         colorize("P5 (MB/s)", "37"),    // Light gray
         colorize("P95 (MB/s)", "37"),   // Light gray
-        colorize("Avg (MB/s)", "1;33"), // Bold yellow for average
+        colorize("Avg (MB/s)", "1;33"), // Bold yellow for the effective throughput
+        colorize("Sample Avg", "37"),   // Light gray
         colorize("Duration", "37"),     // Light gray
         colorize("Samples", "37")
     ); // Light gray
 
     let separator = if use_colors {
-        colorize(&"─".repeat(80), "37")
+        colorize(&"─".repeat(93), "37")
     } else {
-        "-".repeat(80)
+        "-".repeat(93)
     };
     println!("{}", separator);
 
     // Format each test result with enhanced display
-    display_test_result_enhanced("Sequential Write", &results.sequential_write, use_colors);
-    display_test_result_enhanced("Sequential Read", &results.sequential_read, use_colors);
-    display_test_result_enhanced("Random Write", &results.random_write, use_colors);
-    display_test_result_enhanced("Random Read", &results.random_read, use_colors);
-    display_test_result_enhanced("Memory Copy", &results.memory_copy, use_colors);
+    display_optional_test_result(
+        "Sequential Write",
+        results.sequential_write.as_ref(),
+        use_colors,
+    );
+    display_optional_test_result(
+        "Sequential Read",
+        results.sequential_read.as_ref(),
+        use_colors,
+    );
+    display_optional_test_result("Random Write", results.random_write.as_ref(), use_colors);
+    display_optional_test_result("Random Read", results.random_read.as_ref(), use_colors);
+    match &results.mixed_random {
+        Some(mixed) => {
+            display_test_result_enhanced("Mixed Read", &mixed.read, use_colors);
+            display_test_result_enhanced("Mixed Write", &mixed.write, use_colors);
+            display_test_result_enhanced("Mixed Combined", &mixed.combined, use_colors);
+        }
+        None => display_optional_test_result("Mixed Random", None, use_colors),
+    }
+    display_optional_test_result("Sync Write", results.sync_write.as_ref(), use_colors);
+    // Only selected explicitly, so these are left out rather than shown as not run
+    if let Some(small_files) = &results.small_files {
+        display_test_result_enhanced("Small File Write", &small_files.write, use_colors);
+        display_test_result_enhanced("Small File Read", &small_files.read, use_colors);
+    }
+    display_optional_test_result("Memory Copy", results.memory_copy.as_ref(), use_colors);
+    if let Some(stream) = &results.memory_stream {
+        for (name, result) in stream_rows(stream) {
+            display_test_result_enhanced(name, result, use_colors);
+        }
+    }
+    if let Some(sustained) = &results.sustained_write {
+        display_test_result_enhanced("Sustained Write", &sustained.result, use_colors);
+    }
 
     println!();
 
-    // Add summary information
-    let avg_sequential =
-        (results.sequential_write.avg_speed_mbps + results.sequential_read.avg_speed_mbps) / 2.0;
-    let avg_random =
-        (results.random_write.avg_speed_mbps + results.random_read.avg_speed_mbps) / 2.0;
+    display_latency_table(results, use_colors, &separator);
+
+    // Add summary information, averaging only the tests that ran
+    let avg_sequential = average_speed(&[
+        results.sequential_write.as_ref(),
+        results.sequential_read.as_ref(),
+    ]);
+    let avg_random = average_speed(&[results.random_write.as_ref(), results.random_read.as_ref()]);
+    let format_average = |average: Option<f64>| match average {
+        Some(speed) => format!("{:.2} MB/s", speed),
+        None => NOT_RUN.to_string(),
+    };
 
     println!("{}", colorize("Summary:", "1;36"));
-    println!("  Sequential Average: {:.2} MB/s", avg_sequential);
-    println!("  Random Average: {:.2} MB/s", avg_random);
+    println!("  Sequential Average: {}", format_average(avg_sequential));
+    println!("  Random Average: {}", format_average(avg_random));
+    if let Some(mixed) = &results.mixed_random {
+        println!(
+            "  Mixed Random ({}% read): {:.2} MB/s",
+            mixed.read_percentage, mixed.combined.avg_speed_mbps
+        );
+    }
+    if let Some(sync_write) = &results.sync_write {
+        println!(
+            "  Sync Write: {:.0} commits/s, p99 commit latency {}",
+            sync_write.latency.iops,
+            format_latency(sync_write.latency.p99_us)
+        );
+    }
+    if let Some(metadata) = &results.metadata {
+        display_metadata(metadata);
+    }
+    if let Some(small_files) = &results.small_files {
+        display_small_files(small_files);
+    }
     println!(
-        "  Memory Bandwidth: {:.2} MB/s",
-        results.memory_copy.avg_speed_mbps
+        "  Memory Bandwidth: {}",
+        format_average(results.memory_copy.as_ref().map(|r| r.avg_speed_mbps))
     );
-
-    // Performance indicators
-    if avg_sequential > 500.0 {
+    if let Some(curve) = results
+        .memory_curve
+        .as_ref()
+        .filter(|curve| curve.len() > 1)
+    {
+        display_memory_curve(curve);
+    }
+    if let Some(stream) = &results.memory_stream {
+        display_memory_stream(stream);
+    }
+    if let Some(sustained) = &results.sustained_write {
+        display_sustained_write(sustained, &colorize);
+    }
+    if let Some(preparation) = &results.preparation {
         println!(
-            "  {} Excellent sequential performance (SSD-class)",
-            colorize("✓", "1;32")
+            "  Preparation: {:.1}s ({:.2} MB/s)",
+            preparation.test_duration.as_secs_f64(),
+            preparation.avg_speed_mbps
         );
-    } else if avg_sequential > 100.0 {
-        println!("  {} Good sequential performance", colorize("✓", "1;33"));
-    } else {
+    }
+    println!("  I/O Backend: {}", results.io_backend);
+    // Tests end after their duration unless a byte, operation or pass limit is set
+    for (name, result) in [
+        ("Sequential Write", results.sequential_write.as_ref()),
+        ("Sequential Read", results.sequential_read.as_ref()),
+        ("Random Write", results.random_write.as_ref()),
+        ("Random Read", results.random_read.as_ref()),
+        (
+            "Mixed Random",
+            results.mixed_random.as_ref().map(|m| &m.combined),
+        ),
+        ("Sync Write", results.sync_write.as_ref()),
+        ("Memory Copy", results.memory_copy.as_ref()),
+        (
+            "Sustained Write",
+            results.sustained_write.as_ref().map(|s| &s.result),
+        ),
+    ] {
+        if let Some(result) = result.filter(|r| r.stop_reason != StopReason::Duration) {
+            println!("  {} stopped by: {}", name, result.stop_reason);
+        }
+    }
+    if results.interrupted {
         println!(
-            "  {} Consider upgrading storage for better performance",
-            colorize("ℹ", "1;34")
+            "  {} Interrupted: unfinished tests are shown as {}",
+            colorize("⚠", "1;33"),
+            NOT_RUN
         );
     }
+    display_corruption(results, &colorize);
+
+    // Performance indicators
+    if let Some(avg_sequential) = avg_sequential {
+        if avg_sequential > 500.0 {
+            println!(
+                "  {} Excellent sequential performance (SSD-class)",
+                colorize("✓", "1;32")
+            );
+        } else if avg_sequential > 100.0 {
+            println!("  {} Good sequential performance", colorize("✓", "1;33"));
+        } else {
+            println!(
+                "  {} Consider upgrading storage for better performance",
+                colorize("ℹ", "1;34")
+            );
+        }
+    }
+
+    if let Some(avg_random) = avg_random {
+        if avg_random > 50.0 {
+            println!(
+                "  {} Excellent random access performance",
+                colorize("✓", "1;32")
+            );
+        } else if avg_random > 10.0 {
+            println!(
+                "  {} Moderate random access performance",
+                colorize("✓", "1;33")
+            );
+        }
+    }
+
+    println!();
+}
+
+/// Marker shown in place of results for tests that were not selected
+const NOT_RUN: &str = "not run";
+
+/// Rows of an aggregated results table, with `None` for tests that were not selected
+fn aggregated_rows(results: &AggregatedResults) -> Vec<(&'static str, Option<&RunStatistics>)> {
+    let mut rows = vec![
+        ("Sequential Write", results.sequential_write.as_ref()),
+        ("Sequential Read", results.sequential_read.as_ref()),
+        ("Random Write", results.random_write.as_ref()),
+        ("Random Read", results.random_read.as_ref()),
+    ];
+    match &results.mixed_random {
+        Some(mixed) => {
+            rows.push(("Mixed Read", Some(&mixed.read)));
+            rows.push(("Mixed Write", Some(&mixed.write)));
+            rows.push(("Mixed Combined", Some(&mixed.combined)));
+        }
+        None => rows.push(("Mixed Random", None)),
+    }
+    rows.push(("Sync Write", results.sync_write.as_ref()));
+    rows.push(("Memory Copy", results.memory_copy.as_ref()));
+    if let Some(sustained) = &results.sustained_write {
+        rows.push(("Sustained Write", Some(sustained)));
+    }
+    rows
+}
+
+/// Results of the tests that ran, in display order, with the preparation last
+fn completed_results(results: &BenchmarkResults) -> Vec<(&'static str, &TestResult)> {
+    let mut rows = vec![
+        ("Sequential Write", results.sequential_write.as_ref()),
+        ("Sequential Read", results.sequential_read.as_ref()),
+        ("Random Write", results.random_write.as_ref()),
+        ("Random Read", results.random_read.as_ref()),
+    ];
+    if let Some(mixed) = &results.mixed_random {
+        rows.push(("Mixed Read", Some(&mixed.read)));
+        rows.push(("Mixed Write", Some(&mixed.write)));
+        rows.push(("Mixed Combined", Some(&mixed.combined)));
+    }
+    rows.push(("Sync Write", results.sync_write.as_ref()));
+    if let Some(small_files) = &results.small_files {
+        rows.push(("Small File Write", Some(&small_files.write)));
+        rows.push(("Small File Read", Some(&small_files.read)));
+    }
+    rows.push(("Memory Copy", results.memory_copy.as_ref()));
+    if let Some(stream) = &results.memory_stream {
+        rows.extend(stream_rows(stream).map(|(name, result)| (name, Some(result))));
+    }
+    rows.push((
+        "Sustained Write",
+        results
+            .sustained_write
+            .as_ref()
+            .map(|sustained| &sustained.result),
+    ));
+    rows.push(("Preparation", results.preparation.as_ref()));
+    rows.into_iter()
+        .filter_map(|(name, result)| result.map(|result| (name, result)))
+        .collect()
+}
+
+/// Display the results of repeated runs as "avg ± stddev" per test
+fn display_aggregated_table(results: &AggregatedResults) {
+    let use_colors = atty::is(atty::Stream::Stdout);
+
+    let colorize = |text: &str, color_code: &str| -> String {
+        if use_colors {
+            format!("\x1b[{}m{}\x1b[0m", color_code, text)
+        } else {
+            text.to_string()
+        }
+    };
+
+    println!(
+        "\n{}",
+        colorize(
+            &format!("AGGREGATED RESULTS ({} runs)", results.runs.len()),
+            "1;36"
+        )
+    );
+    println!();
+
+    println!(
+        "{:<20} {:>22} {:>14} {:>24} {:>6}",
+        colorize("Test", "1;37"),
+        colorize("Avg ± StdDev (MB/s)", "1;33"), // Bold yellow for the average
+        colorize("Median (MB/s)", "37"),
+        colorize("95% CI (MB/s)", "37"),
+        colorize("Runs", "37")
+    );
 
-    if avg_random > 50.0 {
+    let separator = if use_colors {
+        colorize(&"─".repeat(90), "37")
+    } else {
+        "-".repeat(90)
+    };
+    println!("{}", separator);
+
+    for (name, stats) in aggregated_rows(results) {
+        match stats {
+            Some(stats) => {
+                let avg = format!("{:.2} ± {:.2}", stats.mean_mbps, stats.stddev_mbps);
+                let interval = format!("{:.2} – {:.2}", stats.ci95_low_mbps, stats.ci95_high_mbps);
+                println!(
+                    "{:<20} {} {:>14.2} {:>24} {:>6}",
+                    name,
+                    colorize(&format!("{:>22}", avg), "1"),
+                    stats.median_mbps,
+                    interval,
+                    stats.runs
+                );
+            }
+            None => println!("{:<20} {:>22}", name, NOT_RUN),
+        }
+    }
+
+    println!();
+    if let Some(mixed) = &results.mixed_random {
+        println!("  Mixed Random: {}% read", mixed.read_percentage);
+    }
+    println!("  I/O Backend: {}", results.io_backend);
+    if results.interrupted {
+        println!("  Interrupted during run {}", results.runs.len());
+    }
+    for run in &results.runs {
+        display_corruption(run, &colorize);
+    }
+    println!();
+}
+
+/// Tests whose reads were verified, with the result that holds their corruption
+fn verified_reads(results: &BenchmarkResults) -> [(&'static str, Option<&TestResult>); 2] {
+    [
+        ("Sequential Read", results.sequential_read.as_ref()),
+        ("Random Read", results.random_read.as_ref()),
+    ]
+}
+
+/// Corruption events of a run, with the name of the test that found them
+fn corruption_events(
+    results: &BenchmarkResults,
+) -> impl Iterator<Item = (&'static str, &CorruptionEvent)> {
+    verified_reads(results)
+        .into_iter()
+        .filter_map(|(name, result)| result.map(|result| (name, result)))
+        .flat_map(|(name, result)| result.corruption_events.iter().map(move |e| (name, e)))
+}
+
+/// Show the corrupted sectors `--verify` found, listing the first few offsets
+fn display_corruption(results: &BenchmarkResults, colorize: &dyn Fn(&str, &str) -> String) {
+    const SHOWN_EVENTS: usize = 5;
+
+    for (name, result) in verified_reads(results) {
+        let Some(result) = result.filter(|r| r.has_corruption()) else {
+            continue;
+        };
         println!(
-            "  {} Excellent random access performance",
-            colorize("✓", "1;32")
+            "  {} {}: {} corrupted sectors",
+            colorize("✗", "1;31"),
+            name,
+            result.corrupted_sectors
         );
-    } else if avg_random > 10.0 {
+        for event in result.corruption_events.iter().take(SHOWN_EVENTS) {
+            println!("      offset {}: {}", event.offset, event.kind);
+        }
+        if result.corrupted_sectors > SHOWN_EVENTS as u64 {
+            println!(
+                "      ... {} more",
+                result.corrupted_sectors - SHOWN_EVENTS as u64
+            );
+        }
+    }
+}
+
+/// Rows of the metadata test's operations, named after the test and the operation
+fn metadata_rows(metadata: &MetadataResult) -> impl Iterator<Item = (&'static str, &TestResult)> {
+    metadata.results().map(|(operation, result)| {
+        let name = match operation {
+            MetadataOperation::Create => "Metadata Create",
+            MetadataOperation::Stat => "Metadata Stat",
+            MetadataOperation::Rename => "Metadata Rename",
+            MetadataOperation::List => "Metadata List",
+            MetadataOperation::Delete => "Metadata Delete",
+        };
+        (name, result)
+    })
+}
+
+/// Show the rate of each metadata operation
+fn display_metadata(metadata: &MetadataResult) {
+    let rates: Vec<String> = metadata
+        .results()
+        .map(|(operation, result)| {
+            format!(
+                "{} {:.0}/s",
+                operation.to_string().to_lowercase(),
+                result.latency.iops
+            )
+        })
+        .collect();
+    println!(
+        "  Metadata ({} files in {} directories): {}",
+        metadata.file_count,
+        metadata.directory_count,
+        rates.join(", ")
+    );
+}
+
+/// Show the file rates and throughput of the small-file test
+fn display_small_files(small_files: &SmallFilesResult) {
+    println!(
+        "  Small Files ({} files): write {:.0} files/s ({:.2} MB/s), read {:.0} files/s ({:.2} MB/s), open p99 {}",
+        small_files.file_count,
+        small_files.write_files_per_second(),
+        small_files.write.avg_speed_mbps,
+        small_files.read_files_per_second(),
+        small_files.read.avg_speed_mbps,
+        format_latency(small_files.open_latency.p99_us)
+    );
+}
+
+/// Rows of the memory stream test's kernels
+fn stream_rows(stream: &MemoryStreamResult) -> impl Iterator<Item = (&'static str, &TestResult)> {
+    stream
+        .results()
+        .map(|(kernel, result)| (kernel.test_name(), result))
+}
+
+/// Show the bandwidth of each memory stream kernel
+fn display_memory_stream(stream: &MemoryStreamResult) {
+    let speeds: Vec<String> = stream
+        .results()
+        .map(|(kernel, result)| format!("{} {:.2} MB/s", kernel, result.avg_speed_mbps))
+        .collect();
+    println!(
+        "  Memory Stream ({} threads): {}",
+        stream.threads,
+        speeds.join(", ")
+    );
+}
+
+/// Show the memory copy bandwidth at each working set, where cache levels show as steps
+fn display_memory_curve(curve: &[MemoryBandwidthPoint]) {
+    for point in curve {
         println!(
-            "  {} Moderate random access performance",
-            colorize("✓", "1;33")
+            "      {:>10}: {:>10.2} MB/s",
+            format_file_size(point.working_set as usize),
+            point.result.avg_speed_mbps
         );
     }
+}
+
+/// Show how the sustained write's speed changed over time, phase by phase
+fn display_sustained_write(
+    sustained: &SustainedWriteResult,
+    colorize: &dyn Fn(&str, &str) -> String,
+) {
+    match (sustained.bytes_before_drop, sustained.time_before_drop) {
+        (Some(bytes), Some(time)) => println!(
+            "  Sustained Write: {:.2} MB/s for {:.2} GB ({:.1}s), then {} {}",
+            sustained.fast_speed_mbps,
+            bytes as f64 / (1024.0 * 1024.0 * 1024.0),
+            time.as_secs_f64(),
+            colorize(&format!("{:.2} MB/s", sustained.steady_speed_mbps), "1;33"),
+            colorize("(speed dropped)", "1;33")
+        ),
+        _ => println!(
+            "  Sustained Write: {:.2} MB/s throughout, no drop detected",
+            sustained.fast_speed_mbps
+        ),
+    }
+    if sustained.phases.len() > 1 {
+        for phase in &sustained.phases {
+            println!(
+                "      {:>7.1}s - {:>7.1}s: {:>10.2} MB/s ({:.2} GB)",
+                phase.start.as_secs_f64(),
+                phase.end.as_secs_f64(),
+                phase.speed_mbps,
+                phase.bytes() as f64 / (1024.0 * 1024.0 * 1024.0)
+            );
+        }
+    }
+}
+
+/// Latency of a test that may not have run
+fn latency_of(result: Option<&TestResult>) -> Option<&LatencyStats> {
+    result.map(|result| &result.latency)
+}
+
+/// Display IOPS and per-operation latency of every test
+fn display_latency_table(results: &BenchmarkResults, use_colors: bool, separator: &str) {
+    let bold = |text: &str| -> String {
+        if use_colors {
+            format!("\x1b[1;37m{}\x1b[0m", text)
+        } else {
+            text.to_string()
+        }
+    };
+
+    println!(
+        "{:<20} {:>12} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        bold("Latency"),
+        "IOPS",
+        "Mean",
+        "p50",
+        "p90",
+        "p99",
+        "p99.9",
+        "Max"
+    );
+    println!("{}", separator);
+
+    let mut rows: Vec<(&str, Option<&LatencyStats>)> = vec![
+        (
+            "Sequential Write",
+            latency_of(results.sequential_write.as_ref()),
+        ),
+        (
+            "Sequential Read",
+            latency_of(results.sequential_read.as_ref()),
+        ),
+        ("Random Write", latency_of(results.random_write.as_ref())),
+        ("Random Read", latency_of(results.random_read.as_ref())),
+    ];
+    match &results.mixed_random {
+        Some(mixed) => {
+            rows.push(("Mixed Read", Some(&mixed.read.latency)));
+            rows.push(("Mixed Write", Some(&mixed.write.latency)));
+            rows.push(("Mixed Combined", Some(&mixed.combined.latency)));
+        }
+        None => rows.push(("Mixed Random", None)),
+    }
+    rows.push(("Sync Write", latency_of(results.sync_write.as_ref())));
+    if let Some(metadata) = &results.metadata {
+        rows.extend(metadata_rows(metadata).map(|(name, result)| (name, Some(&result.latency))));
+    }
+    // Operations of the small-file test are whole files; opens are timed on their own
+    if let Some(small_files) = &results.small_files {
+        rows.push(("Small File Write", Some(&small_files.write.latency)));
+        rows.push(("Small File Read", Some(&small_files.read.latency)));
+        rows.push(("Small File Open", Some(&small_files.open_latency)));
+    }
+    rows.push(("Memory Copy", latency_of(results.memory_copy.as_ref())));
+    if let Some(stream) = &results.memory_stream {
+        rows.extend(stream_rows(stream).map(|(name, result)| (name, Some(&result.latency))));
+    }
+    if let Some(sustained) = &results.sustained_write {
+        rows.push(("Sustained Write", Some(&sustained.result.latency)));
+    }
+
+    for (name, latency) in rows {
+        match latency {
+            Some(latency) => {
+                println!(
+                    "{:<20} {:>12.0} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+                    name,
+                    latency.iops,
+                    format_latency(latency.mean_us),
+                    format_latency(latency.p50_us),
+                    format_latency(latency.p90_us),
+                    format_latency(latency.p99_us),
+                    format_latency(latency.p999_us),
+                    format_latency(latency.max_us)
+                );
+            }
+            None => println!("{:<20} {:>12}", name, NOT_RUN),
+        }
+    }
 
     println!();
 }
 
+/// Format a latency given in microseconds with a unit suited to its magnitude
+pub fn format_latency(micros: f64) -> String {
+    if micros < 1.0 {
+        format!("{:.0} ns", micros * 1_000.0)
+    } else if micros < 1_000.0 {
+        format!("{:.1} µs", micros)
+    } else if micros < 1_000_000.0 {
+        format!("{:.2} ms", micros / 1_000.0)
+    } else {
+        format!("{:.2} s", micros / 1_000_000.0)
+    }
+}
+
+/// Average speed of the tests that ran, or `None` if none of them did
+fn average_speed(results: &[Option<&TestResult>]) -> Option<f64> {
+    let speeds: Vec<f64> = results.iter().flatten().map(|r| r.avg_speed_mbps).collect();
+    if speeds.is_empty() {
+        None
+    } else {
+        Some(speeds.iter().sum::<f64>() / speeds.len() as f64)
+    }
+}
+
+/// Display a test result row, or a "not run" row for unselected tests
+pub fn display_optional_test_result(
+    test_name: &str,
+    result: Option<&TestResult>,
+    use_colors: bool,
+) {
+    match result {
+        Some(result) => display_test_result_enhanced(test_name, result, use_colors),
+        None => {
+            let name = if use_colors {
+                format!("\x1b[1;90m{}\x1b[0m", test_name) // Dark gray like failed tests
+            } else {
+                test_name.to_string()
+            };
+            println!("{:<30} {:>12}", name, NOT_RUN);
+        }
+    }
+}
+
 /// Display a single test result row with enhanced formatting
 pub fn display_test_result_enhanced(test_name: &str, result: &TestResult, use_colors: bool) {
     let colorize = |text: &str, color_code: &str| -> String {
@@ -512,11 +1235,12 @@ pub fn display_test_result_enhanced(test_name: &str, result: &TestResult, use_co
     let duration_str = format_duration(result.test_duration);
 
     println!(
-        "{:<30} {:>12.2} {:>12.2} {} {:>10} {:>8}",
+        "{:<30} {:>12.2} {:>12.2} {} {:>12.2} {:>10} {:>8}",
         colored_test_name,
         result.min_speed_mbps,
         result.max_speed_mbps,
         bold_avg,
+        result.sample_avg_speed_mbps,
         duration_str,
         result.sample_count
     );
@@ -530,109 +1254,533 @@ pub fn display_test_result(test_name: &str, result: &TestResult) {
 
 /// Format benchmark results as JSON with additional metadata
 pub fn format_results_json(results: &BenchmarkResults) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&results_json(results))
+}
+
+/// Build the JSON document of a single benchmark run
+fn results_json(results: &BenchmarkResults) -> serde_json::Value {
     use serde_json::json;
     use std::time::SystemTime;
 
-    // Create enhanced JSON with metadata
-    let enhanced_results = json!({
+    let test_json = |result: Option<&TestResult>| match result {
+        Some(result) => {
+            let mut value = json!({
+                "status": "completed",
+                "min_speed_mbps": result.min_speed_mbps,
+                "max_speed_mbps": result.max_speed_mbps,
+                "avg_speed_mbps": result.avg_speed_mbps,
+                "duration_seconds": result.test_duration.as_secs_f64(),
+                "sample_count": result.sample_count,
+                "sample_avg_speed_mbps": result.sample_avg_speed_mbps,
+                "total_bytes": result.total_bytes,
+                "total_operations": result.total_operations,
+                "stop_reason": result.stop_reason,
+                "corrupted_sectors": result.corrupted_sectors,
+                "corruption_events": result.corruption_events,
+                "latency": {
+                    "operations": result.latency.operations,
+                    "iops": result.latency.iops,
+                    "mean_us": result.latency.mean_us,
+                    "p50_us": result.latency.p50_us,
+                    "p90_us": result.latency.p90_us,
+                    "p99_us": result.latency.p99_us,
+                    "p999_us": result.latency.p999_us,
+                    "max_us": result.latency.max_us
+                }
+            });
+            // Only kept when requested with `record_time_series`
+            if !result.time_series.is_empty() {
+                value["time_series"] = result
+                    .time_series
+                    .iter()
+                    .map(|sample| {
+                        json!({
+                            "elapsed_seconds": sample.elapsed.as_secs_f64(),
+                            "bytes": sample.bytes,
+                            "operations": sample.operations,
+                            "speed_mbps": sample.speed_mbps,
+                            "mean_latency_us": sample.mean_latency_us
+                        })
+                    })
+                    .collect();
+            }
+            value
+        }
+        None => json!({ "status": NOT_RUN }),
+    };
+
+    let mixed_random = match &results.mixed_random {
+        Some(mixed) => json!({
+            "status": "completed",
+            "read_percentage": mixed.read_percentage,
+            "read": test_json(Some(&mixed.read)),
+            "write": test_json(Some(&mixed.write)),
+            "combined": test_json(Some(&mixed.combined))
+        }),
+        None => json!({ "status": NOT_RUN }),
+    };
+
+    let sync_write = match &results.sync_write {
+        Some(sync_write) => {
+            let mut value = test_json(Some(sync_write));
+            value["commits_per_second"] = json!(sync_write.latency.iops);
+            value
+        }
+        None => json!({ "status": NOT_RUN }),
+    };
+
+    let metadata = match &results.metadata {
+        Some(metadata) => {
+            let mut value = json!({
+                "status": "completed",
+                "file_count": metadata.file_count,
+                "directory_count": metadata.directory_count
+            });
+            for (operation, result) in metadata.results() {
+                let mut operation_value = test_json(Some(result));
+                operation_value["ops_per_second"] = json!(result.latency.iops);
+                value[operation.to_string().to_lowercase()] = operation_value;
+            }
+            value
+        }
+        None => json!({ "status": NOT_RUN }),
+    };
+
+    let small_files = match &results.small_files {
+        Some(small_files) => {
+            let mut write = test_json(Some(&small_files.write));
+            write["files_per_second"] = json!(small_files.write_files_per_second());
+            let mut read = test_json(Some(&small_files.read));
+            read["files_per_second"] = json!(small_files.read_files_per_second());
+            let open = &small_files.open_latency;
+            json!({
+                "status": "completed",
+                "file_count": small_files.file_count,
+                "directory_count": small_files.directory_count,
+                "write": write,
+                "read": read,
+                "open_latency": {
+                    "operations": open.operations,
+                    "mean_us": open.mean_us,
+                    "p50_us": open.p50_us,
+                    "p90_us": open.p90_us,
+                    "p99_us": open.p99_us,
+                    "p999_us": open.p999_us,
+                    "max_us": open.max_us
+                }
+            })
+        }
+        None => json!({ "status": NOT_RUN }),
+    };
+
+    let sustained_write = match &results.sustained_write {
+        Some(sustained) => {
+            let mut value = test_json(Some(&sustained.result));
+            value["fast_speed_mbps"] = json!(sustained.fast_speed_mbps);
+            value["bytes_before_drop"] = json!(sustained.bytes_before_drop);
+            value["seconds_before_drop"] =
+                json!(sustained.time_before_drop.map(|time| time.as_secs_f64()));
+            value["steady_speed_mbps"] = json!(sustained.steady_speed_mbps);
+            value["phases"] = sustained
+                .phases
+                .iter()
+                .map(|phase| {
+                    json!({
+                        "start_seconds": phase.start.as_secs_f64(),
+                        "end_seconds": phase.end.as_secs_f64(),
+                        "start_bytes": phase.start_bytes,
+                        "end_bytes": phase.end_bytes,
+                        "speed_mbps": phase.speed_mbps
+                    })
+                })
+                .collect();
+            value["samples"] = sustained
+                .samples
+                .iter()
+                .map(|sample| {
+                    json!({
+                        "elapsed_seconds": sample.elapsed.as_secs_f64(),
+                        "bytes": sample.bytes,
+                        "speed_mbps": sample.speed_mbps
+                    })
+                })
+                .collect();
+            value
+        }
+        None => json!({ "status": NOT_RUN }),
+    };
+
+    // The bandwidth at each working set goes with the largest one's result
+    let mut memory_copy = test_json(results.memory_copy.as_ref());
+    if let Some(curve) = &results.memory_curve {
+        memory_copy["working_sets"] = curve
+            .iter()
+            .map(|point| {
+                json!({
+                    "working_set_bytes": point.working_set,
+                    "min_speed_mbps": point.result.min_speed_mbps,
+                    "max_speed_mbps": point.result.max_speed_mbps,
+                    "avg_speed_mbps": point.result.avg_speed_mbps,
+                    "duration_seconds": point.result.test_duration.as_secs_f64(),
+                    "total_bytes": point.result.total_bytes,
+                    "stop_reason": point.result.stop_reason
+                })
+            })
+            .collect();
+    }
+
+    let memory_stream = match &results.memory_stream {
+        Some(stream) => {
+            let mut value = json!({
+                "status": "completed",
+                "threads": stream.threads,
+                "array_size": stream.array_size
+            });
+            for (kernel, result) in stream.results() {
+                value[kernel.to_string().to_lowercase()] = test_json(Some(result));
+            }
+            value
+        }
+        None => json!({ "status": NOT_RUN }),
+    };
+
+    // Create enhanced JSON with metadata; summaries of tests that did not run are null
+    json!({
         "timestamp": SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs(),
         "version": env!("CARGO_PKG_VERSION"),
+        "io_backend": results.io_backend.to_string(),
+        "interrupted": results.interrupted,
+        "corrupted_sectors": results.corrupted_sectors(),
+        "preparation": results.preparation.as_ref().map(|r| test_json(Some(r))),
         "results": {
-            "sequential_write": {
-                "min_speed_mbps": results.sequential_write.min_speed_mbps,
-                "max_speed_mbps": results.sequential_write.max_speed_mbps,
-                "avg_speed_mbps": results.sequential_write.avg_speed_mbps,
-                "duration_seconds": results.sequential_write.test_duration.as_secs_f64(),
-                "sample_count": results.sequential_write.sample_count
-            },
-            "sequential_read": {
-                "min_speed_mbps": results.sequential_read.min_speed_mbps,
-                "max_speed_mbps": results.sequential_read.max_speed_mbps,
-                "avg_speed_mbps": results.sequential_read.avg_speed_mbps,
-                "duration_seconds": results.sequential_read.test_duration.as_secs_f64(),
-                "sample_count": results.sequential_read.sample_count
-            },
-            "random_write": {
-                "min_speed_mbps": results.random_write.min_speed_mbps,
-                "max_speed_mbps": results.random_write.max_speed_mbps,
-                "avg_speed_mbps": results.random_write.avg_speed_mbps,
-                "duration_seconds": results.random_write.test_duration.as_secs_f64(),
-                "sample_count": results.random_write.sample_count
-            },
-            "random_read": {
-                "min_speed_mbps": results.random_read.min_speed_mbps,
-                "max_speed_mbps": results.random_read.max_speed_mbps,
-                "avg_speed_mbps": results.random_read.avg_speed_mbps,
-                "duration_seconds": results.random_read.test_duration.as_secs_f64(),
-                "sample_count": results.random_read.sample_count
-            },
-            "memory_copy": {
-                "min_speed_mbps": results.memory_copy.min_speed_mbps,
-                "max_speed_mbps": results.memory_copy.max_speed_mbps,
-                "avg_speed_mbps": results.memory_copy.avg_speed_mbps,
-                "duration_seconds": results.memory_copy.test_duration.as_secs_f64(),
-                "sample_count": results.memory_copy.sample_count
-            }
+            "sequential_write": test_json(results.sequential_write.as_ref()),
+            "sequential_read": test_json(results.sequential_read.as_ref()),
+            "random_write": test_json(results.random_write.as_ref()),
+            "random_read": test_json(results.random_read.as_ref()),
+            "mixed_random": mixed_random,
+            "sync_write": sync_write,
+            "metadata": metadata,
+            "small_files": small_files,
+            "memory_copy": memory_copy,
+            "memory_stream": memory_stream,
+            "sustained_write": sustained_write
         },
         "summary": {
-            "sequential_avg": (results.sequential_write.avg_speed_mbps + results.sequential_read.avg_speed_mbps) / 2.0,
-            "random_avg": (results.random_write.avg_speed_mbps + results.random_read.avg_speed_mbps) / 2.0,
-            "memory_bandwidth": results.memory_copy.avg_speed_mbps
+            "sequential_avg": average_speed(&[
+                results.sequential_write.as_ref(),
+                results.sequential_read.as_ref(),
+            ]),
+            "random_avg": average_speed(&[
+                results.random_write.as_ref(),
+                results.random_read.as_ref(),
+            ]),
+            "mixed_avg": results.mixed_random.as_ref().map(|m| m.combined.avg_speed_mbps),
+            "memory_bandwidth": results.memory_copy.as_ref().map(|r| r.avg_speed_mbps)
         }
+    })
+}
+
+/// Format the results of repeated runs as JSON, including every run's results
+pub fn format_aggregated_json(results: &AggregatedResults) -> Result<String, serde_json::Error> {
+    use serde_json::json;
+    use std::time::SystemTime;
+
+    let stats_json = |stats: Option<&RunStatistics>| match stats {
+        Some(stats) => json!({
+            "status": "completed",
+            "runs": stats.runs,
+            "mean_mbps": stats.mean_mbps,
+            "median_mbps": stats.median_mbps,
+            "stddev_mbps": stats.stddev_mbps,
+            "ci95_low_mbps": stats.ci95_low_mbps,
+            "ci95_high_mbps": stats.ci95_high_mbps
+        }),
+        None => json!({ "status": NOT_RUN }),
+    };
+
+    let mixed_random = match &results.mixed_random {
+        Some(mixed) => json!({
+            "status": "completed",
+            "read_percentage": mixed.read_percentage,
+            "read": stats_json(Some(&mixed.read)),
+            "write": stats_json(Some(&mixed.write)),
+            "combined": stats_json(Some(&mixed.combined))
+        }),
+        None => json!({ "status": NOT_RUN }),
+    };
+
+    let aggregated = json!({
+        "timestamp": SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        "version": env!("CARGO_PKG_VERSION"),
+        "io_backend": results.io_backend.to_string(),
+        "repetitions": results.runs.len(),
+        "interrupted": results.interrupted,
+        "results": {
+            "sequential_write": stats_json(results.sequential_write.as_ref()),
+            "sequential_read": stats_json(results.sequential_read.as_ref()),
+            "random_write": stats_json(results.random_write.as_ref()),
+            "random_read": stats_json(results.random_read.as_ref()),
+            "mixed_random": mixed_random,
+            "sync_write": stats_json(results.sync_write.as_ref()),
+            "memory_copy": stats_json(results.memory_copy.as_ref()),
+            "sustained_write": stats_json(results.sustained_write.as_ref())
+        },
+        "runs": results.runs.iter().map(results_json).collect::<Vec<_>>()
     });
 
-    serde_json::to_string_pretty(&enhanced_results)
+    serde_json::to_string_pretty(&aggregated)
 }
 
 /// Format benchmark results as CSV with enhanced data
 pub fn format_results_csv(results: &BenchmarkResults) -> String {
     let mut csv = String::new();
 
-    // Enhanced CSV header with more information (P5 replaces Min)
-    csv.push_str("Test,P5 (MB/s),P95 (MB/s),Avg (MB/s),Duration (s),Samples\n");
+    // Enhanced CSV header with more information (P5 replaces Min), then latency and totals
+    csv.push_str(
+        "Test,P5 (MB/s),P95 (MB/s),Avg (MB/s),Duration (s),Samples,\
+         IOPS,Mean (us),P50 (us),P90 (us),P99 (us),P99.9 (us),Max (us),\
+         Sample Avg (MB/s),Total Bytes,Operations,Stop Reason,Corrupted Sectors\n",
+    );
 
     // Helper function to format a test result as CSV row
-    let format_test_csv = |name: &str, result: &TestResult| -> String {
-        format!(
-            "{},{:.2},{:.2},{:.2},{:.2},{}\n",
-            name,
-            result.min_speed_mbps,
-            result.max_speed_mbps,
-            result.avg_speed_mbps,
-            result.test_duration.as_secs_f64(),
-            result.sample_count
-        )
+    let format_test_csv = |name: &str, result: Option<&TestResult>| -> String {
+        match result {
+            Some(result) => format!(
+                "{},{:.2},{:.2},{:.2},{:.2},{},{:.1},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{},{},{},{}\n",
+                name,
+                result.min_speed_mbps,
+                result.max_speed_mbps,
+                result.avg_speed_mbps,
+                result.test_duration.as_secs_f64(),
+                result.sample_count,
+                result.latency.iops,
+                result.latency.mean_us,
+                result.latency.p50_us,
+                result.latency.p90_us,
+                result.latency.p99_us,
+                result.latency.p999_us,
+                result.latency.max_us,
+                result.sample_avg_speed_mbps,
+                result.total_bytes,
+                result.total_operations,
+                result.stop_reason,
+                result.corrupted_sectors
+            ),
+            None => format!("{},{},,,,,,,,,,,,,,,,\n", name, NOT_RUN),
+        }
+    };
+    let format_average_csv = |name: &str, average: Option<f64>| -> String {
+        match average {
+            Some(speed) => format!("{},,,{:.2},,,,,,,,,,,,,,\n", name, speed),
+            None => format!("{},,,{},,,,,,,,,,,,,,\n", name, NOT_RUN),
+        }
     };
 
     // Add each test result
     csv.push_str(&format_test_csv(
         "Sequential Write",
-        &results.sequential_write,
+        results.sequential_write.as_ref(),
     ));
     csv.push_str(&format_test_csv(
         "Sequential Read",
-        &results.sequential_read,
+        results.sequential_read.as_ref(),
+    ));
+    csv.push_str(&format_test_csv(
+        "Random Write",
+        results.random_write.as_ref(),
     ));
-    csv.push_str(&format_test_csv("Random Write", &results.random_write));
-    csv.push_str(&format_test_csv("Random Read", &results.random_read));
-    csv.push_str(&format_test_csv("Memory Copy", &results.memory_copy));
+    csv.push_str(&format_test_csv(
+        "Random Read",
+        results.random_read.as_ref(),
+    ));
+    match &results.mixed_random {
+        Some(mixed) => {
+            csv.push_str(&format_test_csv("Mixed Read", Some(&mixed.read)));
+            csv.push_str(&format_test_csv("Mixed Write", Some(&mixed.write)));
+            csv.push_str(&format_test_csv("Mixed Combined", Some(&mixed.combined)));
+        }
+        None => csv.push_str(&format_test_csv("Mixed Random", None)),
+    }
+    csv.push_str(&format_test_csv("Sync Write", results.sync_write.as_ref()));
+    if let Some(metadata) = &results.metadata {
+        for (name, result) in metadata_rows(metadata) {
+            csv.push_str(&format_test_csv(name, Some(result)));
+        }
+    }
+    if let Some(small_files) = &results.small_files {
+        csv.push_str(&format_test_csv(
+            "Small File Write",
+            Some(&small_files.write),
+        ));
+        csv.push_str(&format_test_csv("Small File Read", Some(&small_files.read)));
+    }
+    csv.push_str(&format_test_csv(
+        "Memory Copy",
+        results.memory_copy.as_ref(),
+    ));
+    if let Some(stream) = &results.memory_stream {
+        for (name, result) in stream_rows(stream) {
+            csv.push_str(&format_test_csv(name, Some(result)));
+        }
+    }
+    if let Some(sustained) = &results.sustained_write {
+        csv.push_str(&format_test_csv("Sustained Write", Some(&sustained.result)));
+    }
 
-    // Add summary row
-    let sequential_avg =
-        (results.sequential_write.avg_speed_mbps + results.sequential_read.avg_speed_mbps) / 2.0;
-    let random_avg =
-        (results.random_write.avg_speed_mbps + results.random_read.avg_speed_mbps) / 2.0;
+    // Add summary rows
+    let sequential_avg = average_speed(&[
+        results.sequential_write.as_ref(),
+        results.sequential_read.as_ref(),
+    ]);
+    let random_avg = average_speed(&[results.random_write.as_ref(), results.random_read.as_ref()]);
 
     csv.push_str("\n# Summary\n");
-    csv.push_str(&format!("Sequential Average,,,{:.2},,\n", sequential_avg));
-    csv.push_str(&format!("Random Average,,,{:.2},,\n", random_avg));
-    csv.push_str(&format!(
-        "Memory Bandwidth,,,{:.2},,\n",
-        results.memory_copy.avg_speed_mbps
+    csv.push_str(&format_average_csv("Sequential Average", sequential_avg));
+    csv.push_str(&format_average_csv("Random Average", random_avg));
+    csv.push_str(&format_average_csv(
+        "Memory Bandwidth",
+        results.memory_copy.as_ref().map(|r| r.avg_speed_mbps),
     ));
+    if let Some(preparation) = &results.preparation {
+        csv.push_str(&format_test_csv("Preparation", Some(preparation)));
+    }
+    if let Some(mixed) = &results.mixed_random {
+        csv.push_str(&format!(
+            "# Mixed Read Percentage: {}\n",
+            mixed.read_percentage
+        ));
+    }
+    if let Some(metadata) = &results.metadata {
+        csv.push_str(&format!(
+            "# Metadata: {} files in {} directories\n",
+            metadata.file_count, metadata.directory_count
+        ));
+    }
+    if let Some(small_files) = &results.small_files {
+        let open = &small_files.open_latency;
+        csv.push_str(&format!(
+            "# Small Files: {} files in {} directories, open latency mean {:.2} us, p50 {:.2} us, p99 {:.2} us, max {:.2} us\n",
+            small_files.file_count,
+            small_files.directory_count,
+            open.mean_us,
+            open.p50_us,
+            open.p99_us,
+            open.max_us
+        ));
+    }
+    for point in results.memory_curve.iter().flatten() {
+        csv.push_str(&format!(
+            "# Memory Working Set: {} bytes, {:.2} MB/s\n",
+            point.working_set, point.result.avg_speed_mbps
+        ));
+    }
+    if let Some(stream) = &results.memory_stream {
+        csv.push_str(&format!(
+            "# Memory Stream: {} threads, {} bytes per array\n",
+            stream.threads, stream.array_size
+        ));
+    }
+    if let Some(sustained) = &results.sustained_write {
+        csv.push_str(&format!(
+            "# Sustained Write: fast {:.2} MB/s, steady {:.2} MB/s, bytes before drop {}\n",
+            sustained.fast_speed_mbps,
+            sustained.steady_speed_mbps,
+            sustained
+                .bytes_before_drop
+                .map_or_else(|| "none".to_string(), |bytes| bytes.to_string())
+        ));
+        for phase in &sustained.phases {
+            csv.push_str(&format!(
+                "# Sustained Phase: {:.1}s-{:.1}s, {} bytes, {:.2} MB/s\n",
+                phase.start.as_secs_f64(),
+                phase.end.as_secs_f64(),
+                phase.bytes(),
+                phase.speed_mbps
+            ));
+        }
+    }
+    csv.push_str(&format!("# I/O Backend: {}\n", results.io_backend));
+    if results.interrupted {
+        csv.push_str("# Interrupted: true\n");
+    }
+    for event in corruption_events(results) {
+        csv.push_str(&format!(
+            "# Corruption: {} at offset {}: {}\n",
+            event.0, event.1.offset, event.1.kind
+        ));
+    }
+
+    // Per-interval samples follow as a second table, when they were recorded
+    let timed: Vec<_> = completed_results(results)
+        .into_iter()
+        .filter(|(_, result)| !result.time_series.is_empty())
+        .collect();
+    if !timed.is_empty() {
+        csv.push_str(
+            "\n# Time Series\n\
+             Test,Elapsed (s),Bytes,Operations,Throughput (MB/s),Mean Latency (us)\n",
+        );
+        for (name, result) in timed {
+            for sample in &result.time_series {
+                csv.push_str(&format!(
+                    "{},{:.3},{},{},{:.2},{}\n",
+                    name,
+                    sample.elapsed.as_secs_f64(),
+                    sample.bytes,
+                    sample.operations,
+                    sample.speed_mbps,
+                    sample
+                        .mean_latency_us
+                        .map_or_else(String::new, |latency| format!("{:.2}", latency))
+                ));
+            }
+        }
+    }
+
+    csv
+}
+
+/// Format the results of repeated runs as CSV, one row of run statistics per test
+pub fn format_aggregated_csv(results: &AggregatedResults) -> String {
+    let mut csv = String::new();
+
+    csv.push_str(
+        "Test,Mean (MB/s),StdDev (MB/s),Median (MB/s),CI95 Low (MB/s),CI95 High (MB/s),Runs\n",
+    );
+
+    for (name, stats) in aggregated_rows(results) {
+        match stats {
+            Some(stats) => csv.push_str(&format!(
+                "{},{:.2},{:.2},{:.2},{:.2},{:.2},{}\n",
+                name,
+                stats.mean_mbps,
+                stats.stddev_mbps,
+                stats.median_mbps,
+                stats.ci95_low_mbps,
+                stats.ci95_high_mbps,
+                stats.runs
+            )),
+            None => csv.push_str(&format!("{},{},,,,,\n", name, NOT_RUN)),
+        }
+    }
+
+    csv.push_str(&format!("\n# Repetitions: {}\n", results.runs.len()));
+    if let Some(mixed) = &results.mixed_random {
+        csv.push_str(&format!(
+            "# Mixed Read Percentage: {}\n",
+            mixed.read_percentage
+        ));
+    }
+    csv.push_str(&format!("# I/O Backend: {}\n", results.io_backend));
+    if results.interrupted {
+        csv.push_str("# Interrupted: true\n");
+    }
 
     csv
 }
@@ -5,7 +5,11 @@ mod tests {
     use super::super::args::OutputFormat;
     use super::super::display::*;
     use disk_speed_test::platform::PlatformError;
-    use disk_speed_test::{BenchmarkError, BenchmarkResults, ProgressCallback, TestResult};
+    use disk_speed_test::{
+        AggregatedResults, BenchmarkError, BenchmarkResults, IntervalSample, IoBackend,
+        LatencyStats, MemoryBandwidthPoint, MemoryStreamResult, MetadataResult, MixedTestResult,
+        ProgressCallback, SmallFilesResult, SustainedWriteResult, TestResult, ThroughputSample,
+    };
     use std::io;
     use std::path::PathBuf;
     use std::time::Duration;
@@ -13,64 +17,126 @@ mod tests {
     /// Create comprehensive test results with varied data
     fn create_comprehensive_test_results() -> BenchmarkResults {
         BenchmarkResults {
-            sequential_write: TestResult::new(
+            preparation: Some(TestResult::new(
+                61.4,  // P5
+                149.2, // P95
+                104.6, // avg
+                Duration::from_secs(10),
+                100, // samples
+            )),
+            sequential_write: Some(TestResult::new(
                 45.2,  // P5
                 156.8, // P95
                 98.7,  // avg
                 Duration::from_secs(12),
                 120, // samples
-            ),
-            sequential_read: TestResult::new(
+            )),
+            sequential_read: Some(TestResult::new(
                 52.1,  // P5
                 178.9, // P95
                 115.3, // avg
                 Duration::from_secs(11),
                 110, // samples
-            ),
-            random_write: TestResult::new(
+            )),
+            random_write: Some(TestResult::new(
                 8.5,  // P5
                 32.7, // P95
                 18.9, // avg
                 Duration::from_secs(10),
                 100, // samples
+            )),
+            random_read: Some(
+                TestResult::new(
+                    12.3, // P5
+                    45.6, // P95
+                    25.4, // avg
+                    Duration::from_secs(10),
+                    100, // samples
+                )
+                .with_sample_average(31.8)
+                .with_totals(266_338_304, 65_024)
+                .with_latency(LatencyStats {
+                    operations: 65_024,
+                    iops: 6502.4,
+                    mean_us: 153.2,
+                    p50_us: 120.5,
+                    p90_us: 240.1,
+                    p99_us: 870.4,
+                    p999_us: 2_310.0,
+                    max_us: 15_020.0,
+                }),
             ),
-            random_read: TestResult::new(
-                12.3, // P5
-                45.6, // P95
-                25.4, // avg
-                Duration::from_secs(10),
-                100, // samples
-            ),
-            memory_copy: TestResult::new(
+            mixed_random: Some(MixedTestResult {
+                read_percentage: 70,
+                read: TestResult::new(9.1, 30.2, 17.6, Duration::from_secs(10), 100),
+                write: TestResult::new(3.9, 13.0, 7.5, Duration::from_secs(10), 100),
+                combined: TestResult::new(13.0, 43.2, 25.1, Duration::from_secs(10), 100),
+            }),
+            memory_copy: Some(TestResult::new(
                 1250.0, // P5
                 2890.5, // P95
                 2156.7, // avg
                 Duration::from_secs(8),
                 80, // samples
-            ),
+            )),
+            sync_write: None,
+            metadata: None,
+            small_files: None,
+            memory_curve: None,
+            memory_stream: None,
+            sustained_write: None,
+            io_backend: IoBackend::Sync,
+            interrupted: false,
         }
     }
 
     /// Create test results with edge case values
     fn create_edge_case_test_results() -> BenchmarkResults {
         BenchmarkResults {
-            sequential_write: TestResult::new(0.001, 0.002, 0.0015, Duration::from_millis(100), 1),
-            sequential_read: TestResult::new(
+            preparation: None,
+            sequential_write: Some(TestResult::new(
+                0.001,
+                0.002,
+                0.0015,
+                Duration::from_millis(100),
+                1,
+            )),
+            sequential_read: Some(TestResult::new(
                 15000.0,
                 25000.0,
                 20000.0,
                 Duration::from_secs(3600),
                 50000,
-            ),
-            random_write: TestResult::new(0.0, 0.0, 0.0, Duration::from_secs(0), 0),
-            random_read: TestResult::new(
+            )),
+            random_write: Some(TestResult::new(0.0, 0.0, 0.0, Duration::from_secs(0), 0)),
+            random_read: Some(TestResult::new(
                 f64::INFINITY,
                 f64::NEG_INFINITY,
                 f64::NAN,
                 Duration::from_secs(1),
                 1,
-            ),
-            memory_copy: TestResult::new(999.99, 1000.01, 1000.0, Duration::from_millis(999), 999),
+            )),
+            mixed_random: Some(MixedTestResult {
+                read_percentage: 100,
+                read: TestResult::new(0.5, 1.5, 1.0, Duration::from_secs(1), 1),
+                write: TestResult::default(),
+                combined: TestResult::new(0.5, 1.5, 1.0, Duration::from_secs(1), 1),
+            }),
+            memory_copy: Some(TestResult::new(
+                999.99,
+                1000.01,
+                1000.0,
+                Duration::from_millis(999),
+                999,
+            )),
+            sync_write: None,
+            metadata: None,
+            small_files: None,
+            memory_curve: None,
+            memory_stream: None,
+            sustained_write: None,
+            io_backend: IoBackend::IoUring,
+            interrupted: false,
         }
     }
 
@@ -290,6 +356,33 @@ mod tests {
         // Verify JSON is valid
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert!(parsed.is_object());
+        assert_eq!(parsed["io_backend"], "sync");
+
+        // Verify the mixed workload is reported per direction
+        let mixed = &parsed["results"]["mixed_random"];
+        assert_eq!(mixed["read_percentage"], 70);
+        assert_eq!(mixed["read"]["avg_speed_mbps"], 17.6);
+        assert_eq!(mixed["write"]["avg_speed_mbps"], 7.5);
+        assert_eq!(mixed["combined"]["avg_speed_mbps"], 25.1);
+
+        // Verify per-operation latency is reported for each test
+        let latency = &parsed["results"]["random_read"]["latency"];
+        assert_eq!(latency["operations"], 65_024);
+        assert_eq!(latency["iops"], 6502.4);
+        assert_eq!(latency["p50_us"], 120.5);
+        assert_eq!(latency["p99_us"], 870.4);
+        assert_eq!(latency["p999_us"], 2310.0);
+        assert_eq!(latency["max_us"], 15020.0);
+        assert_eq!(parsed["results"]["memory_copy"]["latency"]["iops"], 0.0);
+
+        // The effective throughput is the headline; sample statistics are kept alongside
+        let random_read = &parsed["results"]["random_read"];
+        assert_eq!(random_read["avg_speed_mbps"], 25.4);
+        assert_eq!(random_read["sample_avg_speed_mbps"], 31.8);
+        assert_eq!(random_read["total_bytes"], 266_338_304u64);
+        assert_eq!(random_read["total_operations"], 65_024);
+        assert_eq!(parsed["summary"]["mixed_avg"], 25.1);
+        assert_eq!(parsed["preparation"]["avg_speed_mbps"], 104.6);
     }
 
     #[test]
@@ -304,12 +397,20 @@ mod tests {
 
         // Check header
         assert!(lines[0].contains("Test,P5 (MB/s),P95 (MB/s),Avg (MB/s),Duration (s),Samples"));
+        assert!(lines[0].contains("IOPS,Mean (us),P50 (us),P90 (us),P99 (us),P99.9 (us),Max (us)"));
+        assert!(lines[0]
+            .ends_with("Sample Avg (MB/s),Total Bytes,Operations,Stop Reason,Corrupted Sectors"));
 
         // Check data rows
         assert!(csv.contains("Sequential Write,45.20,156.80,98.70,12.00,120"));
         assert!(csv.contains("Sequential Read,52.10,178.90,115.30,11.00,110"));
         assert!(csv.contains("Random Write,8.50,32.70,18.90,10.00,100"));
-        assert!(csv.contains("Random Read,12.30,45.60,25.40,10.00,100"));
+        assert!(csv.contains(
+            "Random Read,12.30,45.60,25.40,10.00,100,6502.4,153.20,120.50,240.10,870.40,2310.00,15020.00,31.80,266338304,65024,duration"
+        ));
+        assert!(csv.contains("Mixed Read,9.10,30.20,17.60,10.00,100"));
+        assert!(csv.contains("Mixed Write,3.90,13.00,7.50,10.00,100"));
+        assert!(csv.contains("Mixed Combined,13.00,43.20,25.10,10.00,100"));
         assert!(csv.contains("Memory Copy,1250.00,2890.50,2156.70,8.00,80"));
 
         // Check summary section
@@ -317,6 +418,9 @@ mod tests {
         assert!(csv.contains("Sequential Average"));
         assert!(csv.contains("Random Average"));
         assert!(csv.contains("Memory Bandwidth"));
+        assert!(csv.contains("Preparation,61.40,149.20,104.60,10.00,100"));
+        assert!(csv.contains("# Mixed Read Percentage: 70"));
+        assert!(csv.contains("# I/O Backend: sync"));
     }
 
     #[test]
@@ -339,6 +443,7 @@ mod tests {
             },
             BenchmarkError::PermissionDenied(PathBuf::from("/restricted/path")),
             BenchmarkError::TestInterrupted("User cancelled".to_string()),
+            BenchmarkError::DataCorruption("3 corrupted sectors".to_string()),
         ];
 
         for error in errors {
@@ -386,11 +491,11 @@ mod tests {
         ];
 
         let test_results = [
-            &results.sequential_write,
-            &results.sequential_read,
-            &results.random_write,
-            &results.random_read,
-            &results.memory_copy,
+            results.sequential_write.as_ref().unwrap(),
+            results.sequential_read.as_ref().unwrap(),
+            results.random_write.as_ref().unwrap(),
+            results.random_read.as_ref().unwrap(),
+            results.memory_copy.as_ref().unwrap(),
         ];
 
         for (name, result) in test_names.iter().zip(test_results.iter()) {
@@ -414,17 +519,20 @@ mod tests {
         // Test with very small values
         callback.on_test_start("Tiny Speed Test");
         callback.on_progress("Tiny Speed Test", 0.001);
-        callback.on_test_complete("Tiny Speed Test", &results.sequential_write);
+        callback.on_test_complete(
+            "Tiny Speed Test",
+            results.sequential_write.as_ref().unwrap(),
+        );
 
         // Test with very large values
         callback.on_test_start("Huge Speed Test");
         callback.on_progress("Huge Speed Test", 20000.0);
-        callback.on_test_complete("Huge Speed Test", &results.sequential_read);
+        callback.on_test_complete("Huge Speed Test", results.sequential_read.as_ref().unwrap());
 
         // Test with zero/invalid values
         callback.on_test_start("Invalid Test");
         callback.on_progress("Invalid Test", f64::NAN);
-        callback.on_test_complete("Invalid Test", &results.random_read);
+        callback.on_test_complete("Invalid Test", results.random_read.as_ref().unwrap());
     }
 
     #[test]
@@ -459,6 +567,242 @@ mod tests {
         assert!(lines.len() > 5); // Header + 5 test rows + summary
     }
 
+    #[test]
+    fn test_sustained_write_output_formats() {
+        let mut results = create_comprehensive_test_results();
+        // 10 seconds at 1000 MB/s, then 10 seconds at 100 MB/s
+        let mut bytes = 0;
+        let samples: Vec<ThroughputSample> = (1..=20)
+            .map(|second| {
+                let speed_mbps = if second <= 10 { 1000.0 } else { 100.0 };
+                bytes += (speed_mbps * 1024.0 * 1024.0) as u64;
+                ThroughputSample {
+                    elapsed: Duration::from_secs(second),
+                    bytes,
+                    speed_mbps,
+                }
+            })
+            .collect();
+        let result = TestResult::new(100.0, 1000.0, 550.0, Duration::from_secs(20), 20);
+        results.sustained_write = Some(SustainedWriteResult::from_samples(result, samples));
+
+        assert!(display_results(&results, &OutputFormat::Table).is_ok());
+
+        let json = format_results_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let sustained = &parsed["results"]["sustained_write"];
+        assert!(sustained["bytes_before_drop"].as_u64().unwrap() > 0);
+        assert_eq!(sustained["samples"].as_array().unwrap().len(), 20);
+        assert_eq!(sustained["phases"].as_array().unwrap().len(), 2);
+
+        let csv = format_results_csv(&results);
+        assert!(csv.contains("Sustained Write,"));
+        assert_eq!(csv.matches("# Sustained Phase:").count(), 2);
+    }
+
+    #[test]
+    fn test_sync_write_output_formats() {
+        let mut results = create_comprehensive_test_results();
+        let mut result = TestResult::new(1.5, 2.5, 2.0, Duration::from_secs(10), 100);
+        result.latency.operations = 5000;
+        result.latency.iops = 500.0;
+        result.latency.p99_us = 4200.0;
+        results.sync_write = Some(result);
+
+        assert!(display_results(&results, &OutputFormat::Table).is_ok());
+
+        let json = format_results_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let sync_write = &parsed["results"]["sync_write"];
+        assert_eq!(sync_write["status"], "completed");
+        assert_eq!(sync_write["commits_per_second"], 500.0);
+
+        assert!(format_results_csv(&results).contains("Sync Write,"));
+    }
+
+    #[test]
+    fn test_metadata_output_formats() {
+        let mut results = create_comprehensive_test_results();
+        let operation = |operations: u64, iops: f64| {
+            let mut result = TestResult::new(0.0, 0.0, 0.0, Duration::from_secs(1), 0)
+                .with_totals(0, operations);
+            result.latency.operations = operations;
+            result.latency.iops = iops;
+            result
+        };
+        results.metadata = Some(MetadataResult {
+            file_count: 1000,
+            directory_count: 10,
+            create: operation(1000, 20000.0),
+            stat: operation(1000, 400000.0),
+            rename: operation(1000, 30000.0),
+            list: operation(10, 5000.0),
+            delete: operation(1000, 25000.0),
+        });
+
+        assert!(display_results(&results, &OutputFormat::Table).is_ok());
+
+        let json = format_results_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let metadata = &parsed["results"]["metadata"];
+        assert_eq!(metadata["status"], "completed");
+        assert_eq!(metadata["file_count"], 1000);
+        assert_eq!(metadata["stat"]["ops_per_second"], 400000.0);
+        assert_eq!(metadata["list"]["total_operations"], 10);
+
+        let csv = format_results_csv(&results);
+        for name in ["Create", "Stat", "Rename", "List", "Delete"] {
+            assert!(csv.contains(&format!("Metadata {},", name)));
+        }
+        assert!(csv.contains("# Metadata: 1000 files in 10 directories"));
+    }
+
+    #[test]
+    fn test_small_files_output_formats() {
+        let mut results = create_comprehensive_test_results();
+        let direction = |speed: f64, iops: f64| {
+            let mut result = TestResult::new(speed, speed, speed, Duration::from_secs(2), 1000)
+                .with_totals(80 * 1024 * 1024, 1000);
+            result.latency.operations = 1000;
+            result.latency.iops = iops;
+            result
+        };
+        results.small_files = Some(SmallFilesResult {
+            file_count: 1000,
+            directory_count: 10,
+            write: direction(40.0, 500.0),
+            read: direction(160.0, 2000.0),
+            open_latency: LatencyStats {
+                operations: 1000,
+                p99_us: 350.0,
+                ..LatencyStats::default()
+            },
+        });
+
+        assert!(display_results(&results, &OutputFormat::Table).is_ok());
+
+        let json = format_results_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let small_files = &parsed["results"]["small_files"];
+        assert_eq!(small_files["status"], "completed");
+        assert_eq!(small_files["directory_count"], 10);
+        assert_eq!(small_files["write"]["files_per_second"], 500.0);
+        assert_eq!(small_files["read"]["avg_speed_mbps"], 160.0);
+        assert_eq!(small_files["open_latency"]["p99_us"], 350.0);
+
+        let csv = format_results_csv(&results);
+        assert!(csv.contains("Small File Write,"));
+        assert!(csv.contains("Small File Read,"));
+        assert!(csv.contains("# Small Files: 1000 files in 10 directories"));
+    }
+
+    #[test]
+    fn test_memory_curve_output_formats() {
+        let mut results = create_comprehensive_test_results();
+        let point = |working_set: u64, speed: f64| MemoryBandwidthPoint {
+            working_set,
+            result: TestResult::new(speed, speed, speed, Duration::from_secs(1), 10),
+        };
+        results.memory_curve = Some(vec![
+            point(4 * 1024, 150000.0),
+            point(1024 * 1024, 40000.0),
+            point(512 * 1024 * 1024, 9000.0),
+        ]);
+
+        assert!(display_results(&results, &OutputFormat::Table).is_ok());
+
+        let json = format_results_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let working_sets = parsed["results"]["memory_copy"]["working_sets"]
+            .as_array()
+            .unwrap();
+        assert_eq!(working_sets.len(), 3);
+        assert_eq!(working_sets[0]["working_set_bytes"], 4096);
+        assert_eq!(working_sets[0]["avg_speed_mbps"], 150000.0);
+        assert_eq!(working_sets[2]["avg_speed_mbps"], 9000.0);
+
+        let csv = format_results_csv(&results);
+        assert!(csv.contains("# Memory Working Set: 4096 bytes, 150000.00 MB/s"));
+        assert!(csv.contains("# Memory Working Set: 536870912 bytes, 9000.00 MB/s"));
+    }
+
+    #[test]
+    fn test_memory_stream_output_formats() {
+        let mut results = create_comprehensive_test_results();
+        let json = format_results_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["results"]["memory_stream"]["status"], "not run");
+
+        let kernel = |speed: f64| TestResult::new(speed, speed, speed, Duration::from_secs(1), 10);
+        results.memory_stream = Some(MemoryStreamResult {
+            threads: 8,
+            array_size: 64 * 1024 * 1024,
+            copy: kernel(20000.0),
+            scale: kernel(19000.0),
+            add: kernel(22000.0),
+            triad: kernel(21000.0),
+        });
+
+        assert!(display_results(&results, &OutputFormat::Table).is_ok());
+
+        let json = format_results_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let stream = &parsed["results"]["memory_stream"];
+        assert_eq!(stream["status"], "completed");
+        assert_eq!(stream["threads"], 8);
+        assert_eq!(stream["array_size"], 64 * 1024 * 1024);
+        assert_eq!(stream["copy"]["avg_speed_mbps"], 20000.0);
+        assert_eq!(stream["triad"]["avg_speed_mbps"], 21000.0);
+
+        let csv = format_results_csv(&results);
+        assert!(csv.contains("Stream Scale,"));
+        assert!(csv.contains("Stream Triad,"));
+        assert!(csv.contains("# Memory Stream: 8 threads, 67108864 bytes per array"));
+    }
+
+    #[test]
+    fn test_time_series_output_formats() {
+        let mut results = create_comprehensive_test_results();
+        let json = format_results_json(&results).unwrap();
+        assert!(!json.contains("time_series"));
+        assert!(!format_results_csv(&results).contains("# Time Series"));
+
+        let series: Vec<IntervalSample> = (1..=3)
+            .map(|interval| IntervalSample {
+                elapsed: Duration::from_millis(100 * interval),
+                bytes: 1024 * 1024,
+                operations: 4,
+                speed_mbps: 10.0,
+                mean_latency_us: (interval < 3).then_some(250.0),
+            })
+            .collect();
+        let write = results.sequential_write.take().unwrap();
+        results.sequential_write = Some(write.with_time_series(series));
+
+        let json = format_results_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let samples = parsed["results"]["sequential_write"]["time_series"]
+            .as_array()
+            .unwrap();
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples[0]["operations"], 4);
+        assert_eq!(samples[0]["mean_latency_us"], 250.0);
+        assert!(samples[2]["mean_latency_us"].is_null());
+        assert!(parsed["results"]["sequential_read"]
+            .get("time_series")
+            .is_none());
+
+        let csv = format_results_csv(&results);
+        let series_csv = csv.split("# Time Series\n").nth(1).unwrap();
+        let rows: Vec<&str> = series_csv.lines().collect();
+        assert_eq!(
+            rows[0],
+            "Test,Elapsed (s),Bytes,Operations,Throughput (MB/s),Mean Latency (us)"
+        );
+        assert_eq!(rows[1], "Sequential Write,0.100,1048576,4,10.00,250.00");
+        assert_eq!(rows[3], "Sequential Write,0.300,1048576,4,10.00,");
+    }
+
     #[test]
     fn test_callback_with_unicode_test_names() {
         let callback = CliProgressCallback::new(OutputFormat::Table);
@@ -506,11 +850,21 @@ mod tests {
     #[test]
     fn test_display_results_with_all_zero_results() {
         let zero_results = BenchmarkResults {
-            sequential_write: TestResult::default(),
-            sequential_read: TestResult::default(),
-            random_write: TestResult::default(),
-            random_read: TestResult::default(),
-            memory_copy: TestResult::default(),
+            preparation: Some(TestResult::default()),
+            sequential_write: Some(TestResult::default()),
+            sequential_read: Some(TestResult::default()),
+            random_write: Some(TestResult::default()),
+            random_read: Some(TestResult::default()),
+            mixed_random: Some(MixedTestResult::default()),
+            memory_copy: Some(TestResult::default()),
+            sync_write: None,
+            metadata: None,
+            small_files: None,
+            memory_curve: None,
+            memory_stream: None,
+            sustained_write: None,
+            io_backend: IoBackend::Sync,
+            interrupted: false,
         };
 
         // Should handle all-zero results without panicking
@@ -524,6 +878,143 @@ mod tests {
         assert!(csv_result.is_ok());
     }
 
+    #[test]
+    fn test_format_results_marks_unselected_tests_not_run() {
+        let results = BenchmarkResults {
+            random_read: Some(TestResult::new(
+                12.3,
+                45.6,
+                25.4,
+                Duration::from_secs(10),
+                100,
+            )),
+            io_backend: IoBackend::Sync,
+            ..Default::default()
+        };
+
+        let json = format_results_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["results"]["random_read"]["status"], "completed");
+        assert_eq!(parsed["results"]["random_read"]["avg_speed_mbps"], 25.4);
+        assert_eq!(parsed["results"]["sequential_write"]["status"], "not run");
+        assert_eq!(parsed["results"]["mixed_random"]["status"], "not run");
+        assert_eq!(parsed["results"]["memory_copy"]["status"], "not run");
+        assert_eq!(parsed["summary"]["random_avg"], 25.4);
+        assert!(parsed["summary"]["sequential_avg"].is_null());
+        assert!(parsed["summary"]["memory_bandwidth"].is_null());
+        assert!(parsed["preparation"].is_null());
+
+        let csv = format_results_csv(&results);
+        assert!(csv.contains("Random Read,12.30,45.60,25.40,10.00,100"));
+        assert!(csv.contains("Sequential Write,not run,,,,"));
+        // Every row has as many columns as the header
+        let columns = csv.lines().next().unwrap().split(',').count();
+        assert!(csv
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .all(|line| line.split(',').count() == columns));
+        assert!(csv.contains("Mixed Random,not run,,,,"));
+        assert!(csv.contains("Memory Copy,not run,,,,"));
+        assert!(csv.contains("Sequential Average,,,not run,,"));
+        assert!(csv.contains("Random Average,,,25.40,,"));
+
+        assert!(display_results(&results, &OutputFormat::Table).is_ok());
+    }
+
+    #[test]
+    fn test_format_interrupted_results() {
+        let results = BenchmarkResults {
+            memory_copy: Some(TestResult::new(
+                900.0,
+                1100.0,
+                1000.0,
+                Duration::from_secs(10),
+                100,
+            )),
+            interrupted: true,
+            ..Default::default()
+        };
+
+        let json = format_results_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["interrupted"], true);
+        assert_eq!(parsed["results"]["memory_copy"]["status"], "completed");
+        assert_eq!(parsed["results"]["sequential_write"]["status"], "not run");
+
+        assert!(format_results_csv(&results).contains("# Interrupted: true"));
+        assert!(!format_results_csv(&BenchmarkResults::default()).contains("# Interrupted"));
+        assert!(display_results(&results, &OutputFormat::Table).is_ok());
+    }
+
+    #[test]
+    fn test_format_aggregated_results() {
+        let mut second_run = create_comprehensive_test_results();
+        second_run.random_read = Some(TestResult::new(
+            10.0,
+            40.0,
+            35.4,
+            Duration::from_secs(10),
+            100,
+        ));
+        let results =
+            AggregatedResults::from_runs(vec![create_comprehensive_test_results(), second_run]);
+
+        let json = format_aggregated_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["repetitions"], 2);
+        let random_read = &parsed["results"]["random_read"];
+        assert_eq!(random_read["status"], "completed");
+        assert_eq!(random_read["runs"], 2);
+        assert_eq!(random_read["mean_mbps"], 30.4);
+        assert_eq!(random_read["median_mbps"], 30.4);
+        assert!(random_read["stddev_mbps"].as_f64().unwrap() > 7.0);
+        assert!(
+            random_read["ci95_low_mbps"].as_f64().unwrap()
+                < random_read["ci95_high_mbps"].as_f64().unwrap()
+        );
+        assert_eq!(parsed["results"]["mixed_random"]["read_percentage"], 70);
+        assert_eq!(parsed["results"]["sequential_write"]["stddev_mbps"], 0.0);
+
+        // Every run keeps its own full results
+        let runs = parsed["runs"].as_array().unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1]["results"]["random_read"]["avg_speed_mbps"], 35.4);
+
+        let csv = format_aggregated_csv(&results);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Test,Mean (MB/s),StdDev (MB/s),Median (MB/s),CI95 Low (MB/s),CI95 High (MB/s),Runs"
+        );
+        assert!(csv.contains("Random Read,30.40,7.07,30.40,"));
+        assert!(csv.contains("Mixed Combined,25.10,0.00,25.10,25.10,25.10,2"));
+        assert!(csv.contains("# Repetitions: 2"));
+
+        assert!(display_aggregated_results(&results, &OutputFormat::Table).is_ok());
+    }
+
+    #[test]
+    fn test_format_aggregated_results_marks_unselected_tests_not_run() {
+        let results = AggregatedResults::from_runs(vec![BenchmarkResults {
+            memory_copy: Some(TestResult::new(1.0, 3.0, 2.0, Duration::from_secs(1), 1)),
+            ..Default::default()
+        }]);
+
+        let json = format_aggregated_json(&results).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["results"]["memory_copy"]["status"], "completed");
+        assert_eq!(parsed["results"]["random_read"]["status"], "not run");
+        assert_eq!(parsed["results"]["mixed_random"]["status"], "not run");
+
+        let csv = format_aggregated_csv(&results);
+        let columns = csv.lines().next().unwrap().split(',').count();
+        assert!(csv
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .all(|line| line.split(',').count() == columns));
+        assert!(csv.contains("Sequential Write,not run,"));
+    }
+
     #[test]
     fn test_error_display_with_unicode_paths() {
         let unicode_path = PathBuf::from("/测试/路径/文件.tmp");
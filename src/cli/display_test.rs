@@ -1,7 +1,10 @@
 //! Tests for CLI display and output formatting
 
 use super::*;
-use disk_speed_test::core::{BenchmarkResults, ProgressCallback, TestResult};
+use disk_speed_test::core::{
+    BenchmarkResults, IoBackend, MixedTestResult, ProgressCallback, ProgressUpdate, TestPhase,
+    TestResult,
+};
 use std::time::Duration;
 
 /// Create a sample test result for testing
@@ -18,11 +21,62 @@ fn create_sample_test_result() -> TestResult {
 /// Create sample benchmark results for testing
 fn create_sample_benchmark_results() -> BenchmarkResults {
     BenchmarkResults {
-        sequential_write: TestResult::new(80.0, 120.0, 100.0, Duration::from_secs(10), 100),
-        sequential_read: TestResult::new(90.0, 130.0, 110.0, Duration::from_secs(10), 100),
-        random_write: TestResult::new(15.0, 25.0, 20.0, Duration::from_secs(10), 100),
-        random_read: TestResult::new(18.0, 28.0, 23.0, Duration::from_secs(10), 100),
-        memory_copy: TestResult::new(800.0, 1200.0, 1000.0, Duration::from_secs(10), 100),
+        preparation: Some(TestResult::new(
+            70.0,
+            110.0,
+            95.0,
+            Duration::from_secs(11),
+            110,
+        )),
+        sequential_write: Some(TestResult::new(
+            80.0,
+            120.0,
+            100.0,
+            Duration::from_secs(10),
+            100,
+        )),
+        sequential_read: Some(TestResult::new(
+            90.0,
+            130.0,
+            110.0,
+            Duration::from_secs(10),
+            100,
+        )),
+        random_write: Some(TestResult::new(
+            15.0,
+            25.0,
+            20.0,
+            Duration::from_secs(10),
+            100,
+        )),
+        random_read: Some(TestResult::new(
+            18.0,
+            28.0,
+            23.0,
+            Duration::from_secs(10),
+            100,
+        )),
+        mixed_random: Some(MixedTestResult {
+            read_percentage: 70,
+            read: TestResult::new(10.0, 20.0, 15.0, Duration::from_secs(10), 100),
+            write: TestResult::new(4.0, 9.0, 6.5, Duration::from_secs(10), 100),
+            combined: TestResult::new(14.0, 29.0, 21.5, Duration::from_secs(10), 100),
+        }),
+        memory_copy: Some(TestResult::new(
+            800.0,
+            1200.0,
+            1000.0,
+            Duration::from_secs(10),
+            100,
+        )),
+        sync_write: None,
+        metadata: None,
+        small_files: None,
+        memory_curve: None,
+        memory_stream: None,
+        sustained_write: None,
+        io_backend: IoBackend::Sync,
+        interrupted: false,
     }
 }
 
@@ -60,6 +114,14 @@ fn test_format_duration() {
     );
 }
 
+#[test]
+fn test_format_latency() {
+    assert_eq!(display::format_latency(0.045), "45 ns");
+    assert_eq!(display::format_latency(87.25), "87.2 µs");
+    assert_eq!(display::format_latency(2_345.0), "2.35 ms");
+    assert_eq!(display::format_latency(1_500_000.0), "1.50 s");
+}
+
 #[test]
 fn test_colorize_functionality() {
     let callback = CliProgressCallback::new(OutputFormat::Table);
@@ -84,6 +146,7 @@ fn test_progress_callback_interface() {
 
     // These should not panic
     callback.on_test_start("Sequential Write");
+    callback.on_warmup("Sequential Write", Duration::from_secs(2));
     callback.on_progress("Sequential Write", 75.5);
     callback.on_test_complete("Sequential Write", &test_result);
 }
@@ -109,6 +172,42 @@ fn test_create_progress_bar() {
     assert!(bar_30.contains("]"));
 }
 
+#[test]
+fn test_progress_update_phases_and_warnings() {
+    let update = ProgressUpdate {
+        test_name: "Sequential Read".to_string(),
+        phase: TestPhase::Measure,
+        elapsed: Duration::from_secs(4),
+        total: Some(Duration::from_secs(10)),
+        fraction: 0.4,
+        eta: Some(Duration::from_secs(6)),
+        bytes: 400 * 1024 * 1024,
+        operations: 400,
+        current_speed_mbps: 105.0,
+        average_speed_mbps: 100.0,
+        iops: 100.0,
+    };
+
+    // These should not panic in any format
+    for format in [OutputFormat::Table, OutputFormat::Json, OutputFormat::Csv] {
+        let callback = CliProgressCallback::new_verbose(format);
+        callback.on_phase_change("Sequential Read", TestPhase::Measure);
+        callback.on_progress_update(&update);
+        callback.on_phase_change("Sequential Write", TestPhase::Flush);
+        callback.on_warning("Failed to sync filesystem");
+        callback.on_phase_change("Cleanup", TestPhase::Cleanup);
+    }
+
+    // The plain bar keeps its width whatever its fill
+    let mut callback = CliProgressCallback::new(OutputFormat::Table);
+    callback.use_colors = false;
+    assert_eq!(callback.create_progress_bar(0).len(), 22);
+    assert_eq!(
+        callback.create_progress_bar(8),
+        format!("[{}{}]", "=".repeat(8), " ".repeat(12))
+    );
+}
+
 #[test]
 fn test_extreme_values() {
     let _extreme_result = TestResult::new(
@@ -141,9 +240,12 @@ fn test_complete_display_workflow() {
     callback.on_progress("Sequential Write", 50.0);
     callback.on_progress("Sequential Write", 75.0);
     callback.on_progress("Sequential Write", 100.0);
-    callback.on_test_complete("Sequential Write", &results.sequential_write);
+    callback.on_test_complete(
+        "Sequential Write",
+        results.sequential_write.as_ref().unwrap(),
+    );
 
     callback.on_test_start("Sequential Read");
     callback.on_progress("Sequential Read", 60.0);
-    callback.on_test_complete("Sequential Read", &results.sequential_read);
+    callback.on_test_complete("Sequential Read", results.sequential_read.as_ref().unwrap());
 }
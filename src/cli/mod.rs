@@ -1,9 +1,16 @@
 //! Command-line interface for the disk speed test utility
 
 use anyhow::Result;
-use disk_speed_test::{run_benchmark, BenchmarkConfig};
+use disk_speed_test::core::tests::SYNC_WRITE_LOG_RECORDS;
+use disk_speed_test::platform::parse_cpu_list;
+use disk_speed_test::{
+    run_benchmark, run_benchmark_repeated, BenchmarkConfig, BenchmarkError, BenchmarkTest,
+    CancellationToken, FileSizeShare, IoBackend, IoEngineKind, SyncMethod, TestDataPattern,
+    TestLimit, ThreadPinning,
+};
 
 pub mod args;
+pub mod capacity;
 pub mod device_list;
 pub mod display;
 
@@ -19,7 +26,11 @@ mod display_comprehensive_test;
 #[cfg(test)]
 mod args_comprehensive_test;
 
-use args::{parse_size, Cli, Commands, OutputFormat};
+use args::{
+    parse_file_sizes, parse_size, Cli, Commands, DataPatternArg, IoBackendArg, IoEngineArg,
+    OutputFormat, SyncMethodArg, TestArg,
+};
+use capacity::{capacity_check_command, CapacityCheckOptions};
 use device_list::list_devices_command;
 use display::CliProgressCallback;
 
@@ -36,7 +47,37 @@ pub fn run_cli() -> Result<()> {
             sequential_block_size,
             random_block_size,
             duration,
+            bytes,
+            operations,
+            passes,
+            time_cap,
+            sustained_duration,
+            warmup,
+            repetitions,
             file_size,
+            queue_depth,
+            threads,
+            mixed_read_percentage,
+            mixed_block_size,
+            sync_size,
+            sync_method,
+            sync_append,
+            metadata_files,
+            metadata_fanout,
+            small_files,
+            small_file_sizes,
+            memory_min_size,
+            memory_max_size,
+            memory_threads,
+            memory_cpus,
+            memory_nodes,
+            tests,
+            data_pattern,
+            compression_ratio,
+            io_backend,
+            io_engine,
+            verify,
+            include_samples,
             enable_cache,
             disable_direct_io,
             output_format,
@@ -46,13 +87,63 @@ pub fn run_cli() -> Result<()> {
                 sequential_block_size,
                 random_block_size,
                 duration,
+                bytes,
+                operations,
+                passes,
+                time_cap,
+                sustained_duration,
+                warmup,
+                repetitions,
                 file_size,
+                queue_depth,
+                threads,
+                mixed_read_percentage,
+                mixed_block_size,
+                sync_size,
+                sync_method,
+                sync_append,
+                metadata_files,
+                metadata_fanout,
+                small_files,
+                small_file_sizes,
+                memory_min_size,
+                memory_max_size,
+                memory_threads,
+                memory_cpus,
+                memory_nodes,
+                tests,
+                data_pattern,
+                compression_ratio,
+                io_backend,
+                io_engine,
+                verify,
+                include_samples,
                 enable_cache,
                 disable_direct_io,
                 output_format,
             };
             run_benchmark_command(options)?;
         }
+        Commands::CapacityCheck {
+            target_path,
+            size,
+            file_size,
+            block_size,
+            keep_files,
+            disable_direct_io,
+            output_format,
+        } => {
+            let options = CapacityCheckOptions {
+                target_path,
+                size,
+                file_size,
+                block_size,
+                keep_files,
+                disable_direct_io,
+                output_format,
+            };
+            capacity_check_command(options)?;
+        }
     }
 
     Ok(())
@@ -65,7 +156,37 @@ struct BenchmarkOptions {
     sequential_block_size: Option<String>,
     random_block_size: Option<String>,
     duration: Option<u64>,
+    bytes: Option<String>,
+    operations: Option<u64>,
+    passes: Option<u64>,
+    time_cap: Option<u64>,
+    sustained_duration: Option<u64>,
+    warmup: Option<u64>,
+    repetitions: Option<usize>,
     file_size: Option<String>,
+    queue_depth: Option<usize>,
+    threads: Option<usize>,
+    mixed_read_percentage: Option<u8>,
+    mixed_block_size: Option<String>,
+    sync_size: Option<String>,
+    sync_method: SyncMethodArg,
+    sync_append: bool,
+    metadata_files: Option<usize>,
+    metadata_fanout: Option<usize>,
+    small_files: Option<usize>,
+    small_file_sizes: Option<String>,
+    memory_min_size: Option<String>,
+    memory_max_size: Option<String>,
+    memory_threads: Option<usize>,
+    memory_cpus: Option<String>,
+    memory_nodes: Option<String>,
+    tests: Vec<TestArg>,
+    data_pattern: DataPatternArg,
+    compression_ratio: Option<f64>,
+    io_backend: IoBackendArg,
+    io_engine: IoEngineArg,
+    verify: bool,
+    include_samples: bool,
     enable_cache: bool,
     disable_direct_io: bool,
     output_format: OutputFormat,
@@ -79,7 +200,37 @@ fn run_benchmark_command(options: BenchmarkOptions) -> Result<()> {
         sequential_block_size,
         random_block_size,
         duration,
+        bytes,
+        operations,
+        passes,
+        time_cap,
+        sustained_duration,
+        warmup,
+        repetitions,
         file_size,
+        queue_depth,
+        threads,
+        mixed_read_percentage,
+        mixed_block_size,
+        sync_size,
+        sync_method,
+        sync_append,
+        metadata_files,
+        metadata_fanout,
+        small_files,
+        small_file_sizes,
+        memory_min_size,
+        memory_max_size,
+        memory_threads,
+        memory_cpus,
+        memory_nodes,
+        tests,
+        data_pattern,
+        compression_ratio,
+        io_backend,
+        io_engine,
+        verify,
+        include_samples,
         enable_cache,
         disable_direct_io,
         output_format,
@@ -102,6 +253,36 @@ fn run_benchmark_command(options: BenchmarkOptions) -> Result<()> {
         config.test_duration_seconds = duration_secs;
     }
 
+    // Clap rejects more than one of --bytes, --operations and --passes
+    if let Some(size_str) = bytes {
+        let size_bytes =
+            parse_size(&size_str).map_err(|e| anyhow::anyhow!("Invalid byte limit: {}", e))?;
+        config.test_limit = TestLimit::Bytes(size_bytes as u64);
+    } else if let Some(count) = operations {
+        config.test_limit = TestLimit::Operations(count);
+    } else if let Some(count) = passes {
+        config.test_limit = TestLimit::Passes(count);
+    }
+
+    if time_cap.is_some() && config.test_limit == TestLimit::Duration {
+        return Err(anyhow::anyhow!(
+            "--time-cap requires --bytes, --operations or --passes"
+        ));
+    }
+    config.time_cap_seconds = time_cap;
+
+    if let Some(sustained_secs) = sustained_duration {
+        config.sustained_write_seconds = sustained_secs;
+    }
+
+    if let Some(warmup_secs) = warmup {
+        config.warmup_seconds = warmup_secs;
+    }
+
+    if let Some(count) = repetitions {
+        config.repetitions = count;
+    }
+
     if let Some(size_str) = file_size {
         let size_bytes =
             parse_size(&size_str).map_err(|e| anyhow::anyhow!("Invalid file size: {}", e))?;
@@ -111,6 +292,130 @@ fn run_benchmark_command(options: BenchmarkOptions) -> Result<()> {
         }
     }
 
+    if let Some(depth) = queue_depth {
+        config.queue_depth = depth;
+    }
+
+    if let Some(thread_count) = threads {
+        config.thread_count = thread_count;
+    }
+
+    if let Some(percentage) = mixed_read_percentage {
+        config.mixed_read_percentage = percentage;
+    }
+
+    if let Some(size_str) = mixed_block_size {
+        config.mixed_block_size = parse_size(&size_str)
+            .map_err(|e| anyhow::anyhow!("Invalid mixed block size: {}", e))?;
+    }
+
+    if let Some(size_str) = sync_size {
+        config.sync_write_size =
+            parse_size(&size_str).map_err(|e| anyhow::anyhow!("Invalid sync write size: {}", e))?;
+    }
+
+    config.sync_method = match sync_method {
+        SyncMethodArg::Fsync => SyncMethod::Fsync,
+        SyncMethodArg::Fdatasync => SyncMethod::Fdatasync,
+        SyncMethodArg::Dsync => SyncMethod::Dsync,
+    };
+    config.sync_write_append = sync_append;
+
+    if let Some(count) = metadata_files {
+        config.metadata_file_count = count;
+    }
+
+    if let Some(count) = metadata_fanout {
+        config.metadata_fanout = count;
+    }
+
+    if let Some(count) = small_files {
+        config.small_file_count = count;
+    }
+
+    if let Some(sizes_str) = small_file_sizes {
+        config.small_file_sizes = parse_file_sizes(&sizes_str)
+            .map_err(|e| anyhow::anyhow!("Invalid small file sizes: {}", e))?
+            .into_iter()
+            .map(|(size, weight)| FileSizeShare::new(size, weight))
+            .collect();
+    }
+
+    if let Some(size_str) = memory_min_size {
+        config.memory_min_size = parse_size(&size_str)
+            .map_err(|e| anyhow::anyhow!("Invalid memory minimum size: {}", e))?;
+    }
+
+    if let Some(size_str) = memory_max_size {
+        config.memory_max_size = parse_size(&size_str)
+            .map_err(|e| anyhow::anyhow!("Invalid memory maximum size: {}", e))?;
+    }
+
+    if let Some(count) = memory_threads {
+        config.memory_threads = count;
+    }
+
+    if let Some(list) = memory_cpus {
+        config.memory_pinning = ThreadPinning::Cpus(
+            parse_cpu_list(&list).map_err(|e| anyhow::anyhow!("Invalid memory CPUs: {}", e))?,
+        );
+    } else if let Some(list) = memory_nodes {
+        config.memory_pinning = ThreadPinning::NumaNodes(
+            parse_cpu_list(&list)
+                .map_err(|e| anyhow::anyhow!("Invalid memory NUMA nodes: {}", e))?,
+        );
+    }
+
+    // An empty selection keeps the default of running every test
+    if !tests.is_empty() {
+        config.tests = tests
+            .into_iter()
+            .map(|test| match test {
+                TestArg::SeqWrite => BenchmarkTest::SequentialWrite,
+                TestArg::SeqRead => BenchmarkTest::SequentialRead,
+                TestArg::RandWrite => BenchmarkTest::RandomWrite,
+                TestArg::RandRead => BenchmarkTest::RandomRead,
+                TestArg::Mixed => BenchmarkTest::MixedRandom,
+                TestArg::SyncWrite => BenchmarkTest::SyncWrite,
+                TestArg::Metadata => BenchmarkTest::Metadata,
+                TestArg::SmallFiles => BenchmarkTest::SmallFiles,
+                TestArg::Memory => BenchmarkTest::MemoryCopy,
+                TestArg::MemoryStream => BenchmarkTest::MemoryStream,
+                TestArg::SustainedWrite => BenchmarkTest::SustainedWrite,
+            })
+            .collect();
+    }
+
+    if compression_ratio.is_some() && data_pattern != DataPatternArg::Compressible {
+        return Err(anyhow::anyhow!(
+            "--compression-ratio requires --data-pattern compressible"
+        ));
+    }
+
+    config.data_pattern = match data_pattern {
+        DataPatternArg::Sequential => TestDataPattern::Sequential,
+        DataPatternArg::Zeros => TestDataPattern::Zeros,
+        DataPatternArg::Random => TestDataPattern::Random,
+        DataPatternArg::Compressible => {
+            TestDataPattern::Compressible(compression_ratio.unwrap_or(2.0))
+        }
+        DataPatternArg::Unique => TestDataPattern::Unique,
+    };
+
+    config.io_backend = match io_backend {
+        IoBackendArg::Sync => IoBackend::Sync,
+        IoBackendArg::IoUring => IoBackend::IoUring,
+    };
+
+    config.io_engine = match io_engine {
+        IoEngineArg::Pread => IoEngineKind::Pread,
+        IoEngineArg::Preadv => IoEngineKind::Preadv,
+        IoEngineArg::Mmap => IoEngineKind::Mmap,
+    };
+
+    config.verify = verify;
+    config.record_time_series = include_samples;
+
     // Set cache behavior (note: disable_os_cache is opposite of enable_cache)
     config.disable_os_cache = !enable_cache;
 
@@ -131,15 +436,135 @@ fn run_benchmark_command(options: BenchmarkOptions) -> Result<()> {
         println!("\nStarting benchmark tests...\n");
     }
 
+    // Ctrl+C stops the running test; the finished ones are still displayed
+    cancel_on_interrupt(config.cancellation.clone());
+
+    // Repeated runs are summarized per test instead of shown one by one
+    if config.repetitions > 1 {
+        let results = run_benchmark_repeated(config, Some(Box::new(progress_callback)))
+            .map_err(|e| anyhow::anyhow!("Benchmark failed: {}", e))?;
+        display::display_aggregated_results(&results, &output_format)?;
+        interrupted_error(results.interrupted)?;
+        return corruption_error(results.runs.iter().map(|run| run.corrupted_sectors()).sum());
+    }
+
     let results = run_benchmark(config, Some(Box::new(progress_callback)))
         .map_err(|e| anyhow::anyhow!("Benchmark failed: {}", e))?;
 
     // Display results
     display::display_results(&results, &output_format)?;
 
+    interrupted_error(results.interrupted)?;
+    corruption_error(results.corrupted_sectors())
+}
+
+/// Fail with `TestInterrupted` after the partial results of a cancelled run are shown
+fn interrupted_error(interrupted: bool) -> Result<()> {
+    if interrupted {
+        return Err(BenchmarkError::TestInterrupted(
+            "benchmark cancelled by user; results of the finished tests are shown above"
+                .to_string(),
+        )
+        .into());
+    }
     Ok(())
 }
 
+/// Fail with `DataCorruption` after the results of a run that found corrupted data are shown
+fn corruption_error(corrupted_sectors: u64) -> Result<()> {
+    if corrupted_sectors > 0 {
+        return Err(BenchmarkError::DataCorruption(format!(
+            "{} sectors read back did not match what was written; see the results above",
+            corrupted_sectors
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Set when Ctrl+C is pressed; read by the thread that cancels the benchmark
+#[cfg(any(unix, windows))]
+static INTERRUPT_REQUESTED: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// Cancel `token` when the user presses Ctrl+C
+///
+/// The handler only sets a flag, as nothing else is safe in a signal handler; a
+/// watcher thread turns it into a cancellation. A second Ctrl+C exits immediately.
+#[cfg(any(unix, windows))]
+fn cancel_on_interrupt(token: CancellationToken) {
+    use std::sync::atomic::Ordering;
+
+    install_interrupt_handler();
+
+    std::thread::spawn(move || {
+        while !INTERRUPT_REQUESTED.load(Ordering::SeqCst) {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        eprintln!("\nBenchmark interrupted by user, cleaning up (press Ctrl+C again to exit now)");
+        token.cancel();
+    });
+}
+
+/// Set [`INTERRUPT_REQUESTED`] on SIGINT, exiting on the second one
+#[cfg(unix)]
+fn install_interrupt_handler() {
+    use std::sync::atomic::Ordering;
+
+    extern "C" fn handle_sigint(_: i32) {
+        if INTERRUPT_REQUESTED.swap(true, Ordering::SeqCst) {
+            unsafe { libc::_exit(130) }; // Standard exit code for SIGINT
+        }
+    }
+
+    unsafe {
+        libc::signal(libc::SIGINT, handle_sigint as *const () as usize);
+    }
+}
+
+/// Set [`INTERRUPT_REQUESTED`] on Ctrl+C or Ctrl+Break
+///
+/// The second one is passed on to the default handler, which exits the process.
+#[cfg(windows)]
+fn install_interrupt_handler() {
+    use std::sync::atomic::Ordering;
+    use winapi::shared::minwindef::{BOOL, DWORD, FALSE, TRUE};
+    use winapi::um::consoleapi::SetConsoleCtrlHandler;
+    use winapi::um::wincon::{CTRL_BREAK_EVENT, CTRL_C_EVENT};
+
+    unsafe extern "system" fn handle_ctrl(ctrl_type: DWORD) -> BOOL {
+        match ctrl_type {
+            CTRL_C_EVENT | CTRL_BREAK_EVENT => {
+                if INTERRUPT_REQUESTED.swap(true, Ordering::SeqCst) {
+                    FALSE
+                } else {
+                    TRUE
+                }
+            }
+            _ => FALSE,
+        }
+    }
+
+    unsafe {
+        SetConsoleCtrlHandler(Some(handle_ctrl), TRUE);
+    }
+}
+
+/// Ctrl+C keeps its default behavior where no handler is available
+#[cfg(not(any(unix, windows)))]
+fn cancel_on_interrupt(_token: CancellationToken) {}
+
+/// Format a record or file size in the largest unit that divides it
+fn format_file_size(size: usize) -> String {
+    if size % (1024 * 1024) == 0 {
+        format!("{} MB", size / (1024 * 1024))
+    } else if size % 1024 == 0 {
+        format!("{} KB", size / 1024)
+    } else {
+        format!("{} bytes", size)
+    }
+}
+
 /// Display the benchmark configuration before starting tests
 fn display_benchmark_config(config: &BenchmarkConfig) {
     println!("Benchmark Configuration:");
@@ -152,7 +577,106 @@ fn display_benchmark_config(config: &BenchmarkConfig) {
         "  Random block size: {} KB",
         config.random_block_size / 1024
     );
-    println!("  Test duration: {} seconds", config.test_duration_seconds);
+    println!(
+        "  Random queue depth / threads: Q{}T{}",
+        config.queue_depth, config.thread_count
+    );
+    println!(
+        "  Mixed workload: {}% read, {} KB blocks",
+        config.mixed_read_percentage,
+        config.mixed_block_size / 1024
+    );
+    println!("  Data pattern: {}", config.data_pattern);
+    println!("  I/O backend: {}", config.io_backend);
+    println!("  I/O engine: {}", config.io_engine);
+    if config.verify {
+        println!("  Data verification: every block read is checked");
+    }
+    println!(
+        "  Tests: {}",
+        config
+            .tests
+            .iter()
+            .map(|test| test.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    match config.test_limit {
+        TestLimit::Duration => {
+            println!("  Test duration: {} seconds", config.test_duration_seconds)
+        }
+        limit => {
+            print!("  Test limit: {} per test", limit);
+            match config.time_cap_seconds {
+                Some(cap) => println!(" (time cap: {} seconds)", cap),
+                None => println!(),
+            }
+        }
+    }
+    if config.tests.contains(&BenchmarkTest::SyncWrite) {
+        println!(
+            "  Sync writes: {} records, {}, {}",
+            format_file_size(config.sync_write_size),
+            config.sync_method,
+            if config.sync_write_append {
+                "appended".to_string()
+            } else {
+                format!("overwriting a {}-record log", SYNC_WRITE_LOG_RECORDS)
+            }
+        );
+    }
+    if config.tests.contains(&BenchmarkTest::Metadata) {
+        println!(
+            "  Metadata: {} files in {} directories",
+            config.metadata_file_count, config.metadata_fanout
+        );
+    }
+    if config.tests.contains(&BenchmarkTest::SmallFiles) {
+        let total_weight: u32 = config.small_file_sizes.iter().map(|s| s.weight).sum();
+        let sizes: Vec<String> = config
+            .small_file_sizes
+            .iter()
+            .map(|share| {
+                format!(
+                    "{} ({:.0}%)",
+                    format_file_size(share.size),
+                    share.weight as f64 * 100.0 / total_weight.max(1) as f64
+                )
+            })
+            .collect();
+        println!(
+            "  Small files: {} files of {}",
+            config.small_file_count,
+            sizes.join(", ")
+        );
+    }
+    if config.tests.contains(&BenchmarkTest::MemoryCopy) {
+        println!(
+            "  Memory working sets: {} to {}",
+            format_file_size(config.memory_min_size),
+            format_file_size(config.memory_max_size)
+        );
+    }
+    if config.tests.contains(&BenchmarkTest::MemoryStream) {
+        println!(
+            "  Memory stream: {} threads ({}), {} across 3 arrays",
+            config.memory_threads,
+            config.memory_pinning,
+            format_file_size(config.memory_max_size)
+        );
+    }
+    if config.tests.contains(&BenchmarkTest::SustainedWrite) {
+        println!(
+            "  Sustained write duration: {} seconds",
+            config.sustained_write_seconds
+        );
+    }
+    if config.warmup_seconds > 0 {
+        println!("  Warm-up: {} seconds per test", config.warmup_seconds);
+    }
+    if config.repetitions > 1 {
+        println!("  Repetitions: {}", config.repetitions);
+    }
     println!("  Test file size: {} MB", config.file_size_mb);
     println!(
         "  OS caching: {}",
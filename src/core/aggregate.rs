@@ -0,0 +1,252 @@
+//! Aggregation of repeated benchmark runs
+
+use crate::core::{BenchmarkResults, IoBackend, TestResult};
+use serde::{Deserialize, Serialize};
+
+/// Two-sided 95% critical values of Student's t distribution for 1 to 30 degrees of freedom
+const T_CRITICAL_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Critical value of the normal distribution, used beyond the t table
+const Z_CRITICAL_95: f64 = 1.960;
+
+/// Spread of a test's effective throughput across repeated runs (MB/s)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunStatistics {
+    /// Number of runs the test completed in
+    pub runs: usize,
+
+    /// Mean of the runs' average speeds
+    pub mean_mbps: f64,
+
+    /// Median of the runs' average speeds
+    pub median_mbps: f64,
+
+    /// Sample standard deviation of the runs' average speeds; zero for a single run
+    pub stddev_mbps: f64,
+
+    /// Lower bound of the 95% confidence interval of the mean
+    pub ci95_low_mbps: f64,
+
+    /// Upper bound of the 95% confidence interval of the mean
+    pub ci95_high_mbps: f64,
+}
+
+impl RunStatistics {
+    /// Summarize one average speed per run
+    ///
+    /// The confidence interval uses Student's t distribution, which suits the small
+    /// run counts benchmarks use. A single run has no spread, so its interval is the
+    /// value itself.
+    pub fn from_speeds(speeds: &[f64]) -> Self {
+        if speeds.is_empty() {
+            return Self::default();
+        }
+
+        let runs = speeds.len();
+        let mean = speeds.iter().sum::<f64>() / runs as f64;
+
+        let mut sorted = speeds.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let median = if runs % 2 == 0 {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2.0
+        } else {
+            sorted[runs / 2]
+        };
+
+        let (stddev, margin) = if runs > 1 {
+            let variance =
+                speeds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (runs - 1) as f64;
+            let stddev = variance.sqrt();
+            let t = T_CRITICAL_95
+                .get(runs - 2)
+                .copied()
+                .unwrap_or(Z_CRITICAL_95);
+            (stddev, t * stddev / (runs as f64).sqrt())
+        } else {
+            (0.0, 0.0)
+        };
+
+        Self {
+            runs,
+            mean_mbps: mean,
+            median_mbps: median,
+            stddev_mbps: stddev,
+            ci95_low_mbps: mean - margin,
+            ci95_high_mbps: mean + margin,
+        }
+    }
+
+    /// Summarize the average speeds of a test's results
+    pub fn from_results<'a>(results: impl IntoIterator<Item = &'a TestResult>) -> Self {
+        let speeds: Vec<f64> = results.into_iter().map(|r| r.avg_speed_mbps).collect();
+        Self::from_speeds(&speeds)
+    }
+}
+
+/// Aggregated read, write and combined throughput of the mixed random test
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MixedRunStatistics {
+    /// Share of requests that were reads, in percent
+    pub read_percentage: u8,
+
+    /// Throughput of the read share
+    pub read: RunStatistics,
+
+    /// Throughput of the write share
+    pub write: RunStatistics,
+
+    /// Combined read and write throughput
+    pub combined: RunStatistics,
+}
+
+/// Results of a benchmark repeated several times
+///
+/// Every run's results are kept in `runs`; the per-test statistics summarize the
+/// runs' effective throughput and are `None` for tests that were not selected.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AggregatedResults {
+    /// Results of each run, in order
+    pub runs: Vec<BenchmarkResults>,
+    pub sequential_write: Option<RunStatistics>,
+    pub sequential_read: Option<RunStatistics>,
+    pub random_write: Option<RunStatistics>,
+    pub random_read: Option<RunStatistics>,
+    pub mixed_random: Option<MixedRunStatistics>,
+    pub memory_copy: Option<RunStatistics>,
+    #[serde(default)]
+    pub sync_write: Option<RunStatistics>,
+    #[serde(default)]
+    pub sustained_write: Option<RunStatistics>,
+    /// I/O backend the disk tests ran on
+    pub io_backend: IoBackend,
+    /// Whether the last run was cancelled, ending the repetitions early
+    #[serde(default)]
+    pub interrupted: bool,
+}
+
+impl AggregatedResults {
+    /// Aggregate the results of repeated runs
+    pub fn from_runs(runs: Vec<BenchmarkResults>) -> Self {
+        let stats = |select: fn(&BenchmarkResults) -> Option<&TestResult>| {
+            let results: Vec<&TestResult> = runs.iter().filter_map(select).collect();
+            (!results.is_empty()).then(|| RunStatistics::from_results(results))
+        };
+
+        let mixed: Vec<_> = runs
+            .iter()
+            .filter_map(|r| r.mixed_random.as_ref())
+            .collect();
+        let mixed_random = mixed.first().map(|first| MixedRunStatistics {
+            read_percentage: first.read_percentage,
+            read: RunStatistics::from_results(mixed.iter().map(|m| &m.read)),
+            write: RunStatistics::from_results(mixed.iter().map(|m| &m.write)),
+            combined: RunStatistics::from_results(mixed.iter().map(|m| &m.combined)),
+        });
+
+        Self {
+            sequential_write: stats(|r| r.sequential_write.as_ref()),
+            sequential_read: stats(|r| r.sequential_read.as_ref()),
+            random_write: stats(|r| r.random_write.as_ref()),
+            random_read: stats(|r| r.random_read.as_ref()),
+            mixed_random,
+            memory_copy: stats(|r| r.memory_copy.as_ref()),
+            sync_write: stats(|r| r.sync_write.as_ref()),
+            sustained_write: stats(|r| r.sustained_write.as_ref().map(|s| &s.result)),
+            io_backend: runs.first().map(|r| r.io_backend).unwrap_or_default(),
+            interrupted: runs.iter().any(|r| r.interrupted),
+            runs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::MixedTestResult;
+    use std::time::Duration;
+
+    fn result(avg_speed_mbps: f64) -> TestResult {
+        TestResult::new(0.0, 0.0, avg_speed_mbps, Duration::from_secs(1), 1)
+    }
+
+    #[test]
+    fn test_run_statistics() {
+        let stats = RunStatistics::from_speeds(&[100.0, 110.0, 90.0, 120.0]);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.mean_mbps, 105.0);
+        assert_eq!(stats.median_mbps, 105.0);
+        assert!((stats.stddev_mbps - 12.9099).abs() < 1e-4);
+
+        // t(0.975, 3) = 3.182
+        let margin = 3.182 * stats.stddev_mbps / 2.0;
+        assert!((stats.ci95_low_mbps - (105.0 - margin)).abs() < 1e-9);
+        assert!((stats.ci95_high_mbps - (105.0 + margin)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_run_statistics_odd_count_median() {
+        let stats = RunStatistics::from_speeds(&[50.0, 10.0, 30.0]);
+        assert_eq!(stats.median_mbps, 30.0);
+        assert_eq!(stats.mean_mbps, 30.0);
+    }
+
+    #[test]
+    fn test_run_statistics_single_run() {
+        let stats = RunStatistics::from_speeds(&[42.0]);
+        assert_eq!(stats.runs, 1);
+        assert_eq!(stats.stddev_mbps, 0.0);
+        assert_eq!(stats.ci95_low_mbps, 42.0);
+        assert_eq!(stats.ci95_high_mbps, 42.0);
+    }
+
+    #[test]
+    fn test_run_statistics_many_runs_use_normal_approximation() {
+        let speeds: Vec<f64> = (0..40).map(|i| (i % 2) as f64 * 10.0).collect();
+        let stats = RunStatistics::from_speeds(&speeds);
+        let margin = Z_CRITICAL_95 * stats.stddev_mbps / (40f64).sqrt();
+        assert!((stats.ci95_high_mbps - stats.mean_mbps - margin).abs() < 1e-9);
+        assert_eq!(RunStatistics::from_speeds(&[]), RunStatistics::default());
+    }
+
+    #[test]
+    fn test_aggregate_runs() {
+        let runs = vec![
+            BenchmarkResults {
+                sequential_read: Some(result(100.0)),
+                mixed_random: Some(MixedTestResult {
+                    read_percentage: 70,
+                    read: result(7.0),
+                    write: result(3.0),
+                    combined: result(10.0),
+                }),
+                ..Default::default()
+            },
+            BenchmarkResults {
+                sequential_read: Some(result(80.0)),
+                mixed_random: Some(MixedTestResult {
+                    read_percentage: 70,
+                    read: result(9.0),
+                    write: result(5.0),
+                    combined: result(14.0),
+                }),
+                ..Default::default()
+            },
+        ];
+
+        let aggregated = AggregatedResults::from_runs(runs);
+        assert_eq!(aggregated.runs.len(), 2);
+        assert_eq!(aggregated.sequential_read.unwrap().mean_mbps, 90.0);
+        assert!(aggregated.sequential_write.is_none());
+        assert!(aggregated.memory_copy.is_none());
+
+        let mixed = aggregated.mixed_random.unwrap();
+        assert_eq!(mixed.read_percentage, 70);
+        assert_eq!(mixed.read.mean_mbps, 8.0);
+        assert_eq!(mixed.write.mean_mbps, 4.0);
+        assert_eq!(mixed.combined.median_mbps, 12.0);
+    }
+}
@@ -0,0 +1,113 @@
+//! Aligned I/O buffers for direct I/O
+
+use std::alloc::{self, Layout};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+/// Heap buffer whose start address is aligned to a given power of two
+///
+/// `O_DIRECT` and `FILE_FLAG_NO_BUFFERING` reject buffers that are not aligned to
+/// the device's memory alignment, which `Vec<u8>` does not guarantee. The buffer is
+/// zero-initialized and dereferences to a byte slice.
+pub struct AlignedBuffer {
+    ptr: NonNull<u8>,
+    len: usize,
+    layout: Layout,
+}
+
+// SAFETY: the buffer exclusively owns its allocation, like `Vec<u8>`
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
+
+impl AlignedBuffer {
+    /// Allocate `len` zeroed bytes aligned to `alignment`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alignment` is not a power of two or the size overflows.
+    pub fn new(len: usize, alignment: usize) -> Self {
+        // Zero-sized allocations are not allowed, so always reserve at least one block
+        let layout = Layout::from_size_align(len.max(alignment).max(1), alignment)
+            .expect("invalid buffer alignment");
+
+        // SAFETY: the layout has a non-zero size
+        let raw = unsafe { alloc::alloc_zeroed(layout) };
+        let ptr = NonNull::new(raw).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+
+        Self { ptr, len, layout }
+    }
+
+    /// Alignment of the buffer's start address
+    pub fn alignment(&self) -> usize {
+        self.layout.align()
+    }
+}
+
+impl Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: `ptr` points to at least `len` initialized bytes owned by `self`
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for AlignedBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: `ptr` points to at least `len` initialized bytes owned by `self`
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        // SAFETY: allocated in `new` with this exact layout
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) }
+    }
+}
+
+impl std::fmt::Debug for AlignedBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlignedBuffer")
+            .field("len", &self.len)
+            .field("alignment", &self.alignment())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_buffer_alignment() {
+        for alignment in [1, 512, 4096, 65536] {
+            let buffer = AlignedBuffer::new(10_000, alignment);
+            assert_eq!(buffer.len(), 10_000);
+            assert_eq!(buffer.as_ptr() as usize % alignment, 0);
+            assert_eq!(buffer.alignment(), alignment);
+            assert!(buffer.iter().all(|&b| b == 0));
+        }
+    }
+
+    #[test]
+    fn test_aligned_buffer_is_writable() {
+        let mut buffer = AlignedBuffer::new(4096, 4096);
+        buffer[..4].copy_from_slice(b"disk");
+        buffer[4095] = 0xFF;
+        assert_eq!(&buffer[..4], b"disk");
+        assert_eq!(buffer[4095], 0xFF);
+    }
+
+    #[test]
+    fn test_aligned_buffer_empty() {
+        let buffer = AlignedBuffer::new(0, 4096);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid buffer alignment")]
+    fn test_aligned_buffer_rejects_bad_alignment() {
+        AlignedBuffer::new(4096, 3000);
+    }
+}
@@ -0,0 +1,312 @@
+//! Cooperative cancellation and pausing of benchmark runs
+
+use crate::core::BenchmarkError;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// Shared handle for stopping or pausing a running benchmark from another thread
+///
+/// Clones share the same state, so a host keeps one clone and hands another to the
+/// benchmark through [`BenchmarkConfig::cancellation`](crate::core::BenchmarkConfig::cancellation).
+/// Every test loop checks for cancellation between I/O requests; a cancelled run
+/// deletes its test file and returns the results of the tests that finished.
+///
+/// Pausing takes effect at the running test's next check, which waits until the
+/// token is resumed or cancelled. Tests time themselves on [`now`](Self::now), which
+/// stands still while paused, so a pause neither lengthens a test nor lowers its
+/// throughput.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<TokenState>,
+}
+
+/// State shared by the clones of a token
+///
+/// The checks made between I/O requests only read the atomics; the mutex is taken
+/// by `pause` and `resume`, and by checks made while paused.
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    paused: AtomicBool,
+    /// Nanoseconds spent in pauses that have ended
+    paused_nanos: AtomicU64,
+    /// Start of the current pause, while paused
+    pause_start: Mutex<Option<Instant>>,
+    changed: Condvar,
+}
+
+impl CancellationToken {
+    /// Create a token that is neither cancelled nor paused
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the benchmark to stop; this cannot be undone
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        let _pause_start = self.lock_pause_start();
+        self.inner.changed.notify_all();
+    }
+
+    /// Whether cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Hold the benchmark at its next check until [`resume`](Self::resume) is called
+    pub fn pause(&self) {
+        let mut pause_start = self.lock_pause_start();
+        if pause_start.is_none() {
+            *pause_start = Some(Instant::now());
+            self.inner.paused.store(true, Ordering::Release);
+        }
+    }
+
+    /// Let a paused benchmark continue
+    pub fn resume(&self) {
+        let mut pause_start = self.lock_pause_start();
+        if let Some(since) = pause_start.take() {
+            let paused = u64::try_from(since.elapsed().as_nanos()).unwrap_or(u64::MAX);
+            self.inner.paused_nanos.fetch_add(paused, Ordering::Release);
+            // Cleared last, so a clock that sees it cleared sees the pause counted
+            self.inner.paused.store(false, Ordering::Release);
+        }
+        self.inner.changed.notify_all();
+    }
+
+    /// Whether the token is paused
+    pub fn is_paused(&self) -> bool {
+        self.inner.paused.load(Ordering::Acquire)
+    }
+
+    /// Current time on a clock that stands still while the token is paused
+    ///
+    /// Instants taken from it are only comparable with each other, not with
+    /// [`Instant::now`].
+    pub fn now(&self) -> Instant {
+        let now = if self.is_paused() {
+            let pause_start = self.lock_pause_start();
+            pause_start.unwrap_or_else(Instant::now)
+        } else {
+            Instant::now()
+        };
+        let paused = Duration::from_nanos(self.inner.paused_nanos.load(Ordering::Acquire));
+        now.checked_sub(paused).unwrap_or(now)
+    }
+
+    /// Block while the token is paused, returning early if it is cancelled
+    pub fn wait_while_paused(&self) {
+        if !self.is_paused() {
+            return;
+        }
+        let mut pause_start = self.lock_pause_start();
+        while pause_start.is_some() && !self.is_cancelled() {
+            pause_start = self
+                .inner
+                .changed
+                .wait(pause_start)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Wait while paused, then fail with [`BenchmarkError::TestInterrupted`] if
+    /// cancellation has been requested
+    pub fn check(&self, test_name: &str) -> Result<(), BenchmarkError> {
+        self.wait_while_paused();
+        if self.is_cancelled() {
+            return Err(BenchmarkError::TestInterrupted(format!(
+                "{} was cancelled",
+                test_name
+            )));
+        }
+        Ok(())
+    }
+
+    fn lock_pause_start(&self) -> std::sync::MutexGuard<'_, Option<Instant>> {
+        self.inner
+            .pause_start
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        run_benchmark, BenchmarkConfig, BenchmarkTest, ProgressCallback, TestResult,
+    };
+    use std::thread;
+
+    #[test]
+    fn test_cancel_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let host = token.clone();
+        assert!(!token.is_cancelled());
+        assert!(token.check("Sequential Write").is_ok());
+
+        host.cancel();
+        assert!(token.is_cancelled());
+        assert!(matches!(
+            token.check("Sequential Write"),
+            Err(BenchmarkError::TestInterrupted(msg)) if msg.contains("Sequential Write")
+        ));
+    }
+
+    #[test]
+    fn test_wait_while_paused_until_resumed() {
+        let token = CancellationToken::new();
+        token.pause();
+        assert!(token.is_paused());
+
+        let host = token.clone();
+        let resumer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            host.resume();
+        });
+
+        let start = Instant::now();
+        token.wait_while_paused();
+        assert!(start.elapsed() >= Duration::from_millis(40));
+        assert!(!token.is_paused());
+        resumer.join().unwrap();
+    }
+
+    #[test]
+    fn test_cancel_releases_paused_waiter() {
+        let token = CancellationToken::new();
+        token.pause();
+
+        let host = token.clone();
+        let canceller = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            host.cancel();
+        });
+
+        token.wait_while_paused();
+        assert!(token.is_cancelled());
+        canceller.join().unwrap();
+    }
+
+    #[test]
+    fn test_clock_stands_still_while_paused() {
+        let token = CancellationToken::new();
+        token.pause();
+        let paused_at = token.now();
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(token.now(), paused_at);
+
+        token.resume();
+        let resumed_at = token.now();
+        assert!(resumed_at >= paused_at);
+        assert!(resumed_at - paused_at < Duration::from_millis(40));
+    }
+
+    #[test]
+    fn test_check_waits_while_paused() {
+        let token = CancellationToken::new();
+        token.pause();
+
+        let host = token.clone();
+        let resumer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            host.resume();
+        });
+
+        let start = Instant::now();
+        assert!(token.check("Sequential Read").is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(40));
+        resumer.join().unwrap();
+    }
+
+    #[test]
+    fn test_cancelled_benchmark_returns_partial_results_and_cleans_up() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = BenchmarkConfig {
+            target_path: temp_dir.path().to_path_buf(),
+            test_duration_seconds: 1,
+            disable_direct_io: true,
+            disable_os_cache: false,
+            file_size_mb: 1,
+            sequential_block_size: 64 * 1024,
+            memory_max_size: 64 * 1024,
+            tests: vec![
+                BenchmarkTest::MemoryCopy,
+                BenchmarkTest::SequentialWrite,
+                BenchmarkTest::SequentialRead,
+            ],
+            ..Default::default()
+        };
+
+        // Cancel once Sequential Read starts, after the other tests have finished
+        struct CancelOnRead(CancellationToken);
+        impl ProgressCallback for CancelOnRead {
+            fn on_test_start(&self, test_name: &str) {
+                if test_name == "Sequential Read" {
+                    self.0.cancel();
+                }
+            }
+            fn on_progress(&self, _test_name: &str, _current_speed_mbps: f64) {}
+            fn on_test_complete(&self, _test_name: &str, _result: &TestResult) {}
+        }
+        let callback = CancelOnRead(config.cancellation.clone());
+
+        let results = run_benchmark(config, Some(Box::new(callback))).unwrap();
+        assert!(results.interrupted);
+        assert!(results.memory_copy.is_some());
+        assert!(results.sequential_write.is_some());
+        assert!(results.sequential_read.is_none());
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_pause_is_left_out_of_the_running_test() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = BenchmarkConfig {
+            target_path: temp_dir.path().to_path_buf(),
+            test_duration_seconds: 1,
+            disable_direct_io: true,
+            disable_os_cache: false,
+            file_size_mb: 1,
+            tests: vec![BenchmarkTest::RandomRead],
+            ..Default::default()
+        };
+
+        // Pause for a second at the first progress report of the test
+        struct PauseOnProgress {
+            token: CancellationToken,
+            resumer: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
+        }
+        impl ProgressCallback for PauseOnProgress {
+            fn on_test_start(&self, _test_name: &str) {}
+            fn on_progress(&self, _test_name: &str, _current_speed_mbps: f64) {
+                let mut resumer = self.resumer.lock().unwrap();
+                if resumer.is_none() {
+                    self.token.pause();
+                    let host = self.token.clone();
+                    *resumer = Some(thread::spawn(move || {
+                        thread::sleep(Duration::from_secs(1));
+                        host.resume();
+                    }));
+                }
+            }
+            fn on_test_complete(&self, _test_name: &str, _result: &TestResult) {}
+        }
+        let resumer = Arc::default();
+        let callback = PauseOnProgress {
+            token: config.cancellation.clone(),
+            resumer: Arc::clone(&resumer),
+        };
+
+        let start = Instant::now();
+        let results = run_benchmark(config, Some(Box::new(callback))).unwrap();
+        let resumer = resumer.lock().unwrap().take().unwrap();
+        resumer.join().unwrap();
+
+        let random_read = results.random_read.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        assert!(random_read.test_duration >= Duration::from_millis(900));
+        assert!(random_read.test_duration < Duration::from_millis(1500));
+    }
+}
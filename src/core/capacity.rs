@@ -0,0 +1,820 @@
+//! Fake-capacity check of a storage device
+//!
+//! Counterfeit USB sticks and memory cards report more capacity than they have.
+//! Writes beyond the real capacity are typically wrapped around onto earlier blocks
+//! or silently dropped. [`run_capacity_check`] fills the free space with files whose
+//! 512-byte sectors are sealed with their position in the fill (see
+//! [`TestDataVerifier::seal`]), then reads everything back. Sectors that come back
+//! altered, from another position or not at all mark where the real capacity ends.
+//!
+//! Progress is saved after every file, so a cancelled check resumes where it left
+//! off when it is run again on the same directory.
+
+use crate::core::engine::{IoEngine, IoOpenOptions, PreadEngine};
+use crate::core::verify::{CorruptionEvent, CorruptionKind, TestDataVerifier, VERIFY_SECTOR_SIZE};
+use crate::core::{
+    AlignedBuffer, BenchmarkError, CancellationToken, ProgressCallback, ProgressUpdate,
+    RealTimeStatsTracker, StatisticsCollector, StopReason, TestDataPattern, TestPhase, TestResult,
+};
+use crate::platform::DirectIoAlignment;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Directory created in the target path for the fill files and the saved progress
+pub const CAPACITY_CHECK_DIR: &str = "disk_speed_test_capacity";
+
+/// Saved progress of an unfinished check, inside [`CAPACITY_CHECK_DIR`]
+const STATE_FILE: &str = "state.json";
+
+/// Progress name of the phase that fills the free space
+pub const FILL_PHASE: &str = "Capacity Fill";
+
+/// Progress name of the phase that reads the fill back
+pub const VERIFY_PHASE: &str = "Capacity Verify";
+
+/// Configuration of a fake-capacity check
+#[derive(Debug, Clone)]
+pub struct CapacityCheckConfig {
+    /// Directory on the device to check, usually its mount point
+    pub target_path: PathBuf,
+
+    /// Most bytes to fill (default: all free space)
+    pub max_bytes: Option<u64>,
+
+    /// Size of each fill file in bytes (default: 1 GiB)
+    /// Progress is saved whenever a file is complete
+    pub file_size: u64,
+
+    /// Size of each read and write in bytes (default: 1 MiB)
+    pub block_size: usize,
+
+    /// Whether to disable direct I/O (default: false)
+    /// Buffered reads may be served from the OS cache instead of the device
+    pub disable_direct_io: bool,
+
+    /// Whether to keep the fill files once the check is complete (default: false)
+    pub keep_files: bool,
+
+    /// Token for stopping the check from another thread (default: a fresh token)
+    /// A cancelled check keeps its files and resumes when it is run again
+    pub cancellation: CancellationToken,
+}
+
+impl Default for CapacityCheckConfig {
+    fn default() -> Self {
+        Self {
+            target_path: PathBuf::from("."),
+            max_bytes: None,
+            file_size: 1024 * 1024 * 1024, // 1GB
+            block_size: 1024 * 1024,       // 1MB
+            disable_direct_io: false,
+            keep_files: false,
+            cancellation: CancellationToken::new(),
+        }
+    }
+}
+
+impl CapacityCheckConfig {
+    /// Create a new configuration with the specified target path
+    pub fn new(target_path: PathBuf) -> Self {
+        Self {
+            target_path,
+            ..Default::default()
+        }
+    }
+
+    /// Validate the configuration parameters
+    pub fn validate(&self) -> Result<(), BenchmarkError> {
+        if self.block_size == 0 || self.block_size % VERIFY_SECTOR_SIZE != 0 {
+            return Err(BenchmarkError::ConfigurationError(format!(
+                "Block size must be a positive multiple of {} bytes",
+                VERIFY_SECTOR_SIZE
+            )));
+        }
+
+        if self.file_size == 0 || self.file_size % self.block_size as u64 != 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "File size must be a positive multiple of the block size".to_string(),
+            ));
+        }
+
+        if self.max_bytes == Some(0) {
+            return Err(BenchmarkError::ConfigurationError(
+                "Fill size must be greater than 0".to_string(),
+            ));
+        }
+
+        if !self.target_path.is_dir() {
+            return Err(BenchmarkError::ConfigurationError(format!(
+                "Target path is not a directory: {}",
+                self.target_path.display()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Write and read speed of one fill file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapacitySample {
+    /// Position of the file's first byte in the fill
+    pub offset: u64,
+    /// Bytes written to the file
+    pub bytes: u64,
+    /// Throughput writing the file, including flushing it (MB/s)
+    pub write_speed_mbps: f64,
+    /// Throughput reading the file back (MB/s); `None` until it has been read
+    pub read_speed_mbps: Option<f64>,
+}
+
+/// Outcome of a fake-capacity check
+///
+/// Byte counts cover every session of a resumed check; the `write` and `read`
+/// statistics only cover the blocks transferred in the last session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CapacityCheckResults {
+    /// Free space the device reported when the check started, if it is a listed device
+    pub reported_free_bytes: Option<u64>,
+    /// Bytes written before the device was full or the fill size was reached
+    pub written_bytes: u64,
+    /// Bytes read back intact
+    pub verified_bytes: u64,
+    /// Bytes read back corrupted, from another position, stale or not at all
+    pub corrupted_bytes: u64,
+    /// Bytes before the first bad sector: the capacity that can actually be used
+    pub usable_bytes: u64,
+    /// First bad sector, with its position in the fill
+    pub first_corruption: Option<CorruptionEvent>,
+    /// Write throughput of the fill
+    pub write: TestResult,
+    /// Read throughput of the verification
+    pub read: TestResult,
+    /// Write and read speed of every fill file, in fill order
+    pub samples: Vec<CapacitySample>,
+    /// Whether the check continued one that had been cancelled
+    pub resumed: bool,
+    /// Whether the check was cancelled before it finished; its files are kept
+    pub interrupted: bool,
+}
+
+impl CapacityCheckResults {
+    /// Position of the first bad sector in the fill
+    pub fn first_bad_offset(&self) -> Option<u64> {
+        self.first_corruption.map(|event| event.offset)
+    }
+
+    /// Whether any data failed to read back intact
+    pub fn has_corruption(&self) -> bool {
+        self.corrupted_bytes > 0
+    }
+}
+
+/// Progress of a check, saved after every file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CheckState {
+    /// Generation every sector of this check is sealed with
+    generation: u64,
+    file_size: u64,
+    block_size: usize,
+    reported_free_bytes: Option<u64>,
+    /// Bytes to fill; `None` fills until the device is full
+    target_bytes: Option<u64>,
+    /// Fill files written so far; only the last one may be shorter than `file_size`
+    samples: Vec<CapacitySample>,
+    /// Whether the fill is complete
+    filled: bool,
+    /// Fill files read back so far
+    files_read: usize,
+    verified_bytes: u64,
+    corrupted_bytes: u64,
+    first_corruption: Option<CorruptionEvent>,
+}
+
+impl CheckState {
+    fn written_bytes(&self) -> u64 {
+        self.samples.iter().map(|sample| sample.bytes).sum()
+    }
+}
+
+/// Verification of one fill file, merged into the state once the file is done
+#[derive(Debug, Default)]
+struct FileCheck {
+    verified_bytes: u64,
+    corrupted_bytes: u64,
+    first_corruption: Option<CorruptionEvent>,
+}
+
+impl FileCheck {
+    /// Account for the result of [`TestDataVerifier::check_sealed`]
+    fn add_sectors(&mut self, checked: u64, events: Vec<CorruptionEvent>) {
+        let sector_size = VERIFY_SECTOR_SIZE as u64;
+        self.verified_bytes += (checked - events.len() as u64) * sector_size;
+        self.corrupted_bytes += events.len() as u64 * sector_size;
+        self.first_corruption = self.first_corruption.or(events.first().copied());
+    }
+
+    /// Account for `len` bytes at `offset` that could not be read
+    fn add_unreadable(&mut self, offset: u64, len: u64) {
+        self.corrupted_bytes += len;
+        self.first_corruption = self.first_corruption.or(Some(CorruptionEvent {
+            offset,
+            kind: CorruptionKind::Unreadable,
+        }));
+    }
+}
+
+/// A running check: its configuration, saved progress and I/O buffer
+struct CapacityCheck<'a> {
+    config: &'a CapacityCheckConfig,
+    dir: PathBuf,
+    state: CheckState,
+    buffer: AlignedBuffer,
+    progress_callback: Option<&'a dyn ProgressCallback>,
+}
+
+/// Fill the free space of a device and read it back to find its real capacity
+///
+/// Files of `config.file_size` bytes are written to [`CAPACITY_CHECK_DIR`] in the
+/// target path until the device is full or `config.max_bytes` is reached, then read
+/// back in the same order. The fill stops early at the free space the device
+/// reports, if [`storage_device_for_path`](crate::platform::storage_device_for_path)
+/// finds it.
+///
+/// Cancelling `config.cancellation` stops at the next block, keeps the files written
+/// so far and returns with [`CapacityCheckResults::interrupted`] set; running the check
+/// again on the same target resumes it. A finished check deletes its files unless
+/// `config.keep_files` is set.
+pub fn run_capacity_check(
+    config: CapacityCheckConfig,
+    progress_callback: Option<Box<dyn ProgressCallback>>,
+) -> Result<CapacityCheckResults, BenchmarkError> {
+    check_capacity(&config, progress_callback.as_deref())
+}
+
+fn check_capacity(
+    config: &CapacityCheckConfig,
+    progress_callback: Option<&dyn ProgressCallback>,
+) -> Result<CapacityCheckResults, BenchmarkError> {
+    config.validate()?;
+    let dir = config.target_path.join(CAPACITY_CHECK_DIR);
+
+    let (state, resumed) = match load_state(&dir) {
+        Some(state) => (state, true),
+        None => (start_check(config, &dir)?, false),
+    };
+
+    let alignment = if config.disable_direct_io {
+        DirectIoAlignment::NONE
+    } else {
+        crate::platform::direct_io_alignment(&dir).unwrap_or_default()
+    };
+    if state.block_size % alignment.offset != 0 {
+        return Err(BenchmarkError::ConfigurationError(format!(
+            "Block size must be a multiple of the {} byte direct I/O alignment",
+            alignment.offset
+        )));
+    }
+    let mut buffer = AlignedBuffer::new(state.block_size, alignment.memory);
+    TestDataPattern::Random.fill(&mut buffer);
+
+    let mut check = CapacityCheck {
+        config,
+        dir,
+        state,
+        buffer,
+        progress_callback,
+    };
+
+    let write = if check.state.filled {
+        TestResult::default()
+    } else {
+        check.fill()?
+    };
+    let read = if config.cancellation.is_cancelled() {
+        TestResult::default()
+    } else {
+        check.verify()?
+    };
+
+    let interrupted = config.cancellation.is_cancelled();
+    if !interrupted {
+        check.clean_up()?;
+    }
+
+    let state = check.state;
+    Ok(CapacityCheckResults {
+        reported_free_bytes: state.reported_free_bytes,
+        written_bytes: state.written_bytes(),
+        verified_bytes: state.verified_bytes,
+        corrupted_bytes: state.corrupted_bytes,
+        usable_bytes: state
+            .first_corruption
+            .map_or(state.verified_bytes, |event| event.offset),
+        first_corruption: state.first_corruption,
+        write,
+        read,
+        samples: state.samples,
+        resumed,
+        interrupted,
+    })
+}
+
+/// Load the progress of a cancelled check, if there is one
+fn load_state(dir: &Path) -> Option<CheckState> {
+    let json = std::fs::read_to_string(dir.join(STATE_FILE)).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Set up a new check, deleting the files of any earlier one
+fn start_check(config: &CapacityCheckConfig, dir: &Path) -> Result<CheckState, BenchmarkError> {
+    if dir.exists() {
+        std::fs::remove_dir_all(dir)?;
+    }
+    std::fs::create_dir_all(dir)?;
+
+    let reported_free_bytes = crate::platform::storage_device_for_path(&config.target_path)
+        .ok()
+        .flatten()
+        .map(|device| device.available_space);
+    let block_size = config.block_size as u64;
+    let target_bytes = match (config.max_bytes, reported_free_bytes) {
+        (Some(max), Some(free)) => Some(max.min(free)),
+        (max, free) => max.or(free),
+    }
+    .map(|bytes| bytes / block_size * block_size);
+
+    // Any non-zero value works; a time-based one tells sessions apart
+    let generation = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+        | 1;
+
+    let state = CheckState {
+        generation,
+        file_size: config.file_size,
+        block_size: config.block_size,
+        reported_free_bytes,
+        target_bytes,
+        samples: Vec::new(),
+        filled: target_bytes == Some(0),
+        files_read: 0,
+        verified_bytes: 0,
+        corrupted_bytes: 0,
+        first_corruption: None,
+    };
+    save_state(dir, &state)?;
+    Ok(state)
+}
+
+fn save_state(dir: &Path, state: &CheckState) -> Result<(), BenchmarkError> {
+    let json = serde_json::to_string(state)
+        .map_err(|e| BenchmarkError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+    std::fs::write(dir.join(STATE_FILE), json)?;
+    Ok(())
+}
+
+/// Whether a write failed because the device is full
+fn is_out_of_space(error: &std::io::Error) -> bool {
+    // ENOSPC on Unix; ERROR_HANDLE_DISK_FULL and ERROR_DISK_FULL on Windows
+    #[cfg(unix)]
+    const CODES: &[i32] = &[28];
+    #[cfg(windows)]
+    const CODES: &[i32] = &[39, 112];
+    #[cfg(not(any(unix, windows)))]
+    const CODES: &[i32] = &[];
+
+    error
+        .raw_os_error()
+        .is_some_and(|code| CODES.contains(&code))
+}
+
+impl CapacityCheck<'_> {
+    fn file_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("fill_{:06}.bin", index))
+    }
+
+    fn block_size(&self) -> u64 {
+        self.state.block_size as u64
+    }
+
+    /// Write fill files until the device is full, the target is reached or the
+    /// check is cancelled
+    fn fill(&mut self) -> Result<TestResult, BenchmarkError> {
+        if let Some(callback) = self.progress_callback {
+            callback.on_test_start(FILL_PHASE);
+        }
+        let mut progress = PhaseProgress::new(
+            FILL_PHASE,
+            self.state.written_bytes(),
+            self.state.target_bytes,
+        );
+        let mut stats_tracker = RealTimeStatsTracker::new();
+        let direct_io = !self.config.disable_direct_io;
+
+        while !self.state.filled {
+            let index = self.state.samples.len();
+            let offset = index as u64 * self.state.file_size;
+            let len = match self.state.target_bytes {
+                Some(target) => target.saturating_sub(offset).min(self.state.file_size),
+                None => self.state.file_size,
+            };
+            if len == 0 {
+                self.state.filled = true;
+                break;
+            }
+
+            let path = self.file_path(index);
+            let file_start = Instant::now();
+            let handle = PreadEngine.open(&path, &IoOpenOptions::create(0, direct_io))?;
+            let mut written = 0u64;
+            let mut full = false;
+            while written < len {
+                if self.config.cancellation.is_cancelled() {
+                    // The partial file is written again when the check resumes
+                    return Ok(stats_tracker.finalize());
+                }
+
+                let block_len = self.block_size().min(len - written) as usize;
+                let block = &mut self.buffer[..block_len];
+                TestDataVerifier::seal(block, offset + written, self.state.generation);
+                let write_start = Instant::now();
+                match handle.write_at(block, written) {
+                    Ok(bytes) => {
+                        written += bytes as u64;
+                        if let Some(current_speed) =
+                            stats_tracker.record_block(bytes, write_start.elapsed())
+                        {
+                            progress.report(
+                                self.progress_callback,
+                                &stats_tracker,
+                                current_speed,
+                                offset + written,
+                            );
+                        }
+                        if bytes < block_len {
+                            full = true;
+                            break;
+                        }
+                    }
+                    Err(e) if is_out_of_space(&e) => {
+                        full = true;
+                        break;
+                    }
+                    Err(e) => return Err(BenchmarkError::IoError(e)),
+                }
+            }
+
+            match handle.flush() {
+                Ok(()) => {}
+                // Data that did not fit is lost, which the verification reports
+                Err(e) if is_out_of_space(&e) => full = true,
+                Err(e) => return Err(BenchmarkError::IoError(e)),
+            }
+            drop(handle);
+
+            if written == 0 {
+                std::fs::remove_file(&path)?;
+            } else {
+                self.state.samples.push(CapacitySample {
+                    offset,
+                    bytes: written,
+                    write_speed_mbps: StatisticsCollector::calculate_speed_mbps(
+                        written,
+                        file_start.elapsed(),
+                    ),
+                    read_speed_mbps: None,
+                });
+            }
+            self.state.filled = full || written < len;
+            save_state(&self.dir, &self.state)?;
+        }
+
+        let result = stats_tracker
+            .finalize()
+            .with_stop_reason(StopReason::PassLimit);
+        if let Some(callback) = self.progress_callback {
+            callback.on_test_complete(FILL_PHASE, &result);
+        }
+        Ok(result)
+    }
+
+    /// Read the fill files back in order, checking every sector
+    fn verify(&mut self) -> Result<TestResult, BenchmarkError> {
+        if let Some(callback) = self.progress_callback {
+            callback.on_test_start(VERIFY_PHASE);
+        }
+        let read_before = self.state.samples[..self.state.files_read]
+            .iter()
+            .map(|sample| sample.bytes)
+            .sum();
+        let mut progress =
+            PhaseProgress::new(VERIFY_PHASE, read_before, Some(self.state.written_bytes()));
+        let mut stats_tracker = RealTimeStatsTracker::new();
+        let direct_io = !self.config.disable_direct_io;
+
+        while self.state.files_read < self.state.samples.len() {
+            let index = self.state.files_read;
+            let CapacitySample { offset, bytes, .. } = self.state.samples[index];
+            let file_start = Instant::now();
+            let mut file_check = FileCheck::default();
+
+            match PreadEngine.open(&self.file_path(index), &IoOpenOptions::read(direct_io)) {
+                Ok(handle) => {
+                    let mut read = 0u64;
+                    while read < bytes {
+                        if self.config.cancellation.is_cancelled() {
+                            // The file is checked again when the check resumes
+                            return Ok(stats_tracker.finalize());
+                        }
+
+                        let block_len = self.block_size().min(bytes - read) as usize;
+                        let read_start = Instant::now();
+                        match handle.read_at(&mut self.buffer[..block_len], read) {
+                            Ok(0) => {
+                                file_check.add_unreadable(offset + read, bytes - read);
+                                break;
+                            }
+                            Ok(got) => {
+                                let elapsed = read_start.elapsed();
+                                let (checked, events) = TestDataVerifier::check_sealed(
+                                    &self.buffer[..got],
+                                    offset + read,
+                                    self.state.generation,
+                                );
+                                file_check.add_sectors(checked, events);
+                                read += got as u64;
+                                if let Some(current_speed) =
+                                    stats_tracker.record_block(got, elapsed)
+                                {
+                                    progress.report(
+                                        self.progress_callback,
+                                        &stats_tracker,
+                                        current_speed,
+                                        offset + read,
+                                    );
+                                }
+                            }
+                            Err(_) => {
+                                file_check.add_unreadable(offset + read, block_len as u64);
+                                read += block_len as u64;
+                            }
+                        }
+                    }
+                }
+                Err(_) => file_check.add_unreadable(offset, bytes),
+            }
+
+            let state = &mut self.state;
+            state.samples[index].read_speed_mbps = Some(StatisticsCollector::calculate_speed_mbps(
+                bytes,
+                file_start.elapsed(),
+            ));
+            state.verified_bytes += file_check.verified_bytes;
+            state.corrupted_bytes += file_check.corrupted_bytes;
+            state.first_corruption = state.first_corruption.or(file_check.first_corruption);
+            state.files_read += 1;
+            save_state(&self.dir, &self.state)?;
+        }
+
+        let result = stats_tracker
+            .finalize()
+            .with_stop_reason(StopReason::PassLimit);
+        if let Some(callback) = self.progress_callback {
+            callback.on_test_complete(VERIFY_PHASE, &result);
+        }
+        Ok(result)
+    }
+
+    /// Delete the fill files, or only the saved progress if they are kept
+    fn clean_up(&self) -> Result<(), BenchmarkError> {
+        if let Some(callback) = self.progress_callback {
+            callback.on_phase_change(VERIFY_PHASE, TestPhase::Cleanup);
+        }
+        if self.config.keep_files {
+            std::fs::remove_file(self.dir.join(STATE_FILE))?;
+        } else {
+            std::fs::remove_dir_all(&self.dir)?;
+        }
+        Ok(())
+    }
+}
+
+/// Progress of the fill or verification across sessions
+struct PhaseProgress {
+    test_name: &'static str,
+    started: Instant,
+    /// Bytes done before this session
+    done_before: u64,
+    /// Bytes the phase covers, if known
+    target: Option<u64>,
+}
+
+impl PhaseProgress {
+    fn new(test_name: &'static str, done_before: u64, target: Option<u64>) -> Self {
+        Self {
+            test_name,
+            started: Instant::now(),
+            done_before,
+            target,
+        }
+    }
+
+    /// Report that `done` bytes of the phase are complete
+    fn report(
+        &mut self,
+        callback: Option<&dyn ProgressCallback>,
+        stats_tracker: &RealTimeStatsTracker,
+        current_speed_mbps: f64,
+        done: u64,
+    ) {
+        let Some(callback) = callback else {
+            return;
+        };
+
+        let elapsed = self.started.elapsed();
+        let (bytes, operations) = stats_tracker.measured_totals();
+        let fraction = self
+            .target
+            .filter(|&target| target > 0)
+            .map_or(0.0, |target| (done as f64 / target as f64).min(1.0));
+        // Extrapolate from this session's pace
+        let done_now = done.saturating_sub(self.done_before);
+        let eta = self.target.filter(|_| done_now > 0).and_then(|target| {
+            let remaining = target.saturating_sub(done) as f64 / done_now as f64;
+            Duration::try_from_secs_f64(elapsed.as_secs_f64() * remaining).ok()
+        });
+        callback.on_progress_update(&ProgressUpdate {
+            test_name: self.test_name.to_string(),
+            phase: TestPhase::Measure,
+            elapsed,
+            total: None,
+            fraction,
+            eta,
+            bytes,
+            operations,
+            current_speed_mbps,
+            average_speed_mbps: StatisticsCollector::calculate_speed_mbps(bytes, elapsed),
+            iops: if elapsed.is_zero() {
+                0.0
+            } else {
+                operations as f64 / elapsed.as_secs_f64()
+            },
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::TestProgressCallback;
+
+    fn small_config(target: &Path) -> CapacityCheckConfig {
+        CapacityCheckConfig {
+            target_path: target.to_path_buf(),
+            max_bytes: Some(1024 * 1024),
+            file_size: 256 * 1024,
+            block_size: 64 * 1024,
+            disable_direct_io: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = small_config(temp_dir.path());
+        assert!(config.validate().is_ok());
+
+        config.block_size = 1000;
+        assert!(config.validate().is_err());
+        config.block_size = 64 * 1024;
+        config.file_size = 100 * 1024;
+        assert!(config.validate().is_err());
+        config.file_size = 256 * 1024;
+        config.max_bytes = Some(0);
+        assert!(config.validate().is_err());
+        config.max_bytes = None;
+        config.target_path = temp_dir.path().join("missing");
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_genuine_fill_verifies_and_cleans_up() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let callback = TestProgressCallback::new();
+
+        let results = check_capacity(&small_config(temp_dir.path()), Some(&callback)).unwrap();
+
+        assert_eq!(results.written_bytes, 1024 * 1024);
+        assert_eq!(results.verified_bytes, 1024 * 1024);
+        assert_eq!(results.usable_bytes, 1024 * 1024);
+        assert!(!results.has_corruption());
+        assert!(!results.resumed && !results.interrupted);
+        assert_eq!(results.samples.len(), 4);
+        assert_eq!(results.samples[3].offset, 768 * 1024);
+        assert!(results.samples.iter().all(|s| s.read_speed_mbps.is_some()));
+        assert!(!temp_dir.path().join(CAPACITY_CHECK_DIR).exists());
+
+        let completed: Vec<String> = callback
+            .test_complete_events()
+            .into_iter()
+            .map(|(test_name, _)| test_name)
+            .collect();
+        assert_eq!(completed, [FILL_PHASE, VERIFY_PHASE]);
+    }
+
+    #[test]
+    fn test_wrapped_writes_report_first_bad_offset() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = small_config(temp_dir.path());
+        config.keep_files = true;
+        let dir = temp_dir.path().join(CAPACITY_CHECK_DIR);
+
+        // Stop after the fill, then act like a drive that only stores 512KB and
+        // wraps later writes around onto the start
+        config.cancellation.cancel();
+        let interrupted = run_capacity_check(config.clone(), None).unwrap();
+        assert!(interrupted.interrupted);
+        assert_eq!(interrupted.written_bytes, 0);
+
+        config.cancellation = CancellationToken::new();
+        let mut state = load_state(&dir).unwrap();
+        let check = CapacityCheck {
+            config: &config,
+            dir: dir.clone(),
+            state: state.clone(),
+            buffer: AlignedBuffer::new(64 * 1024, 1),
+            progress_callback: None,
+        };
+        for index in 0..4 {
+            let mut data = vec![0u8; 256 * 1024];
+            let offset = index as u64 * 256 * 1024;
+            TestDataVerifier::seal(&mut data, offset, state.generation);
+            std::fs::write(check.file_path(index), &data).unwrap();
+            state.samples.push(CapacitySample {
+                offset,
+                bytes: data.len() as u64,
+                write_speed_mbps: 0.0,
+                read_speed_mbps: None,
+            });
+        }
+        std::fs::copy(check.file_path(0), check.file_path(2)).unwrap();
+        std::fs::write(check.file_path(3), vec![0u8; 128 * 1024]).unwrap();
+        state.filled = true;
+        save_state(&dir, &state).unwrap();
+
+        let results = run_capacity_check(config, None).unwrap();
+        assert!(results.resumed);
+        assert_eq!(results.written_bytes, 1024 * 1024);
+        assert_eq!(results.verified_bytes, 512 * 1024);
+        assert_eq!(results.corrupted_bytes, 512 * 1024);
+        assert_eq!(results.usable_bytes, 512 * 1024);
+        assert_eq!(
+            results.first_corruption,
+            Some(CorruptionEvent {
+                offset: 512 * 1024,
+                kind: CorruptionKind::Misplaced { found_offset: 0 }
+            })
+        );
+        // The files are kept, but the check is over
+        assert!(dir.join("fill_000000.bin").exists());
+        assert!(!dir.join(STATE_FILE).exists());
+    }
+
+    #[test]
+    fn test_cancelled_check_resumes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = small_config(temp_dir.path());
+        let dir = temp_dir.path().join(CAPACITY_CHECK_DIR);
+
+        // Cancel once the fill is done, before anything is read back
+        struct CancelOnVerify(CancellationToken);
+        impl ProgressCallback for CancelOnVerify {
+            fn on_test_start(&self, test_name: &str) {
+                if test_name == VERIFY_PHASE {
+                    self.0.cancel();
+                }
+            }
+            fn on_progress(&self, _test_name: &str, _current_speed_mbps: f64) {}
+            fn on_test_complete(&self, _test_name: &str, _result: &TestResult) {}
+        }
+        let mut first = config.clone();
+        first.cancellation = CancellationToken::new();
+        let callback = CancelOnVerify(first.cancellation.clone());
+        let interrupted = check_capacity(&first, Some(&callback)).unwrap();
+        assert!(interrupted.interrupted);
+        assert_eq!(interrupted.written_bytes, 1024 * 1024);
+        assert_eq!(interrupted.verified_bytes, 0);
+        assert_eq!(load_state(&dir).unwrap().files_read, 0);
+
+        let results = run_capacity_check(config, None).unwrap();
+        assert!(results.resumed && !results.interrupted);
+        assert_eq!(results.written_bytes, 1024 * 1024);
+        assert_eq!(results.verified_bytes, 1024 * 1024);
+        assert_eq!(results.write.total_bytes, 0);
+        assert!(!dir.exists());
+    }
+}
@@ -1,9 +1,268 @@
 //! Benchmark configuration structures and validation
 
-use crate::core::BenchmarkError;
+use crate::core::verify::VERIFY_SECTOR_SIZE;
+use crate::core::{BenchmarkError, CancellationToken, TestDataPattern};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+/// I/O backend used to issue disk reads and writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IoBackend {
+    /// Blocking `read`/`write` calls, one request in flight per worker
+    #[default]
+    Sync,
+    /// Linux io_uring with registered buffers, `queue_depth` requests in flight per ring
+    IoUring,
+}
+
+impl IoBackend {
+    /// Whether this backend was compiled in and is usable on the current platform
+    pub fn is_available(self) -> bool {
+        match self {
+            IoBackend::Sync => true,
+            IoBackend::IoUring => cfg!(all(target_os = "linux", feature = "io-uring")),
+        }
+    }
+}
+
+impl std::fmt::Display for IoBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IoBackend::Sync => write!(f, "sync"),
+            IoBackend::IoUring => write!(f, "io_uring"),
+        }
+    }
+}
+
+/// Built-in I/O engine used by the sync backend to access the test file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IoEngineKind {
+    /// Positioned `pread`/`pwrite`, one system call per block
+    #[default]
+    Pread,
+    /// Vectored `preadv`/`pwritev`, each block split into page-sized segments
+    Preadv,
+    /// Memory-mapped file; always uses the page cache, so direct I/O does not apply
+    Mmap,
+}
+
+impl std::fmt::Display for IoEngineKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IoEngineKind::Pread => write!(f, "pread"),
+            IoEngineKind::Preadv => write!(f, "preadv"),
+            IoEngineKind::Mmap => write!(f, "mmap"),
+        }
+    }
+}
+
+/// How the sync write test makes each record durable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SyncMethod {
+    /// `fsync` after every write, flushing the data and all file metadata
+    Fsync,
+    /// `fdatasync` after every write, flushing the data and only the metadata
+    /// needed to read it back
+    #[default]
+    Fdatasync,
+    /// Writes to a file opened with `O_DSYNC` (write-through on Windows), durable
+    /// once each write returns
+    Dsync,
+}
+
+impl SyncMethod {
+    /// Whether the method is supported on the current platform
+    pub fn is_available(self) -> bool {
+        match self {
+            SyncMethod::Fsync | SyncMethod::Fdatasync => true,
+            SyncMethod::Dsync => cfg!(any(target_os = "linux", target_os = "macos", windows)),
+        }
+    }
+}
+
+impl std::fmt::Display for SyncMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncMethod::Fsync => write!(f, "fsync"),
+            SyncMethod::Fdatasync => write!(f, "fdatasync"),
+            SyncMethod::Dsync => write!(f, "O_DSYNC"),
+        }
+    }
+}
+
+/// Share of the small-file test's files that have one size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileSizeShare {
+    /// File size in bytes
+    pub size: usize,
+    /// Weight of this size relative to the other sizes of the distribution
+    pub weight: u32,
+}
+
+impl FileSizeShare {
+    /// Default distribution, weighted towards small files like a source tree
+    pub const DEFAULT: [FileSizeShare; 5] = [
+        FileSizeShare::new(4 * 1024, 40),
+        FileSizeShare::new(16 * 1024, 25),
+        FileSizeShare::new(64 * 1024, 20),
+        FileSizeShare::new(256 * 1024, 10),
+        FileSizeShare::new(1024 * 1024, 5),
+    ];
+
+    pub const fn new(size: usize, weight: u32) -> Self {
+        Self { size, weight }
+    }
+}
+
+/// Amount of work after which each test stops
+///
+/// Byte, operation and pass limits count the measured phase only, after any
+/// warm-up, and are checked before each request, so a byte limit is exceeded by
+/// less than one block per worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TestLimit {
+    /// Run for `test_duration_seconds`
+    #[default]
+    Duration,
+    /// Stop after transferring this many bytes
+    Bytes(u64),
+    /// Stop after this many operations (blocks read, written or copied)
+    Operations(u64),
+    /// Stop after transferring the test file's size this many times
+    Passes(u64),
+}
+
+impl std::fmt::Display for TestLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TestLimit::Duration => write!(f, "duration"),
+            TestLimit::Bytes(bytes) => write!(f, "{} bytes", bytes),
+            TestLimit::Operations(operations) => write!(f, "{} operations", operations),
+            TestLimit::Passes(passes) => write!(f, "{} passes", passes),
+        }
+    }
+}
+
+/// Individual test that a benchmark run can include
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BenchmarkTest {
+    SequentialWrite,
+    SequentialRead,
+    RandomWrite,
+    RandomRead,
+    MixedRandom,
+    SyncWrite,
+    Metadata,
+    SmallFiles,
+    MemoryCopy,
+    MemoryStream,
+    SustainedWrite,
+}
+
+impl BenchmarkTest {
+    /// Every test
+    pub const ALL: [BenchmarkTest; 11] = [
+        BenchmarkTest::SequentialWrite,
+        BenchmarkTest::SequentialRead,
+        BenchmarkTest::RandomWrite,
+        BenchmarkTest::RandomRead,
+        BenchmarkTest::MixedRandom,
+        BenchmarkTest::SyncWrite,
+        BenchmarkTest::Metadata,
+        BenchmarkTest::SmallFiles,
+        BenchmarkTest::MemoryCopy,
+        BenchmarkTest::MemoryStream,
+        BenchmarkTest::SustainedWrite,
+    ];
+
+    /// Tests run when none are selected, in the default run order
+    ///
+    /// The sustained write test runs for minutes, the metadata and small-file tests
+    /// create thousands of files and the memory stream test keeps every CPU busy,
+    /// so they only run when selected.
+    pub const DEFAULT: [BenchmarkTest; 7] = [
+        BenchmarkTest::SequentialWrite,
+        BenchmarkTest::SequentialRead,
+        BenchmarkTest::RandomWrite,
+        BenchmarkTest::RandomRead,
+        BenchmarkTest::MixedRandom,
+        BenchmarkTest::SyncWrite,
+        BenchmarkTest::MemoryCopy,
+    ];
+
+    /// Whether the test accesses the test file on disk
+    ///
+    /// The sync write test commits its records to a small file of its own, and the
+    /// metadata and small-file tests work on directory trees of their own.
+    pub fn uses_test_file(self) -> bool {
+        !matches!(
+            self,
+            BenchmarkTest::MemoryCopy
+                | BenchmarkTest::MemoryStream
+                | BenchmarkTest::SyncWrite
+                | BenchmarkTest::Metadata
+                | BenchmarkTest::SmallFiles
+        )
+    }
+
+    /// Whether the test expects the test file to exist before it starts
+    pub fn needs_existing_file(self) -> bool {
+        self.uses_test_file()
+            && !matches!(
+                self,
+                BenchmarkTest::SequentialWrite | BenchmarkTest::SustainedWrite
+            )
+    }
+}
+
+impl std::fmt::Display for BenchmarkTest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BenchmarkTest::SequentialWrite => write!(f, "Sequential Write"),
+            BenchmarkTest::SequentialRead => write!(f, "Sequential Read"),
+            BenchmarkTest::RandomWrite => write!(f, "Random Write"),
+            BenchmarkTest::RandomRead => write!(f, "Random Read"),
+            BenchmarkTest::MixedRandom => write!(f, "Mixed Random"),
+            BenchmarkTest::SyncWrite => write!(f, "Sync Write"),
+            BenchmarkTest::Metadata => write!(f, "Metadata"),
+            BenchmarkTest::SmallFiles => write!(f, "Small Files"),
+            BenchmarkTest::MemoryCopy => write!(f, "Memory Copy"),
+            BenchmarkTest::MemoryStream => write!(f, "Memory Stream"),
+            BenchmarkTest::SustainedWrite => write!(f, "Sustained Write"),
+        }
+    }
+}
+
+/// CPUs the memory stream test's threads are pinned to
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThreadPinning {
+    /// Threads run wherever the OS schedules them
+    #[default]
+    Unpinned,
+    /// Each thread runs on one of these CPUs, dealt out round-robin
+    Cpus(Vec<usize>),
+    /// Each thread runs on the CPUs of one of these NUMA nodes, dealt out
+    /// round-robin, and allocates its memory there
+    NumaNodes(Vec<usize>),
+}
+
+impl std::fmt::Display for ThreadPinning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |items: &[usize]| {
+            items
+                .iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        match self {
+            ThreadPinning::Unpinned => write!(f, "unpinned"),
+            ThreadPinning::Cpus(cpus) => write!(f, "CPUs {}", list(cpus)),
+            ThreadPinning::NumaNodes(nodes) => write!(f, "NUMA nodes {}", list(nodes)),
+        }
+    }
+}
+
 /// Configuration parameters for benchmark execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkConfig {
@@ -19,6 +278,25 @@ pub struct BenchmarkConfig {
     /// Duration to run each test in seconds (default: 10)
     pub test_duration_seconds: u64,
 
+    /// Amount of work after which each test stops (default: `test_duration_seconds`)
+    pub test_limit: TestLimit,
+
+    /// Duration of the sustained write test in seconds (default: 300)
+    /// It ignores `test_limit` and the warm-up, as its start is what it measures
+    pub sustained_write_seconds: u64,
+
+    /// Longest a test with a byte, operation or pass limit may measure, in seconds
+    /// (default: none); the duration limit ignores it
+    pub time_cap_seconds: Option<u64>,
+
+    /// Warm-up in seconds before each test's measurement starts (default: 0)
+    /// I/O is issued during the warm-up but not sampled, and the measured
+    /// duration follows it in full
+    pub warmup_seconds: u64,
+
+    /// Number of times `run_benchmark_repeated` runs the selected tests (default: 1)
+    pub repetitions: usize,
+
     /// Whether to disable OS caching (default: true)
     pub disable_os_cache: bool,
 
@@ -28,6 +306,86 @@ pub struct BenchmarkConfig {
 
     /// Size of test file in MB (default: 1024)
     pub file_size_mb: usize,
+
+    /// Outstanding I/O requests per worker thread (default: 1)
+    /// The sync backend emulates this for random tests with concurrent workers;
+    /// the io_uring backend keeps this many requests in flight on every ring
+    pub queue_depth: usize,
+
+    /// Number of worker threads for random tests (default: 1)
+    pub thread_count: usize,
+
+    /// Share of reads in the mixed random test, in percent (default: 70)
+    pub mixed_read_percentage: u8,
+
+    /// Block size for the mixed random test (default: 8KB)
+    pub mixed_block_size: usize,
+
+    /// Backend used to issue disk I/O (default: sync)
+    pub io_backend: IoBackend,
+
+    /// Engine used by the sync backend to access the test file (default: pread)
+    pub io_engine: IoEngineKind,
+
+    /// Tests to run, in execution order (default: [`BenchmarkTest::DEFAULT`])
+    pub tests: Vec<BenchmarkTest>,
+
+    /// Content of the write buffers (default: sequential bytes)
+    pub data_pattern: TestDataPattern,
+
+    /// Size of each record the sync write test commits (default: 4KB)
+    pub sync_write_size: usize,
+
+    /// How the sync write test makes each record durable (default: fdatasync)
+    pub sync_method: SyncMethod,
+
+    /// Whether the sync write test appends its records instead of overwriting a
+    /// preallocated log (default: false)
+    /// Appends grow the file, so each commit also persists the new file size
+    pub sync_write_append: bool,
+
+    /// Number of files the metadata test creates, stats, renames and deletes
+    /// (default: 10000)
+    pub metadata_file_count: usize,
+
+    /// Number of directories the metadata test spreads its files across (default: 100)
+    pub metadata_fanout: usize,
+
+    /// Number of files the small-file test writes and reads back (default: 1000)
+    pub small_file_count: usize,
+
+    /// Sizes of the small-file test's files and how often each occurs
+    /// (default: [`FileSizeShare::DEFAULT`], 4KB to 1MB)
+    pub small_file_sizes: Vec<FileSizeShare>,
+
+    /// Smallest working set the memory test copies within, in bytes (default: 4KB)
+    /// The working set is the source and destination buffers together
+    pub memory_min_size: usize,
+
+    /// Largest working set the memory test copies within, in bytes (default: 512MB)
+    /// The test allocates this much memory, independently of the test file size
+    pub memory_max_size: usize,
+
+    /// Number of threads the memory stream test runs its kernels on
+    /// (default: one per available CPU)
+    pub memory_threads: usize,
+
+    /// CPUs or NUMA nodes the memory stream test's threads are pinned to
+    /// (default: unpinned)
+    pub memory_pinning: ThreadPinning,
+
+    /// Whether to stamp written blocks and check every block read back (default: false)
+    /// Requires block sizes that are multiples of 512 bytes
+    pub verify: bool,
+
+    /// Whether to keep the throughput and latency of every sampling interval in each
+    /// test's [`TestResult::time_series`](crate::core::TestResult::time_series) (default: false)
+    pub record_time_series: bool,
+
+    /// Token for stopping or pausing the run from another thread (default: a fresh token)
+    /// Not serialized; clone it before starting the benchmark to keep control
+    #[serde(skip)]
+    pub cancellation: CancellationToken,
 }
 
 impl Default for BenchmarkConfig {
@@ -37,9 +395,36 @@ impl Default for BenchmarkConfig {
             sequential_block_size: 4 * 1024 * 1024, // 4MB
             random_block_size: 4 * 1024,            // 4KB
             test_duration_seconds: 10,
+            test_limit: TestLimit::Duration,
+            sustained_write_seconds: 300,
+            time_cap_seconds: None,
+            warmup_seconds: 0,
+            repetitions: 1,
             disable_os_cache: true,
             disable_direct_io: false, // Enable direct I/O by default for performance
-            file_size_mb: 1024, // 1GB
+            file_size_mb: 1024,       // 1GB
+            queue_depth: 1,
+            thread_count: 1,
+            mixed_read_percentage: 70,
+            mixed_block_size: 8 * 1024, // 8KB
+            io_backend: IoBackend::Sync,
+            io_engine: IoEngineKind::Pread,
+            tests: BenchmarkTest::DEFAULT.to_vec(),
+            data_pattern: TestDataPattern::Sequential,
+            sync_write_size: 4 * 1024, // 4KB
+            sync_method: SyncMethod::Fdatasync,
+            sync_write_append: false,
+            metadata_file_count: 10_000,
+            metadata_fanout: 100,
+            small_file_count: 1000,
+            small_file_sizes: FileSizeShare::DEFAULT.to_vec(),
+            memory_min_size: 4 * 1024,          // 4KB
+            memory_max_size: 512 * 1024 * 1024, // 512MB
+            memory_threads: std::thread::available_parallelism().map_or(1, |count| count.get()),
+            memory_pinning: ThreadPinning::Unpinned,
+            verify: false,
+            record_time_series: false,
+            cancellation: CancellationToken::new(),
         }
     }
 }
@@ -68,6 +453,128 @@ impl BenchmarkConfig {
             ));
         }
 
+        if self.mixed_block_size == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Mixed block size must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.sync_write_size == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Sync write size must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.metadata_file_count == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Metadata file count must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.metadata_fanout == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Metadata fan-out must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.small_file_count == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Small file count must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.small_file_sizes.is_empty() {
+            return Err(BenchmarkError::ConfigurationError(
+                "At least one small file size must be given".to_string(),
+            ));
+        }
+
+        if self.small_file_sizes.iter().any(|share| share.size == 0) {
+            return Err(BenchmarkError::ConfigurationError(
+                "Small file sizes must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.small_file_sizes.iter().any(|share| share.weight == 0) {
+            return Err(BenchmarkError::ConfigurationError(
+                "Small file size weights must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.memory_min_size == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Memory working set sizes must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.memory_min_size > self.memory_max_size {
+            return Err(BenchmarkError::ConfigurationError(
+                "Smallest memory working set must not exceed the largest".to_string(),
+            ));
+        }
+
+        if self.memory_threads == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Memory thread count must be greater than 0".to_string(),
+            ));
+        }
+
+        match &self.memory_pinning {
+            ThreadPinning::Cpus(cpus) if cpus.is_empty() => {
+                return Err(BenchmarkError::ConfigurationError(
+                    "At least one CPU must be given to pin memory threads to".to_string(),
+                ));
+            }
+            ThreadPinning::NumaNodes(nodes) if nodes.is_empty() => {
+                return Err(BenchmarkError::ConfigurationError(
+                    "At least one NUMA node must be given to pin memory threads to".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        if self.mixed_read_percentage > 100 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Mixed read percentage must be between 0 and 100".to_string(),
+            ));
+        }
+
+        // Validate test selection
+        if self.tests.is_empty() {
+            return Err(BenchmarkError::ConfigurationError(
+                "At least one test must be selected".to_string(),
+            ));
+        }
+
+        for (index, test) in self.tests.iter().enumerate() {
+            if self.tests[..index].contains(test) {
+                return Err(BenchmarkError::ConfigurationError(format!(
+                    "The {} test is selected more than once",
+                    test
+                )));
+            }
+        }
+
+        self.data_pattern
+            .validate()
+            .map_err(BenchmarkError::ConfigurationError)?;
+
+        if self.verify {
+            let block_sizes = [
+                ("Sequential", self.sequential_block_size),
+                ("Random", self.random_block_size),
+                ("Mixed", self.mixed_block_size),
+            ];
+            for (name, size) in block_sizes {
+                if size % VERIFY_SECTOR_SIZE != 0 {
+                    return Err(BenchmarkError::ConfigurationError(format!(
+                        "{} block size must be a multiple of {} bytes when verifying data",
+                        name, VERIFY_SECTOR_SIZE
+                    )));
+                }
+            }
+        }
+
         // Validate test duration
         if self.test_duration_seconds == 0 {
             return Err(BenchmarkError::ConfigurationError(
@@ -75,6 +582,33 @@ impl BenchmarkConfig {
             ));
         }
 
+        if self.sustained_write_seconds == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Sustained write duration must be greater than 0".to_string(),
+            ));
+        }
+
+        match self.test_limit {
+            TestLimit::Bytes(0) | TestLimit::Operations(0) | TestLimit::Passes(0) => {
+                return Err(BenchmarkError::ConfigurationError(
+                    "Test limit must be greater than 0".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        if self.time_cap_seconds == Some(0) {
+            return Err(BenchmarkError::ConfigurationError(
+                "Time cap must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.repetitions == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Repetitions must be greater than 0".to_string(),
+            ));
+        }
+
         // Validate file size
         if self.file_size_mb == 0 {
             return Err(BenchmarkError::ConfigurationError(
@@ -82,6 +616,40 @@ impl BenchmarkConfig {
             ));
         }
 
+        // Validate random I/O concurrency
+        if self.queue_depth == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Queue depth must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.thread_count == 0 {
+            return Err(BenchmarkError::ConfigurationError(
+                "Thread count must be greater than 0".to_string(),
+            ));
+        }
+
+        if !self.io_backend.is_available() {
+            return Err(BenchmarkError::ConfigurationError(format!(
+                "The {} backend is not available in this build (requires Linux and the `io-uring` feature)",
+                self.io_backend
+            )));
+        }
+
+        if self.io_backend == IoBackend::IoUring && self.io_engine != IoEngineKind::Pread {
+            return Err(BenchmarkError::ConfigurationError(format!(
+                "The {} engine cannot be combined with the {} backend, which submits its own requests",
+                self.io_engine, self.io_backend
+            )));
+        }
+
+        if self.runs_test(BenchmarkTest::SyncWrite) && !self.sync_method.is_available() {
+            return Err(BenchmarkError::ConfigurationError(format!(
+                "{} sync writes are not supported on this platform",
+                self.sync_method
+            )));
+        }
+
         // Validate target path exists
         if !self.target_path.exists() {
             return Err(BenchmarkError::ConfigurationError(format!(
@@ -93,10 +661,27 @@ impl BenchmarkConfig {
         Ok(())
     }
 
+    /// Whether the configuration selects the given test
+    pub fn runs_test(&self, test: BenchmarkTest) -> bool {
+        self.tests.contains(&test)
+    }
+
     /// Get the test file size in bytes
     pub fn file_size_bytes(&self) -> u64 {
         (self.file_size_mb as u64) * 1024 * 1024
     }
+
+    /// Get the number of concurrent I/O workers used by random tests
+    ///
+    /// Blocking I/O can only keep one request in flight per worker, so each
+    /// thread's queue depth is emulated by that many concurrent workers. The
+    /// io_uring backend queues requests itself and needs one worker per thread.
+    pub fn random_worker_count(&self) -> usize {
+        match self.io_backend {
+            IoBackend::Sync => self.thread_count.saturating_mul(self.queue_depth).max(1),
+            IoBackend::IoUring => self.thread_count.max(1),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -110,8 +695,23 @@ mod tests {
         assert_eq!(config.sequential_block_size, 4 * 1024 * 1024);
         assert_eq!(config.random_block_size, 4 * 1024);
         assert_eq!(config.test_duration_seconds, 10);
+        assert_eq!(config.test_limit, TestLimit::Duration);
+        assert_eq!(config.time_cap_seconds, None);
+        assert_eq!(config.warmup_seconds, 0);
+        assert_eq!(config.repetitions, 1);
         assert!(config.disable_os_cache);
         assert_eq!(config.file_size_mb, 1024);
+        assert_eq!(config.queue_depth, 1);
+        assert_eq!(config.thread_count, 1);
+        assert_eq!(config.mixed_read_percentage, 70);
+        assert_eq!(config.mixed_block_size, 8 * 1024);
+        assert_eq!(config.io_backend, IoBackend::Sync);
+        assert_eq!(config.io_engine, IoEngineKind::Pread);
+        assert_eq!(config.tests, BenchmarkTest::DEFAULT.to_vec());
+        assert_eq!(config.sustained_write_seconds, 300);
+        assert_eq!(config.data_pattern, TestDataPattern::Sequential);
+        assert!(!config.verify);
+        assert!(!config.record_time_series);
     }
 
     #[test]
@@ -3,7 +3,7 @@
 #[cfg(test)]
 mod tests {
     use super::super::config::*;
-    use super::super::BenchmarkError;
+    use super::super::{BenchmarkError, TestDataPattern};
     use std::env;
     use std::fs;
     use std::path::PathBuf;
@@ -129,6 +129,223 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_validation_zero_queue_depth() {
+        let temp_dir = env::temp_dir();
+        let mut config = BenchmarkConfig::new(temp_dir);
+        config.queue_depth = 0;
+
+        match config.validate().unwrap_err() {
+            BenchmarkError::ConfigurationError(msg) => {
+                assert!(msg.contains("Queue depth must be greater than 0"));
+            }
+            _ => panic!("Expected ConfigurationError"),
+        }
+    }
+
+    #[test]
+    fn test_config_validation_zero_thread_count() {
+        let temp_dir = env::temp_dir();
+        let mut config = BenchmarkConfig::new(temp_dir);
+        config.thread_count = 0;
+
+        match config.validate().unwrap_err() {
+            BenchmarkError::ConfigurationError(msg) => {
+                assert!(msg.contains("Thread count must be greater than 0"));
+            }
+            _ => panic!("Expected ConfigurationError"),
+        }
+    }
+
+    #[test]
+    fn test_config_validation_zero_repetitions() {
+        let temp_dir = env::temp_dir();
+        let mut config = BenchmarkConfig::new(temp_dir);
+        config.repetitions = 0;
+
+        match config.validate().unwrap_err() {
+            BenchmarkError::ConfigurationError(msg) => {
+                assert!(msg.contains("Repetitions must be greater than 0"));
+            }
+            _ => panic!("Expected ConfigurationError"),
+        }
+    }
+
+    #[test]
+    fn test_config_validation_test_limit() {
+        let temp_dir = env::temp_dir();
+        let mut config = BenchmarkConfig::new(temp_dir);
+
+        for limit in [
+            TestLimit::Bytes(0),
+            TestLimit::Operations(0),
+            TestLimit::Passes(0),
+        ] {
+            config.test_limit = limit;
+            match config.validate().unwrap_err() {
+                BenchmarkError::ConfigurationError(msg) => {
+                    assert!(msg.contains("Test limit must be greater than 0"));
+                }
+                _ => panic!("Expected ConfigurationError"),
+            }
+        }
+
+        config.test_limit = TestLimit::Passes(2);
+        assert!(config.validate().is_ok());
+        config.time_cap_seconds = Some(0);
+        match config.validate().unwrap_err() {
+            BenchmarkError::ConfigurationError(msg) => {
+                assert!(msg.contains("Time cap must be greater than 0"));
+            }
+            _ => panic!("Expected ConfigurationError"),
+        }
+    }
+
+    #[test]
+    fn test_config_validation_mixed_workload() {
+        let temp_dir = env::temp_dir();
+        let mut config = BenchmarkConfig::new(temp_dir);
+
+        // Pure read and pure write mixes are both valid
+        for percentage in [0, 70, 100] {
+            config.mixed_read_percentage = percentage;
+            assert!(config.validate().is_ok());
+        }
+
+        config.mixed_read_percentage = 101;
+        match config.validate().unwrap_err() {
+            BenchmarkError::ConfigurationError(msg) => {
+                assert!(msg.contains("Mixed read percentage"));
+            }
+            _ => panic!("Expected ConfigurationError"),
+        }
+
+        config.mixed_read_percentage = 70;
+        config.mixed_block_size = 0;
+        match config.validate().unwrap_err() {
+            BenchmarkError::ConfigurationError(msg) => {
+                assert!(msg.contains("Mixed block size"));
+            }
+            _ => panic!("Expected ConfigurationError"),
+        }
+    }
+
+    #[test]
+    fn test_config_validation_data_pattern() {
+        let mut config = BenchmarkConfig::new(env::temp_dir());
+
+        config.data_pattern = TestDataPattern::Compressible(3.0);
+        assert!(config.validate().is_ok());
+
+        config.data_pattern = TestDataPattern::Compressible(0.0);
+        match config.validate().unwrap_err() {
+            BenchmarkError::ConfigurationError(msg) => {
+                assert!(msg.contains("Compression ratio"));
+            }
+            _ => panic!("Expected ConfigurationError"),
+        }
+    }
+
+    #[test]
+    fn test_config_validation_test_selection() {
+        let mut config = BenchmarkConfig::new(env::temp_dir());
+
+        // Any non-empty subset in any order is valid
+        config.tests = vec![BenchmarkTest::RandomRead, BenchmarkTest::SequentialWrite];
+        assert!(config.validate().is_ok());
+        assert!(config.runs_test(BenchmarkTest::RandomRead));
+        assert!(!config.runs_test(BenchmarkTest::MemoryCopy));
+
+        config.tests.clear();
+        match config.validate().unwrap_err() {
+            BenchmarkError::ConfigurationError(msg) => {
+                assert!(msg.contains("At least one test"));
+            }
+            _ => panic!("Expected ConfigurationError"),
+        }
+
+        config.tests = vec![BenchmarkTest::MemoryCopy, BenchmarkTest::MemoryCopy];
+        match config.validate().unwrap_err() {
+            BenchmarkError::ConfigurationError(msg) => {
+                assert!(msg.contains("Memory Copy test is selected more than once"));
+            }
+            _ => panic!("Expected ConfigurationError"),
+        }
+    }
+
+    #[test]
+    fn test_benchmark_test_file_requirements() {
+        assert!(!BenchmarkTest::SequentialWrite.needs_existing_file());
+        assert!(BenchmarkTest::SequentialWrite.uses_test_file());
+        assert!(BenchmarkTest::RandomRead.needs_existing_file());
+        assert!(BenchmarkTest::MixedRandom.needs_existing_file());
+        assert!(!BenchmarkTest::MemoryCopy.uses_test_file());
+        assert!(!BenchmarkTest::MemoryCopy.needs_existing_file());
+        assert!(!BenchmarkTest::SyncWrite.uses_test_file());
+        assert!(!BenchmarkTest::SyncWrite.needs_existing_file());
+        assert!(!BenchmarkTest::Metadata.uses_test_file());
+    }
+
+    #[test]
+    fn test_random_worker_count() {
+        let mut config = BenchmarkConfig::default();
+        assert_eq!(config.random_worker_count(), 1);
+
+        // CrystalDiskMark-style Q32T16
+        config.queue_depth = 32;
+        config.thread_count = 16;
+        assert_eq!(config.random_worker_count(), 512);
+
+        // io_uring keeps the queue depth on the ring, one worker per thread
+        config.io_backend = IoBackend::IoUring;
+        assert_eq!(config.random_worker_count(), 16);
+    }
+
+    #[test]
+    fn test_config_validation_io_backend_availability() {
+        let temp_dir = env::temp_dir();
+        let mut config = BenchmarkConfig::new(temp_dir);
+        config.io_backend = IoBackend::IoUring;
+
+        if IoBackend::IoUring.is_available() {
+            assert!(config.validate().is_ok());
+        } else {
+            match config.validate().unwrap_err() {
+                BenchmarkError::ConfigurationError(msg) => {
+                    assert!(msg.contains("io_uring"));
+                }
+                _ => panic!("Expected ConfigurationError"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_validation_io_engine() {
+        let temp_dir = env::temp_dir();
+        let mut config = BenchmarkConfig::new(temp_dir);
+
+        for engine in [
+            IoEngineKind::Pread,
+            IoEngineKind::Preadv,
+            IoEngineKind::Mmap,
+        ] {
+            config.io_engine = engine;
+            assert!(config.validate().is_ok());
+        }
+
+        // io_uring submits its own requests, so only the default engine is allowed
+        if IoBackend::IoUring.is_available() {
+            config.io_backend = IoBackend::IoUring;
+            config.io_engine = IoEngineKind::Mmap;
+            match config.validate().unwrap_err() {
+                BenchmarkError::ConfigurationError(msg) => {
+                    assert!(msg.contains("mmap"));
+                }
+                _ => panic!("Expected ConfigurationError"),
+            }
+        }
+    }
+
     #[test]
     fn test_config_validation_nonexistent_path() {
         let nonexistent_path = PathBuf::from("/nonexistent/path/that/should/not/exist");
@@ -333,6 +550,127 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_config_verify_requires_sector_multiple_block_sizes() {
+        let mut config = BenchmarkConfig::new(env::temp_dir());
+        config.random_block_size = 1000;
+        assert!(config.validate().is_ok());
+
+        config.verify = true;
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Random block size must be a multiple of 512 bytes"));
+
+        config.random_block_size = 4096;
+        config.mixed_block_size = 4608;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_sustained_write_duration() {
+        let mut config = BenchmarkConfig::new(env::temp_dir());
+        assert!(!config.tests.contains(&BenchmarkTest::SustainedWrite));
+
+        config.sustained_write_seconds = 0;
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Sustained write duration must be greater than 0"));
+    }
+
+    #[test]
+    fn test_config_sync_write() {
+        let mut config = BenchmarkConfig::new(env::temp_dir());
+        assert!(config.tests.contains(&BenchmarkTest::SyncWrite));
+        assert_eq!(config.sync_method, SyncMethod::Fdatasync);
+
+        config.sync_write_size = 0;
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Sync write size must be greater than 0"));
+    }
+
+    #[test]
+    fn test_config_metadata() {
+        let mut config = BenchmarkConfig::new(env::temp_dir());
+        assert!(!config.tests.contains(&BenchmarkTest::Metadata));
+
+        config.metadata_file_count = 0;
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Metadata file count must be greater than 0"));
+
+        config.metadata_file_count = 100;
+        config.metadata_fanout = 0;
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Metadata fan-out must be greater than 0"));
+    }
+
+    #[test]
+    fn test_config_small_files() {
+        let mut config = BenchmarkConfig::new(env::temp_dir());
+        assert!(!config.tests.contains(&BenchmarkTest::SmallFiles));
+        assert!(!BenchmarkTest::SmallFiles.uses_test_file());
+
+        config.small_file_count = 0;
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Small file count must be greater than 0"));
+
+        config.small_file_count = 100;
+        config.small_file_sizes.clear();
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("At least one small file size must be given"));
+
+        config.small_file_sizes = vec![FileSizeShare::new(0, 1)];
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Small file sizes must be greater than 0"));
+
+        config.small_file_sizes = vec![FileSizeShare::new(4096, 0)];
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Small file size weights must be greater than 0"));
+    }
+
+    #[test]
+    fn test_config_memory_sizes() {
+        let mut config = BenchmarkConfig::new(env::temp_dir());
+        assert_eq!(config.memory_min_size, 4 * 1024);
+        assert_eq!(config.memory_max_size, 512 * 1024 * 1024);
+
+        config.memory_min_size = 0;
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Memory working set sizes must be greater than 0"));
+
+        config.memory_min_size = 1024 * 1024;
+        config.memory_max_size = 64 * 1024;
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Smallest memory working set must not exceed the largest"));
+
+        // A single working set is allowed
+        config.memory_max_size = config.memory_min_size;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_memory_stream() {
+        let mut config = BenchmarkConfig::new(env::temp_dir());
+        assert!(!config.tests.contains(&BenchmarkTest::MemoryStream));
+        assert!(!BenchmarkTest::MemoryStream.uses_test_file());
+        assert!(config.memory_threads > 0);
+        assert_eq!(config.memory_pinning, ThreadPinning::Unpinned);
+
+        config.memory_threads = 0;
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Memory thread count must be greater than 0"));
+
+        config.memory_threads = 2;
+        config.memory_pinning = ThreadPinning::Cpus(Vec::new());
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("At least one CPU must be given to pin memory threads to"));
+
+        config.memory_pinning = ThreadPinning::NumaNodes(Vec::new());
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("At least one NUMA node must be given to pin memory threads to"));
+
+        config.memory_pinning = ThreadPinning::Cpus(vec![0, 1]);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.memory_pinning.to_string(), "CPUs 0,1");
+    }
+
     #[test]
     fn test_file_size_bytes_overflow_protection() {
         let temp_dir = env::temp_dir();
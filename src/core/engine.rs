@@ -0,0 +1,667 @@
+//! Pluggable I/O engines used by the disk benchmark tests
+//!
+//! An [`IoEngine`] opens the test file and hands back an [`IoHandle`] that the test
+//! loops drive with positioned reads and writes. Every test runs unchanged on any
+//! engine, so new access methods only need to implement these two traits.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use disk_speed_test::core::engine::{IoEngine, IoHandle, IoOpenOptions, PreadEngine};
+//! use disk_speed_test::{run_benchmark_with_engine, BenchmarkConfig, BenchmarkError, BenchmarkTest};
+//! use std::path::Path;
+//!
+//! /// Engine that logs every file it opens before delegating to `pread`/`pwrite`
+//! struct LoggingEngine;
+//!
+//! impl IoEngine for LoggingEngine {
+//!     fn name(&self) -> &str {
+//!         "logging"
+//!     }
+//!
+//!     fn open(
+//!         &self,
+//!         path: &Path,
+//!         options: &IoOpenOptions,
+//!     ) -> Result<Box<dyn IoHandle>, BenchmarkError> {
+//!         println!("opening {} ({:?})", path.display(), options);
+//!         PreadEngine.open(path, options)
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), BenchmarkError> {
+//! let mut config = BenchmarkConfig::new(".".into());
+//! config.tests = vec![BenchmarkTest::RandomRead];
+//! let results = run_benchmark_with_engine(config, Box::new(LoggingEngine), None)?;
+//! if let Some(result) = &results.random_read {
+//!     println!("Random Read: {:.2} MB/s", result.avg_speed_mbps);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::config::IoEngineKind;
+use crate::core::BenchmarkError;
+use std::fs::{File, OpenOptions};
+use std::io::{self, IoSlice, IoSliceMut};
+use std::path::Path;
+use std::sync::{PoisonError, RwLock};
+
+/// How a test wants its file opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IoOpenOptions {
+    /// Open for writing as well as reading
+    pub write: bool,
+    /// Create (or truncate) the file and preallocate it to this many bytes
+    pub create_len: Option<u64>,
+    /// Bypass the OS page cache where the engine supports it
+    pub direct_io: bool,
+}
+
+impl IoOpenOptions {
+    /// Create a new file of `len` bytes for writing
+    pub fn create(len: u64, direct_io: bool) -> Self {
+        Self {
+            write: true,
+            create_len: Some(len),
+            direct_io,
+        }
+    }
+
+    /// Open an existing file for reading
+    pub fn read(direct_io: bool) -> Self {
+        Self {
+            write: false,
+            create_len: None,
+            direct_io,
+        }
+    }
+
+    /// Open an existing file for writing
+    pub fn write(direct_io: bool) -> Self {
+        Self {
+            write: true,
+            create_len: None,
+            direct_io,
+        }
+    }
+}
+
+/// Factory for the file handles a benchmark test runs against
+///
+/// Engines must be `Send + Sync` because a benchmark may run on a background thread.
+pub trait IoEngine: Send + Sync {
+    /// Short name shown in configuration output (e.g. "pread")
+    fn name(&self) -> &str;
+
+    /// Open or create the test file at `path`
+    fn open(
+        &self,
+        path: &Path,
+        options: &IoOpenOptions,
+    ) -> Result<Box<dyn IoHandle>, BenchmarkError>;
+}
+
+/// An open test file that supports positioned I/O
+///
+/// Handles are shared by reference between the worker threads of a random test, so
+/// every method takes `&self` and must not depend on a shared file cursor.
+pub trait IoHandle: Send + Sync {
+    /// Read into `buf` starting at `offset`, returning the number of bytes read
+    ///
+    /// A return value of 0 means `offset` is at or past the end of the file.
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize>;
+
+    /// Write `buf` starting at `offset`, returning the number of bytes written
+    fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize>;
+
+    /// Scatter a read starting at `offset` across `bufs`
+    ///
+    /// The default implementation issues one `read_at` per buffer and stops at the
+    /// first short read.
+    fn read_vectored_at(&self, bufs: &mut [IoSliceMut<'_>], offset: u64) -> io::Result<usize> {
+        let mut total = 0;
+        for buf in bufs.iter_mut() {
+            let n = self.read_at(buf, offset + total as u64)?;
+            total += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    /// Gather a write starting at `offset` from `bufs`
+    ///
+    /// The default implementation issues one `write_at` per buffer and stops at the
+    /// first short write.
+    fn write_vectored_at(&self, bufs: &[IoSlice<'_>], offset: u64) -> io::Result<usize> {
+        let mut total = 0;
+        for buf in bufs {
+            let n = self.write_at(buf, offset + total as u64)?;
+            total += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    /// Hand any data buffered by the engine to the operating system
+    ///
+    /// Durability is handled separately by the tests through
+    /// [`crate::platform::sync_file_system`].
+    fn flush(&self) -> io::Result<()>;
+
+    /// The underlying file, for backends that submit I/O on the descriptor directly
+    ///
+    /// Engines that do not go through a regular file handle return `None`.
+    fn as_file(&self) -> Option<&File> {
+        None
+    }
+}
+
+impl IoEngineKind {
+    /// Create the built-in engine for this kind
+    pub fn engine(self) -> Box<dyn IoEngine> {
+        match self {
+            IoEngineKind::Pread => Box::new(PreadEngine),
+            IoEngineKind::Preadv => Box::new(PreadvEngine),
+            IoEngineKind::Mmap => Box::new(MmapEngine),
+        }
+    }
+}
+
+/// Open the test file with the platform's direct I/O helpers or plain buffered I/O
+fn open_test_file(path: &Path, options: &IoOpenOptions) -> Result<File, BenchmarkError> {
+    match (options.create_len, options.direct_io) {
+        (Some(len), true) => Ok(crate::platform::create_direct_io_file(path, len)?),
+        (None, true) => Ok(crate::platform::open_direct_io_file(path, options.write)?),
+        (Some(len), false) => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            let file = OpenOptions::new()
+                .create(true)
+                .read(true)
+                .write(true)
+                .truncate(true)
+                .open(path)?;
+
+            // Pre-allocate the file size for consistency with direct I/O path
+            file.set_len(len)?;
+            Ok(file)
+        }
+        (None, false) => Ok(OpenOptions::new()
+            .read(true)
+            .write(options.write)
+            .open(path)?),
+    }
+}
+
+/// Positioned read that leaves the shared file cursor untouched
+#[cfg(unix)]
+fn file_read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    use std::os::unix::fs::FileExt;
+    file.read_at(buf, offset)
+}
+
+/// Positioned read; on Windows this also moves the file cursor, which nothing else relies on
+#[cfg(windows)]
+fn file_read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    use std::os::windows::fs::FileExt;
+    file.seek_read(buf, offset)
+}
+
+/// Positioned write that leaves the shared file cursor untouched
+#[cfg(unix)]
+fn file_write_at(file: &File, buf: &[u8], offset: u64) -> io::Result<usize> {
+    use std::os::unix::fs::FileExt;
+    file.write_at(buf, offset)
+}
+
+/// Positioned write; on Windows this also moves the file cursor, which nothing else relies on
+#[cfg(windows)]
+fn file_write_at(file: &File, buf: &[u8], offset: u64) -> io::Result<usize> {
+    use std::os::windows::fs::FileExt;
+    file.seek_write(buf, offset)
+}
+
+/// Blocking positioned I/O with one system call per block (`pread`/`pwrite`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreadEngine;
+
+impl IoEngine for PreadEngine {
+    fn name(&self) -> &str {
+        "pread"
+    }
+
+    fn open(
+        &self,
+        path: &Path,
+        options: &IoOpenOptions,
+    ) -> Result<Box<dyn IoHandle>, BenchmarkError> {
+        Ok(Box::new(PreadHandle {
+            file: open_test_file(path, options)?,
+        }))
+    }
+}
+
+/// File handle used by [`PreadEngine`]
+struct PreadHandle {
+    file: File,
+}
+
+impl IoHandle for PreadHandle {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        file_read_at(&self.file, buf, offset)
+    }
+
+    fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+        file_write_at(&self.file, buf, offset)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn as_file(&self) -> Option<&File> {
+        Some(&self.file)
+    }
+}
+
+/// Size of each segment a block is split into by [`PreadvEngine`]
+pub const VECTOR_SEGMENT_SIZE: usize = 4096;
+
+/// Most segments passed to a single `preadv`/`pwritev` call (Linux `IOV_MAX`)
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const MAX_SEGMENTS_PER_CALL: usize = 1024;
+
+/// Blocking vectored I/O (`preadv`/`pwritev`)
+///
+/// Each block is split into [`VECTOR_SEGMENT_SIZE`] segments and submitted as one
+/// scatter/gather request. Platforms without `preadv` fall back to one positioned
+/// call per segment.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreadvEngine;
+
+impl IoEngine for PreadvEngine {
+    fn name(&self) -> &str {
+        "preadv"
+    }
+
+    fn open(
+        &self,
+        path: &Path,
+        options: &IoOpenOptions,
+    ) -> Result<Box<dyn IoHandle>, BenchmarkError> {
+        Ok(Box::new(PreadvHandle {
+            file: open_test_file(path, options)?,
+        }))
+    }
+}
+
+/// File handle used by [`PreadvEngine`]
+struct PreadvHandle {
+    file: File,
+}
+
+impl IoHandle for PreadvHandle {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        let mut segments: Vec<IoSliceMut<'_>> = buf
+            .chunks_mut(VECTOR_SEGMENT_SIZE)
+            .map(IoSliceMut::new)
+            .collect();
+        self.read_vectored_at(&mut segments, offset)
+    }
+
+    fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+        let segments: Vec<IoSlice<'_>> =
+            buf.chunks(VECTOR_SEGMENT_SIZE).map(IoSlice::new).collect();
+        self.write_vectored_at(&segments, offset)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn read_vectored_at(&self, bufs: &mut [IoSliceMut<'_>], offset: u64) -> io::Result<usize> {
+        use std::os::unix::io::AsRawFd;
+
+        let mut total = 0;
+        for chunk in bufs.chunks_mut(MAX_SEGMENTS_PER_CALL) {
+            let requested: usize = chunk.iter().map(|buf| buf.len()).sum();
+            // SAFETY: IoSliceMut is ABI compatible with iovec, and every slice stays
+            // borrowed for the duration of the call
+            let n = unsafe {
+                libc::preadv(
+                    self.file.as_raw_fd(),
+                    chunk.as_ptr().cast(),
+                    chunk.len() as libc::c_int,
+                    (offset + total as u64) as libc::off_t,
+                )
+            };
+            if n < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            total += n as usize;
+            if (n as usize) < requested {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn write_vectored_at(&self, bufs: &[IoSlice<'_>], offset: u64) -> io::Result<usize> {
+        use std::os::unix::io::AsRawFd;
+
+        let mut total = 0;
+        for chunk in bufs.chunks(MAX_SEGMENTS_PER_CALL) {
+            let requested: usize = chunk.iter().map(|buf| buf.len()).sum();
+            // SAFETY: IoSlice is ABI compatible with iovec, and every slice stays
+            // borrowed for the duration of the call
+            let n = unsafe {
+                libc::pwritev(
+                    self.file.as_raw_fd(),
+                    chunk.as_ptr().cast(),
+                    chunk.len() as libc::c_int,
+                    (offset + total as u64) as libc::off_t,
+                )
+            };
+            if n < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            total += n as usize;
+            if (n as usize) < requested {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn as_file(&self) -> Option<&File> {
+        Some(&self.file)
+    }
+}
+
+/// Memory-mapped I/O: reads and writes are plain memory copies into a shared mapping
+///
+/// Mappings always go through the page cache, so direct I/O does not apply to this
+/// engine and the request is ignored. Workers sharing a writable mapping lock each
+/// megabyte their copy touches, so copies into and out of the same bytes never run
+/// at the same time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MmapEngine;
+
+impl IoEngine for MmapEngine {
+    fn name(&self) -> &str {
+        "mmap"
+    }
+
+    fn open(
+        &self,
+        path: &Path,
+        options: &IoOpenOptions,
+    ) -> Result<Box<dyn IoHandle>, BenchmarkError> {
+        let buffered = IoOpenOptions {
+            direct_io: false,
+            ..*options
+        };
+        let file = open_test_file(path, &buffered)?;
+
+        let map = if options.write {
+            memmap2::MmapOptions::new().map_raw(&file)?
+        } else {
+            memmap2::MmapOptions::new().map_raw_read_only(&file)?
+        };
+
+        Ok(Box::new(MmapHandle::new(map, options.write)))
+    }
+}
+
+/// Bytes of a writable mapping guarded by one lock of [`MmapHandle`]
+const MMAP_LOCK_REGION: u64 = 1024 * 1024;
+
+/// Mapping used by [`MmapEngine`]
+struct MmapHandle {
+    map: memmap2::MmapRaw,
+    writable: bool,
+    /// One lock per [`MMAP_LOCK_REGION`] of the mapping; writes lock their regions
+    /// exclusively and reads share them
+    regions: Box<[RwLock<()>]>,
+}
+
+impl MmapHandle {
+    fn new(map: memmap2::MmapRaw, writable: bool) -> Self {
+        let count = (map.len() as u64 + MMAP_LOCK_REGION - 1) / MMAP_LOCK_REGION;
+        Self {
+            map,
+            writable,
+            regions: (0..count.max(1)).map(|_| RwLock::new(())).collect(),
+        }
+    }
+
+    /// Locks of the regions a non-empty transfer of `len` bytes at `offset` touches
+    ///
+    /// They are in ascending order, the order every transfer takes them in, so two
+    /// transfers never wait on each other's locks.
+    fn regions(&self, offset: u64, len: usize) -> &[RwLock<()>] {
+        let first = (offset / MMAP_LOCK_REGION) as usize;
+        let last = ((offset + len as u64 - 1) / MMAP_LOCK_REGION) as usize;
+        &self.regions[first..=last]
+    }
+
+    /// Clamp a transfer of `len` bytes at `offset` to the mapped range
+    fn transfer_len(&self, offset: u64, len: usize) -> usize {
+        let remaining = (self.map.len() as u64).saturating_sub(offset);
+        std::cmp::min(len as u64, remaining) as usize
+    }
+}
+
+impl IoHandle for MmapHandle {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        let len = self.transfer_len(offset, buf.len());
+        if len > 0 {
+            // Only a writable mapping can be written through this handle meanwhile
+            let _guards: Vec<_> = if self.writable {
+                self.regions(offset, len)
+                    .iter()
+                    .map(|lock| lock.read().unwrap_or_else(PoisonError::into_inner))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            // SAFETY: `offset + len` lies within the mapping, which outlives this call.
+            // No write through this handle overlaps the copy, since writes hold their
+            // regions' locks exclusively. The test file is private to the benchmark,
+            // so nothing outside this handle writes the mapped bytes.
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    self.map.as_ptr().add(offset as usize),
+                    buf.as_mut_ptr(),
+                    len,
+                );
+            }
+        }
+        Ok(len)
+    }
+
+    fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+        if !self.writable {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "mapping was opened read-only",
+            ));
+        }
+
+        let len = self.transfer_len(offset, buf.len());
+        if len > 0 {
+            let _guards: Vec<_> = self
+                .regions(offset, len)
+                .iter()
+                .map(|lock| lock.write().unwrap_or_else(PoisonError::into_inner))
+                .collect();
+            // SAFETY: `offset + len` lies within the writable mapping, which outlives
+            // this call. The exclusive locks on its regions keep every other read or
+            // write through this handle off these bytes until the copy is done, and
+            // the test file is private to the benchmark, so the copy has them alone.
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    buf.as_ptr(),
+                    self.map.as_mut_ptr().add(offset as usize),
+                    len,
+                );
+            }
+        }
+        Ok(len)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        if self.writable {
+            self.map.flush_async()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FILE_LEN: u64 = 64 * 1024;
+
+    fn pattern(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn round_trip(engine: &dyn IoEngine) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(format!("{}.dat", engine.name()));
+
+        // A block that spans several vector segments and ends off a segment boundary
+        let data = pattern(3 * VECTOR_SEGMENT_SIZE + 100);
+
+        let handle = engine
+            .open(&path, &IoOpenOptions::create(FILE_LEN, false))
+            .unwrap();
+        assert_eq!(handle.write_at(&data, 8192).unwrap(), data.len());
+        handle.flush().unwrap();
+        drop(handle);
+
+        let handle = engine.open(&path, &IoOpenOptions::read(false)).unwrap();
+        let mut buf = vec![0u8; data.len()];
+        assert_eq!(handle.read_at(&mut buf, 8192).unwrap(), data.len());
+        assert_eq!(buf, data);
+
+        // Reads are clamped at the end of the file
+        let mut tail = vec![0u8; 4096];
+        assert_eq!(handle.read_at(&mut tail, FILE_LEN - 1000).unwrap(), 1000);
+        assert_eq!(handle.read_at(&mut tail, FILE_LEN).unwrap(), 0);
+
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), FILE_LEN);
+    }
+
+    #[test]
+    fn test_pread_engine_round_trip() {
+        round_trip(&PreadEngine);
+    }
+
+    #[test]
+    fn test_preadv_engine_round_trip() {
+        round_trip(&PreadvEngine);
+    }
+
+    #[test]
+    fn test_mmap_engine_round_trip() {
+        round_trip(&MmapEngine);
+    }
+
+    #[test]
+    fn test_vectored_io_spans_buffers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("vectored.dat");
+
+        for engine in [
+            IoEngineKind::Pread,
+            IoEngineKind::Preadv,
+            IoEngineKind::Mmap,
+        ] {
+            let engine = engine.engine();
+            let handle = engine
+                .open(&path, &IoOpenOptions::create(FILE_LEN, false))
+                .unwrap();
+
+            let first = [1u8; 10];
+            let second = [2u8; 20];
+            let written = handle
+                .write_vectored_at(&[IoSlice::new(&first), IoSlice::new(&second)], 100)
+                .unwrap();
+            assert_eq!(written, 30, "{} write", engine.name());
+
+            let mut a = [0u8; 15];
+            let mut b = [0u8; 15];
+            let read = handle
+                .read_vectored_at(&mut [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)], 100)
+                .unwrap();
+            assert_eq!(read, 30, "{} read", engine.name());
+            assert_eq!(a[..10], [1u8; 10]);
+            assert_eq!(a[10..], [2u8; 5]);
+            assert_eq!(b, [2u8; 15]);
+        }
+    }
+
+    #[test]
+    fn test_mmap_concurrent_writes_do_not_tear() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("concurrent.dat");
+        let handle = MmapEngine
+            .open(&path, &IoOpenOptions::create(4 * MMAP_LOCK_REGION, false))
+            .unwrap();
+        // Blocks straddle a region boundary, so each copy takes two locks
+        let block = MMAP_LOCK_REGION as usize;
+        let offset = MMAP_LOCK_REGION / 2;
+
+        std::thread::scope(|scope| {
+            for worker in 1..=4u8 {
+                let handle = &handle;
+                scope.spawn(move || {
+                    let data = vec![worker; block];
+                    let mut read_back = vec![0u8; block];
+                    for _ in 0..20 {
+                        handle.write_at(&data, offset).unwrap();
+                        handle.read_at(&mut read_back, offset).unwrap();
+                        assert!(read_back.iter().all(|&byte| byte == read_back[0]));
+                    }
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn test_mmap_read_only_rejects_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("readonly.dat");
+        drop(
+            MmapEngine
+                .open(&path, &IoOpenOptions::create(FILE_LEN, false))
+                .unwrap(),
+        );
+
+        let handle = MmapEngine.open(&path, &IoOpenOptions::read(false)).unwrap();
+        let err = handle.write_at(&[0u8; 16], 0).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(handle.as_file().is_none());
+    }
+
+    #[test]
+    fn test_engine_kind_names() {
+        assert_eq!(IoEngineKind::Pread.engine().name(), "pread");
+        assert_eq!(IoEngineKind::Preadv.engine().name(), "preadv");
+        assert_eq!(IoEngineKind::Mmap.engine().name(), "mmap");
+    }
+}
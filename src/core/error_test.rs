@@ -68,6 +68,15 @@ mod tests {
         assert!(error_string.contains("User cancelled"));
     }
 
+    #[test]
+    fn test_benchmark_error_data_corruption() {
+        let corruption_error = BenchmarkError::DataCorruption("3 corrupted sectors".to_string());
+
+        let error_string = corruption_error.to_string();
+        assert!(error_string.contains("Data corruption detected"));
+        assert!(error_string.contains("3 corrupted sectors"));
+    }
+
     #[test]
     fn test_benchmark_error_from_platform_error() {
         let platform_error = PlatformError::DirectIoNotSupported;
@@ -0,0 +1,188 @@
+//! Running a benchmark on a background thread
+
+use crate::core::{
+    run_benchmark_with_engine, BenchmarkConfig, BenchmarkError, BenchmarkResults,
+    CancellationToken, ChannelProgressCallback, IoEngine, ProgressEvent,
+};
+use std::sync::mpsc::{self, Receiver};
+use std::thread::{self, JoinHandle};
+
+/// A benchmark running on a background thread
+///
+/// Progress arrives as [`ProgressEvent`]s on [`events`](Self::events), which
+/// disconnects when the run ends, so an event loop can poll it with `try_recv` and
+/// a worker thread can simply iterate it. [`join`](Self::join) waits for the
+/// results. Dropping the handle leaves the run going in the background; cancel it
+/// first to stop it.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use disk_speed_test::{BenchmarkConfig, BenchmarkHandle, ProgressEvent};
+/// use std::path::PathBuf;
+///
+/// # fn main() -> Result<(), disk_speed_test::BenchmarkError> {
+/// let handle = BenchmarkHandle::start(BenchmarkConfig::new(PathBuf::from(".")))?;
+/// for event in handle.events() {
+///     if let ProgressEvent::Update(update) = event {
+///         println!(
+///             "{}: {:.0}% at {:.2} MB/s",
+///             update.test_name,
+///             update.fraction * 100.0,
+///             update.current_speed_mbps
+///         );
+///     }
+/// }
+/// let results = handle.join()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BenchmarkHandle {
+    events: Receiver<ProgressEvent>,
+    cancellation: CancellationToken,
+    thread: JoinHandle<Result<BenchmarkResults, BenchmarkError>>,
+}
+
+impl BenchmarkHandle {
+    /// Validate `config` and start the selected tests on a background thread
+    ///
+    /// # Errors
+    ///
+    /// Returns `BenchmarkError` if the configuration is invalid or the thread
+    /// cannot be spawned; errors during the run are returned by [`join`](Self::join).
+    pub fn start(config: BenchmarkConfig) -> Result<Self, BenchmarkError> {
+        let engine = config.io_engine.engine();
+        Self::start_with_engine(config, engine)
+    }
+
+    /// Start the selected tests on a background thread against a caller-provided I/O engine
+    ///
+    /// # Errors
+    ///
+    /// Returns `BenchmarkError` if the configuration is invalid or the thread
+    /// cannot be spawned.
+    pub fn start_with_engine(
+        config: BenchmarkConfig,
+        engine: Box<dyn IoEngine>,
+    ) -> Result<Self, BenchmarkError> {
+        config.validate()?;
+
+        let (sender, events) = mpsc::channel();
+        let cancellation = config.cancellation.clone();
+        let thread = thread::Builder::new()
+            .name("disk-speed-test".to_string())
+            .spawn(move || {
+                let callback = ChannelProgressCallback::new(sender);
+                run_benchmark_with_engine(config, engine, Some(Box::new(callback)))
+            })?;
+
+        Ok(Self {
+            events,
+            cancellation,
+            thread,
+        })
+    }
+
+    /// Progress events of the run; disconnected once it has ended
+    pub fn events(&self) -> &Receiver<ProgressEvent> {
+        &self.events
+    }
+
+    /// Token that stops or pauses the run
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+
+    /// Stop the run; [`join`](Self::join) then returns the tests that finished
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Whether the run has ended, so [`join`](Self::join) will not block
+    pub fn is_finished(&self) -> bool {
+        self.thread.is_finished()
+    }
+
+    /// Wait for the run to end and return its results
+    ///
+    /// # Errors
+    ///
+    /// Returns the `BenchmarkError` the run failed with, or `TestInterrupted` if
+    /// the benchmark thread panicked.
+    pub fn join(self) -> Result<BenchmarkResults, BenchmarkError> {
+        self.thread.join().unwrap_or_else(|_| {
+            Err(BenchmarkError::TestInterrupted(
+                "benchmark thread panicked".to_string(),
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::BenchmarkTest;
+    use std::time::{Duration, Instant};
+
+    fn memory_copy_config(test_duration_seconds: u64) -> BenchmarkConfig {
+        BenchmarkConfig {
+            target_path: std::env::temp_dir(),
+            sequential_block_size: 64 * 1024,
+            test_duration_seconds,
+            file_size_mb: 1,
+            tests: vec![BenchmarkTest::MemoryCopy],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_handle_streams_events_and_joins() {
+        let handle = BenchmarkHandle::start(memory_copy_config(1)).unwrap();
+
+        // The channel disconnects when the run ends
+        let events: Vec<ProgressEvent> = handle.events().iter().collect();
+        assert!(matches!(
+            events.first(),
+            Some(ProgressEvent::TestStart { test_name }) if test_name == "Memory Copy"
+        ));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, ProgressEvent::Update(u) if u.test_name == "Memory Copy")));
+        assert!(matches!(
+            events.last(),
+            Some(ProgressEvent::TestComplete { test_name, .. }) if test_name == "Memory Copy"
+        ));
+
+        let results = handle.join().unwrap();
+        assert!(results.memory_copy.is_some());
+        assert!(!results.interrupted);
+    }
+
+    #[test]
+    fn test_handle_cancel() {
+        let handle = BenchmarkHandle::start(memory_copy_config(30)).unwrap();
+        let start = Instant::now();
+
+        // Wait for the test to be under way before cancelling
+        let first = handle.events().recv_timeout(Duration::from_secs(10));
+        assert!(matches!(first, Ok(ProgressEvent::TestStart { .. })));
+        handle.cancel();
+        assert!(handle.cancellation().is_cancelled());
+
+        let results = handle.join().unwrap();
+        assert!(results.interrupted);
+        assert!(results.memory_copy.is_none());
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_handle_rejects_invalid_config() {
+        let mut config = memory_copy_config(1);
+        config.repetitions = 0;
+        assert!(matches!(
+            BenchmarkHandle::start(config),
+            Err(BenchmarkError::ConfigurationError(_))
+        ));
+    }
+}
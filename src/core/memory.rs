@@ -0,0 +1,313 @@
+//! Working-set sizes, kernels and results of the memory tests
+//!
+//! Copying within a buffer that fits a CPU cache runs at that cache's bandwidth, so
+//! the memory copy test repeats its copy at doubling working-set sizes. The
+//! bandwidth drops at each size that outgrows a cache level, showing L1, L2, L3 and
+//! DRAM bandwidth side by side.
+//!
+//! One thread cannot keep every channel of a server's memory controllers busy. The
+//! memory stream test runs the four kernels of the STREAM benchmark on many threads
+//! at once, each over its own slice of three arrays far larger than the caches.
+
+use crate::core::TestResult;
+use serde::{Deserialize, Serialize};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+/// Fewest bytes one timed copy moves
+///
+/// Working sets within the L1 cache copy in tens of nanoseconds, so their copy is
+/// repeated until it takes long enough that reading the clock does not distort it.
+pub(crate) const MIN_TIMED_COPY: usize = 256 * 1024;
+
+/// Copy bandwidth at one working-set size of the memory test
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryBandwidthPoint {
+    /// Size of the source and destination buffers together, in bytes
+    pub working_set: u64,
+    /// Copies within this working set; bytes count what was copied, not read and written
+    pub result: TestResult,
+}
+
+/// Kernel of the memory stream test, after the STREAM benchmark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamKernel {
+    /// `c = a`
+    Copy,
+    /// `b = s * c`
+    Scale,
+    /// `c = a + b`
+    Add,
+    /// `a = b + s * c`
+    Triad,
+}
+
+/// Factor the scale and triad kernels multiply by
+const STREAM_SCALAR: f64 = 3.0;
+
+impl StreamKernel {
+    /// Every kernel, in the order the test runs them
+    pub const ALL: [StreamKernel; 4] = [
+        StreamKernel::Copy,
+        StreamKernel::Scale,
+        StreamKernel::Add,
+        StreamKernel::Triad,
+    ];
+
+    /// Name the kernel's results and progress are reported under
+    pub fn test_name(self) -> &'static str {
+        match self {
+            StreamKernel::Copy => "Stream Copy",
+            StreamKernel::Scale => "Stream Scale",
+            StreamKernel::Add => "Stream Add",
+            StreamKernel::Triad => "Stream Triad",
+        }
+    }
+
+    /// Number of arrays one pass reads or writes
+    ///
+    /// As in STREAM, a pass moves this many array sizes of data: every array read
+    /// counts once and so does the array written.
+    pub fn arrays(self) -> u64 {
+        match self {
+            StreamKernel::Copy | StreamKernel::Scale => 2,
+            StreamKernel::Add | StreamKernel::Triad => 3,
+        }
+    }
+
+    /// Run one pass of the kernel over a thread's slices of the three arrays
+    pub(crate) fn run(self, a: &mut [f64], b: &mut [f64], c: &mut [f64]) {
+        match self {
+            StreamKernel::Copy => c.copy_from_slice(a),
+            StreamKernel::Scale => {
+                for (b, c) in b.iter_mut().zip(c.iter()) {
+                    *b = STREAM_SCALAR * c;
+                }
+            }
+            StreamKernel::Add => {
+                for ((c, a), b) in c.iter_mut().zip(a.iter()).zip(b.iter()) {
+                    *c = a + b;
+                }
+            }
+            StreamKernel::Triad => {
+                for ((a, b), c) in a.iter_mut().zip(b.iter()).zip(c.iter()) {
+                    *a = b + STREAM_SCALAR * c;
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for StreamKernel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamKernel::Copy => write!(f, "Copy"),
+            StreamKernel::Scale => write!(f, "Scale"),
+            StreamKernel::Add => write!(f, "Add"),
+            StreamKernel::Triad => write!(f, "Triad"),
+        }
+    }
+}
+
+/// Results of the memory stream test, one per kernel
+///
+/// An operation is one pass of all threads over the arrays, and its bytes are
+/// counted the STREAM way, as described at [`StreamKernel::arrays`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryStreamResult {
+    /// Number of threads the kernels ran on
+    pub threads: usize,
+    /// Size of each of the three arrays, across all threads, in bytes
+    pub array_size: u64,
+    pub copy: TestResult,
+    pub scale: TestResult,
+    pub add: TestResult,
+    pub triad: TestResult,
+}
+
+impl MemoryStreamResult {
+    /// Result of one kernel
+    pub fn result(&self, kernel: StreamKernel) -> &TestResult {
+        match kernel {
+            StreamKernel::Copy => &self.copy,
+            StreamKernel::Scale => &self.scale,
+            StreamKernel::Add => &self.add,
+            StreamKernel::Triad => &self.triad,
+        }
+    }
+
+    /// Result of one kernel, for the test to fill in
+    pub(crate) fn result_mut(&mut self, kernel: StreamKernel) -> &mut TestResult {
+        match kernel {
+            StreamKernel::Copy => &mut self.copy,
+            StreamKernel::Scale => &mut self.scale,
+            StreamKernel::Add => &mut self.add,
+            StreamKernel::Triad => &mut self.triad,
+        }
+    }
+
+    /// Results of every kernel, in the order the test ran them
+    pub fn results(&self) -> impl Iterator<Item = (StreamKernel, &TestResult)> {
+        StreamKernel::ALL
+            .into_iter()
+            .map(|kernel| (kernel, self.result(kernel)))
+    }
+}
+
+/// Working sets the memory test measures: `min` doubled until it reaches `max`
+///
+/// `max` is always the last size, even when it is not `min` times a power of two.
+pub(crate) fn working_set_sizes(min: usize, max: usize) -> Vec<usize> {
+    let mut sizes = Vec::new();
+    let mut size = min.max(1);
+    while size < max {
+        sizes.push(size);
+        size = size.saturating_mul(2);
+    }
+    sizes.push(max.max(1));
+    sizes
+}
+
+/// Barrier the memory stream threads meet at before and after each pass
+///
+/// Unlike [`std::sync::Barrier`] it can be broken, releasing every thread waiting
+/// at it. A thread holding a [`guard`](Self::guard) breaks it when it panics, so
+/// the others stop waiting for a thread that will never arrive.
+pub(crate) struct StreamBarrier {
+    parties: usize,
+    state: Mutex<BarrierState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct BarrierState {
+    /// Threads waiting for the current pass
+    arrived: usize,
+    /// Passes the barrier has released so far
+    generation: u64,
+    broken: bool,
+}
+
+impl StreamBarrier {
+    /// Barrier released once `parties` threads wait at it
+    pub(crate) fn new(parties: usize) -> Self {
+        Self {
+            parties,
+            state: Mutex::default(),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Wait for every party, returning `false` if the barrier is broken instead
+    pub(crate) fn wait(&self) -> bool {
+        let mut state = self.lock();
+        if state.broken {
+            return false;
+        }
+        state.arrived += 1;
+        if state.arrived == self.parties {
+            state.arrived = 0;
+            state.generation += 1;
+            self.changed.notify_all();
+            return true;
+        }
+
+        let generation = state.generation;
+        while state.generation == generation && !state.broken {
+            state = self
+                .changed
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        state.generation != generation
+    }
+
+    /// Release every waiting thread and fail all later waits
+    pub(crate) fn abort(&self) {
+        self.lock().broken = true;
+        self.changed.notify_all();
+    }
+
+    /// Break the barrier if the calling thread panics while the guard is alive
+    pub(crate) fn guard(&self) -> AbortOnPanic<'_> {
+        AbortOnPanic(self)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BarrierState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Breaks a [`StreamBarrier`] when dropped during a panic
+pub(crate) struct AbortOnPanic<'a>(&'a StreamBarrier);
+
+impl Drop for AbortOnPanic<'_> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.0.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_working_set_sizes_double_up_to_the_largest() {
+        assert_eq!(
+            working_set_sizes(4096, 32768),
+            vec![4096, 8192, 16384, 32768]
+        );
+        assert_eq!(
+            working_set_sizes(4096, 20000),
+            vec![4096, 8192, 16384, 20000]
+        );
+        assert_eq!(working_set_sizes(4096, 4096), vec![4096]);
+    }
+
+    #[test]
+    fn test_stream_kernels() {
+        let mut a = vec![1.0; 4];
+        let mut b = vec![2.0; 4];
+        let mut c = vec![0.5; 4];
+        let mut pass = |kernel: StreamKernel| kernel.run(&mut a, &mut b, &mut c);
+
+        pass(StreamKernel::Copy);
+        pass(StreamKernel::Scale);
+        pass(StreamKernel::Add);
+        pass(StreamKernel::Triad);
+        // c = 1, b = 3, c = 1 + 3, a = 3 + 3 * 4
+        assert_eq!((a[0], b[0], c[0]), (15.0, 3.0, 4.0));
+        assert!(a.iter().all(|&value| value == 15.0));
+    }
+
+    #[test]
+    fn test_stream_barrier_releases_every_party() {
+        let barrier = StreamBarrier::new(3);
+        std::thread::scope(|scope| {
+            let waiters: Vec<_> = (0..2)
+                .map(|_| scope.spawn(|| barrier.wait() && barrier.wait()))
+                .collect();
+            assert!(barrier.wait() && barrier.wait());
+            for waiter in waiters {
+                assert!(waiter.join().unwrap());
+            }
+        });
+    }
+
+    #[test]
+    fn test_stream_barrier_is_broken_by_a_panic() {
+        let barrier = StreamBarrier::new(3);
+        std::thread::scope(|scope| {
+            let waiter = scope.spawn(|| barrier.wait());
+            let panicked = scope.spawn(|| {
+                let _guard = barrier.guard();
+                panic!("worker failed");
+            });
+            assert!(panicked.join().is_err());
+            assert!(!waiter.join().unwrap());
+            assert!(!barrier.wait());
+        });
+    }
+}
@@ -0,0 +1,156 @@
+//! Filesystem metadata operations of the metadata test
+//!
+//! Builds, package managers and source checkouts are often limited by how fast the
+//! filesystem creates, looks up, renames and deletes small files rather than by its
+//! bandwidth. The metadata test times each of these operations separately on empty
+//! files spread across a number of directories.
+
+use crate::core::TestResult;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Operation timed by the metadata test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataOperation {
+    /// Creating an empty file
+    Create,
+    /// Reading a file's metadata
+    Stat,
+    /// Renaming a file within its directory
+    Rename,
+    /// Reading all entries of a directory
+    List,
+    /// Deleting a file
+    Delete,
+}
+
+impl MetadataOperation {
+    /// Every operation, in the order the test runs them
+    pub const ALL: [MetadataOperation; 5] = [
+        MetadataOperation::Create,
+        MetadataOperation::Stat,
+        MetadataOperation::Rename,
+        MetadataOperation::List,
+        MetadataOperation::Delete,
+    ];
+}
+
+impl std::fmt::Display for MetadataOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetadataOperation::Create => write!(f, "Create"),
+            MetadataOperation::Stat => write!(f, "Stat"),
+            MetadataOperation::Rename => write!(f, "Rename"),
+            MetadataOperation::List => write!(f, "List"),
+            MetadataOperation::Delete => write!(f, "Delete"),
+        }
+    }
+}
+
+/// Results of the metadata test, one per operation type
+///
+/// Each result counts its operations in `total_operations` and their rate and
+/// latency in `latency`; `latency.iops` is the operations per second. No data is
+/// transferred, so the speeds are zero.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataResult {
+    /// Number of files the test worked on
+    pub file_count: u64,
+    /// Number of directories the files were spread across
+    pub directory_count: u64,
+    pub create: TestResult,
+    pub stat: TestResult,
+    pub rename: TestResult,
+    /// Listing every directory once; an operation is one directory
+    pub list: TestResult,
+    pub delete: TestResult,
+}
+
+impl MetadataResult {
+    /// Result of one operation type
+    pub fn result(&self, operation: MetadataOperation) -> &TestResult {
+        match operation {
+            MetadataOperation::Create => &self.create,
+            MetadataOperation::Stat => &self.stat,
+            MetadataOperation::Rename => &self.rename,
+            MetadataOperation::List => &self.list,
+            MetadataOperation::Delete => &self.delete,
+        }
+    }
+
+    /// Results of every operation type, in the order the test ran them
+    pub fn results(&self) -> impl Iterator<Item = (MetadataOperation, &TestResult)> {
+        MetadataOperation::ALL
+            .into_iter()
+            .map(|operation| (operation, self.result(operation)))
+    }
+}
+
+/// Layout of a test's files, dealt out round-robin across a number of directories
+///
+/// The metadata and small-file tests both work on such a tree.
+pub(crate) struct FileTree {
+    root: PathBuf,
+    fanout: usize,
+}
+
+impl FileTree {
+    pub(crate) fn new(root: &Path, fanout: usize) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            fanout: fanout.max(1),
+        }
+    }
+
+    /// Number of directories under the root
+    pub(crate) fn directory_count(&self) -> usize {
+        self.fanout
+    }
+
+    /// Path of the directory with the given index
+    pub(crate) fn directory(&self, index: usize) -> PathBuf {
+        self.root.join(format!("dir_{}", index))
+    }
+
+    /// Path of a file before it is renamed
+    pub(crate) fn file(&self, index: usize) -> PathBuf {
+        self.directory(index % self.fanout)
+            .join(format!("file_{}", index))
+    }
+
+    /// Path of a file after it is renamed
+    pub(crate) fn renamed(&self, index: usize) -> PathBuf {
+        self.directory(index % self.fanout)
+            .join(format!("renamed_{}", index))
+    }
+
+    /// Create the root and its directories
+    pub(crate) fn create_directories(&self) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.root)?;
+        (0..self.fanout).try_for_each(|index| std::fs::create_dir(self.directory(index)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_spreads_files_across_directories() {
+        let tree = FileTree::new(Path::new("root"), 3);
+        assert_eq!(tree.file(0).parent(), Some(tree.directory(0).as_path()));
+        assert_eq!(tree.file(4).parent(), Some(tree.directory(1).as_path()));
+        assert_eq!(tree.renamed(5).parent(), Some(tree.directory(2).as_path()));
+        assert_ne!(tree.file(5), tree.renamed(5));
+    }
+
+    #[test]
+    fn test_results_follow_operation_order() {
+        let mut result = MetadataResult::default();
+        result.rename.total_operations = 7;
+        let operations: Vec<_> = result.results().map(|(operation, _)| operation).collect();
+        assert_eq!(operations, MetadataOperation::ALL);
+        assert_eq!(result.result(MetadataOperation::Rename).total_operations, 7);
+    }
+}
@@ -4,10 +4,25 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use thiserror::Error;
 
+pub mod aggregate;
+pub mod buffer;
+pub mod cancel;
+pub mod capacity;
 pub mod config;
+pub mod engine;
+pub mod handle;
+pub mod memory;
+pub mod metadata;
+pub mod pattern;
 pub mod progress;
+pub mod small_files;
 pub mod stats;
+pub mod sustained;
 pub mod tests;
+pub mod verify;
+
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+pub(crate) mod uring;
 
 #[cfg(test)]
 mod progress_integration_test;
@@ -21,22 +36,93 @@ mod error_test;
 #[cfg(test)]
 mod stats_comprehensive_test;
 
-pub use config::BenchmarkConfig;
-pub use progress::{NoOpProgressCallback, ProgressEvent, ProgressReporter, TestProgressCallback};
-pub use stats::{RealTimeStatsTracker, StatisticsCollector, TestResult};
+pub use aggregate::{AggregatedResults, MixedRunStatistics, RunStatistics};
+pub use buffer::AlignedBuffer;
+pub use cancel::CancellationToken;
+pub use capacity::{run_capacity_check, CapacityCheckConfig, CapacityCheckResults, CapacitySample};
+pub use config::{
+    BenchmarkConfig, BenchmarkTest, FileSizeShare, IoBackend, IoEngineKind, SyncMethod, TestLimit,
+    ThreadPinning,
+};
+pub use engine::{IoEngine, IoHandle, IoOpenOptions, MmapEngine, PreadEngine, PreadvEngine};
+pub use handle::BenchmarkHandle;
+pub use memory::{MemoryBandwidthPoint, MemoryStreamResult, StreamKernel};
+pub use metadata::{MetadataOperation, MetadataResult};
+pub use pattern::TestDataPattern;
+pub use progress::{
+    ChannelProgressCallback, NoOpProgressCallback, ProgressEvent, ProgressReporter, ProgressUpdate,
+    TestPhase, TestProgressCallback,
+};
+pub use small_files::SmallFilesResult;
+pub use stats::{
+    IntervalSample, LatencyRecorder, LatencyStats, MixedTestResult, RealTimeStatsTracker,
+    StatisticsCollector, StopReason, TestResult,
+};
+pub use sustained::{SustainedWriteResult, ThroughputPhase, ThroughputSample};
 pub use tests::{
-    run_memory_copy_test, run_random_read_test, run_random_write_test, run_sequential_read_test,
-    run_sequential_write_test,
+    run_memory_copy_test, run_memory_stream_test, run_metadata_test, run_mixed_random_test,
+    run_preparation, run_random_read_test, run_random_write_test, run_sequential_read_test,
+    run_sequential_write_test, run_small_files_test, run_sustained_write_test, run_sync_write_test,
 };
+pub use verify::{CorruptionEvent, CorruptionKind, TestDataVerifier};
 
 /// Results from a complete benchmark run
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Tests that were not selected in [`BenchmarkConfig::tests`] are `None`, as are
+/// tests that had not finished when the run was cancelled.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BenchmarkResults {
-    pub sequential_write: TestResult,
-    pub sequential_read: TestResult,
-    pub random_write: TestResult,
-    pub random_read: TestResult,
-    pub memory_copy: TestResult,
+    /// Writing the whole test file before the first test that reads it;
+    /// `None` if no selected test needed the file
+    pub preparation: Option<TestResult>,
+    pub sequential_write: Option<TestResult>,
+    pub sequential_read: Option<TestResult>,
+    pub random_write: Option<TestResult>,
+    pub random_read: Option<TestResult>,
+    /// Interleaved random reads and writes, reported per direction and combined
+    pub mixed_random: Option<MixedTestResult>,
+    /// Memory copy bandwidth at the largest working set of the memory test
+    pub memory_copy: Option<TestResult>,
+    /// Memory copy bandwidth at each working set of the memory test, smallest first
+    #[serde(default)]
+    pub memory_curve: Option<Vec<MemoryBandwidthPoint>>,
+    /// STREAM kernels run across many threads
+    #[serde(default)]
+    pub memory_stream: Option<MemoryStreamResult>,
+    /// Small writes each made durable before the next; an operation is one commit
+    #[serde(default)]
+    pub sync_write: Option<TestResult>,
+    /// Creating, stating, renaming, listing and deleting many empty files
+    #[serde(default)]
+    pub metadata: Option<MetadataResult>,
+    /// Writing and reading back a tree of small files
+    #[serde(default)]
+    pub small_files: Option<SmallFilesResult>,
+    /// Long sequential write with its throughput over time
+    #[serde(default)]
+    pub sustained_write: Option<SustainedWriteResult>,
+    /// I/O backend the disk tests ran on
+    pub io_backend: IoBackend,
+    /// Whether the run was cancelled before all selected tests finished
+    #[serde(default)]
+    pub interrupted: bool,
+}
+
+impl BenchmarkResults {
+    /// Sectors that failed verification across all tests; zero unless `verify` is enabled
+    pub fn corrupted_sectors(&self) -> u64 {
+        [
+            &self.sequential_read,
+            &self.random_read,
+            &self.preparation,
+            &self.sequential_write,
+            &self.random_write,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|result| result.corrupted_sectors)
+        .sum()
+    }
 }
 
 /// Trait for receiving progress updates during benchmark execution
@@ -89,6 +175,78 @@ pub trait ProgressCallback: Send + Sync {
     /// * `current_speed_mbps` - Current instantaneous speed in MB/s
     fn on_progress(&self, test_name: &str, current_speed_mbps: f64);
 
+    /// Called periodically during test execution with a snapshot of the test
+    ///
+    /// The snapshot carries the completion fraction, the bytes and operations so
+    /// far, the current and average throughput and an estimate of the remaining
+    /// time. The default implementation forwards the current speed to
+    /// `on_progress`, so callbacks only interested in the speed need not
+    /// implement it.
+    ///
+    /// # Arguments
+    ///
+    /// * `update` - Progress of the currently running test
+    fn on_progress_update(&self, update: &ProgressUpdate) {
+        self.on_progress(&update.test_name, update.current_speed_mbps);
+    }
+
+    /// Called when a test moves on to another phase
+    ///
+    /// Tests report [`TestPhase::Measure`] when their measured I/O starts, after
+    /// [`TestPhase::Warmup`] if they warm up first, and [`TestPhase::Flush`] while
+    /// written data is synced. The preparation reports [`TestPhase::Prepare`] under
+    /// the name "Preparation", and deleting the test file reports
+    /// [`TestPhase::Cleanup`] under the name "Cleanup". The default implementation
+    /// does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `test_name` - Name of the test changing phase
+    /// * `phase` - Phase the test is entering
+    fn on_phase_change(&self, test_name: &str, phase: TestPhase) {
+        let _ = (test_name, phase);
+    }
+
+    /// Called for a problem that does not stop the benchmark
+    ///
+    /// Examples are a failed test whose successors still run, or a test file that
+    /// could not be deleted. The default implementation prints the message to
+    /// standard error.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Description of the problem
+    fn on_warning(&self, message: &str) {
+        eprintln!("Warning: {}", message);
+    }
+
+    /// Called after `on_test_start` when the test begins with a warm-up
+    ///
+    /// I/O issued during the warm-up is not measured, so no progress updates
+    /// arrive until it ends; `on_phase_change` with [`TestPhase::Measure`] marks
+    /// the measured phase. The default implementation does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `test_name` - Name of the test that is warming up
+    /// * `warmup` - Length of the warm-up
+    fn on_warmup(&self, test_name: &str, warmup: std::time::Duration) {
+        let _ = (test_name, warmup);
+    }
+
+    /// Called before each run of a repeated benchmark
+    ///
+    /// Only [`run_benchmark_repeated`] reports runs; the tests of each run then
+    /// report their progress as usual. The default implementation does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `run` - Number of the run that is starting, from 1
+    /// * `total_runs` - Number of runs in the benchmark
+    fn on_run_start(&self, run: usize, total_runs: usize) {
+        let _ = (run, total_runs);
+    }
+
     /// Called when a test completes
     ///
     /// # Arguments
@@ -118,16 +276,32 @@ pub enum BenchmarkError {
 
     #[error("Test interrupted: {0}")]
     TestInterrupted(String),
+
+    #[error("Data corruption detected: {0}")]
+    DataCorruption(String),
 }
 
-/// Main function to run all benchmark tests
+/// Main function to run the selected benchmark tests
 ///
-/// This function executes all five benchmark tests in sequence:
+/// This function executes the tests listed in `config.tests` once, in that order,
+/// whatever `config.repetitions` is; see [`run_benchmark_repeated`]. By
+/// default these are the seven tests of [`BenchmarkTest::DEFAULT`]:
 /// 1. Sequential Write
 /// 2. Sequential Read  
 /// 3. Random Write
 /// 4. Random Read
-/// 5. Memory Copy
+/// 5. Mixed Random
+/// 6. Sync Write
+/// 7. Memory Copy
+///
+/// Before the first test that reads the test file, a preparation phase writes the
+/// whole file with real data so no test reads sparse holes. Sequential Write
+/// recreates the file, so it is prepared again if a reading test follows it.
+///
+/// Cancelling `config.cancellation` stops the running test, deletes the test files
+/// and directories and returns the tests that finished, with [`BenchmarkResults::interrupted`] set.
+/// Pausing it holds the running test at its next request, leaving the paused time
+/// out of the test's results.
 ///
 /// # Arguments
 ///
@@ -136,7 +310,7 @@ pub enum BenchmarkError {
 ///
 /// # Returns
 ///
-/// Returns `BenchmarkResults` containing performance statistics for all tests
+/// Returns `BenchmarkResults` containing performance statistics for the selected tests
 ///
 /// # Errors
 ///
@@ -145,13 +319,92 @@ pub fn run_benchmark(
     config: BenchmarkConfig,
     progress_callback: Option<Box<dyn ProgressCallback>>,
 ) -> Result<BenchmarkResults, BenchmarkError> {
-    use crate::platform;
-    use std::fs;
-    use std::time::SystemTime;
+    let engine = config.io_engine.engine();
+    run_benchmark_with_engine(config, engine, progress_callback)
+}
 
+/// Run all benchmark tests against a caller-provided I/O engine
+///
+/// Behaves like [`run_benchmark`], but the disk tests open the test file through
+/// `engine` instead of the built-in engine selected by `config.io_engine`.
+///
+/// # Errors
+///
+/// Returns `BenchmarkError` if any test fails or configuration is invalid
+pub fn run_benchmark_with_engine(
+    config: BenchmarkConfig,
+    engine: Box<dyn IoEngine>,
+    progress_callback: Option<Box<dyn ProgressCallback>>,
+) -> Result<BenchmarkResults, BenchmarkError> {
     // Validate configuration
     config.validate()?;
 
+    let callback_ref = progress_callback.as_ref().map(|cb| cb.as_ref());
+    run_benchmark_once(&config, engine.as_ref(), callback_ref)
+}
+
+/// Run the selected benchmark tests `config.repetitions` times
+///
+/// Each run executes the tests like [`run_benchmark`], with its own test file.
+/// The runs' results are kept and summarized per test with the median, mean,
+/// standard deviation and 95% confidence interval of the average speed.
+///
+/// A cancelled run ends the repetitions; its finished tests are aggregated with the
+/// earlier runs and [`AggregatedResults::interrupted`] is set.
+///
+/// # Errors
+///
+/// Returns `BenchmarkError` if any run fails or configuration is invalid
+pub fn run_benchmark_repeated(
+    config: BenchmarkConfig,
+    progress_callback: Option<Box<dyn ProgressCallback>>,
+) -> Result<AggregatedResults, BenchmarkError> {
+    let engine = config.io_engine.engine();
+    run_benchmark_repeated_with_engine(config, engine, progress_callback)
+}
+
+/// Run the selected benchmark tests repeatedly against a caller-provided I/O engine
+///
+/// Behaves like [`run_benchmark_repeated`], but the disk tests open the test file
+/// through `engine` instead of the built-in engine selected by `config.io_engine`.
+///
+/// # Errors
+///
+/// Returns `BenchmarkError` if any run fails or configuration is invalid
+pub fn run_benchmark_repeated_with_engine(
+    config: BenchmarkConfig,
+    engine: Box<dyn IoEngine>,
+    progress_callback: Option<Box<dyn ProgressCallback>>,
+) -> Result<AggregatedResults, BenchmarkError> {
+    config.validate()?;
+
+    let callback_ref = progress_callback.as_ref().map(|cb| cb.as_ref());
+    let mut runs = Vec::with_capacity(config.repetitions);
+    for run in 1..=config.repetitions {
+        if let Some(callback) = callback_ref {
+            callback.on_run_start(run, config.repetitions);
+        }
+        let results = run_benchmark_once(&config, engine.as_ref(), callback_ref)?;
+        let interrupted = results.interrupted;
+        runs.push(results);
+        if interrupted {
+            break;
+        }
+    }
+
+    Ok(AggregatedResults::from_runs(runs))
+}
+
+/// Run the selected tests once against an already validated configuration
+fn run_benchmark_once(
+    config: &BenchmarkConfig,
+    engine: &dyn IoEngine,
+    callback_ref: Option<&dyn ProgressCallback>,
+) -> Result<BenchmarkResults, BenchmarkError> {
+    use crate::platform;
+    use std::fs;
+    use std::time::SystemTime;
+
     // Check available space before starting tests
     let _required_space = config.file_size_bytes();
     if let Ok(_metadata) = fs::metadata(&config.target_path) {
@@ -166,112 +419,303 @@ pub fn run_benchmark(
         .as_nanos();
     let test_file_name = format!("disk_speed_test_{}.tmp", timestamp);
     let test_file_path = config.target_path.join(&test_file_name);
-
-    // Convert boxed callback to reference for easier handling
-    let callback_ref = progress_callback.as_ref().map(|cb| cb.as_ref());
-
-    // Initialize results with default values
+    let sync_file_path = config
+        .target_path
+        .join(format!("disk_speed_test_{}_sync.tmp", timestamp));
+    let metadata_dir_path = config
+        .target_path
+        .join(format!("disk_speed_test_{}_metadata", timestamp));
+    let small_files_dir_path = config
+        .target_path
+        .join(format!("disk_speed_test_{}_small_files", timestamp));
+
+    // Tests that are not selected stay `None`
     let mut results = BenchmarkResults {
-        sequential_write: TestResult::default(),
-        sequential_read: TestResult::default(),
-        random_write: TestResult::default(),
-        random_read: TestResult::default(),
-        memory_copy: TestResult::default(),
+        io_backend: config.io_backend,
+        ..Default::default()
     };
 
+    // Generations written to the test file, when verifying its contents
+    let verifier = config.verify.then(|| {
+        TestDataVerifier::new(config.data_pattern).with_file_size(config.file_size_bytes())
+    });
+    let verifier = verifier.as_ref();
+
     // Track which tests have been completed for cleanup purposes
     let mut test_file_created = false;
-
-    // Execute tests in sequence with proper error handling and cleanup
-    let benchmark_result = (|| -> Result<BenchmarkResults, BenchmarkError> {
-        // Test 1: Sequential Write
-        // This test creates the test file, so we track its creation
-        match tests::run_sequential_write_test(&config, &test_file_path, callback_ref) {
-            Ok(result) => {
-                results.sequential_write = result;
+    let mut test_file_prepared = false;
+    let mut sync_file_created = false;
+    let mut metadata_dir_created = false;
+    let mut small_files_dir_created = false;
+
+    // Execute tests in the selected order with proper error handling and cleanup
+    let benchmark_result = (|| -> Result<(), BenchmarkError> {
+        for &test in &config.tests {
+            config.cancellation.check(&test.to_string())?;
+
+            // Populate the whole file before any test that depends on its contents
+            if test.needs_existing_file() && !test_file_prepared {
                 test_file_created = true;
+                let result = tests::run_preparation(
+                    config,
+                    engine,
+                    &test_file_path,
+                    callback_ref,
+                    verifier,
+                )?;
+                results.preparation = Some(result);
+                test_file_prepared = true;
             }
-            Err(e) => {
-                // If sequential write fails, we can't continue with read tests
-                return Err(e);
-            }
-        }
 
-        // Test 2: Sequential Read
-        // Requires the test file created by sequential write
-        match tests::run_sequential_read_test(&config, &test_file_path, callback_ref) {
-            Ok(result) => {
-                results.sequential_read = result;
-            }
-            Err(e) => {
-                // Log error but continue with other tests that don't require the file
-                eprintln!("Warning: Sequential read test failed: {}", e);
-                // Set default result to indicate test failure
-                results.sequential_read = TestResult::default();
+            match test {
+                BenchmarkTest::SequentialWrite => {
+                    // This test creates the test file, so we track its creation
+                    // If it fails, we can't continue with read tests
+                    test_file_created = true;
+                    let result = tests::run_sequential_write_test(
+                        config,
+                        engine,
+                        &test_file_path,
+                        callback_ref,
+                        verifier,
+                    )?;
+                    results.sequential_write = Some(result);
+                    // The file was recreated and may be only partially written
+                    test_file_prepared = false;
+                }
+                BenchmarkTest::SequentialRead => {
+                    let result = tests::run_sequential_read_test(
+                        config,
+                        engine,
+                        &test_file_path,
+                        callback_ref,
+                        verifier,
+                    );
+                    // Log error but continue with the remaining tests, using a
+                    // default result to indicate test failure
+                    results.sequential_read = Some(continue_after_failure(
+                        config,
+                        test,
+                        result,
+                        callback_ref,
+                        TestResult::default,
+                    )?);
+                }
+                BenchmarkTest::RandomWrite => {
+                    let result = tests::run_random_write_test(
+                        config,
+                        engine,
+                        &test_file_path,
+                        callback_ref,
+                        verifier,
+                    );
+                    results.random_write = Some(continue_after_failure(
+                        config,
+                        test,
+                        result,
+                        callback_ref,
+                        TestResult::default,
+                    )?);
+                }
+                BenchmarkTest::RandomRead => {
+                    let result = tests::run_random_read_test(
+                        config,
+                        engine,
+                        &test_file_path,
+                        callback_ref,
+                        verifier,
+                    );
+                    results.random_read = Some(continue_after_failure(
+                        config,
+                        test,
+                        result,
+                        callback_ref,
+                        TestResult::default,
+                    )?);
+                }
+                BenchmarkTest::MixedRandom => {
+                    let result = tests::run_mixed_random_test(
+                        config,
+                        engine,
+                        &test_file_path,
+                        callback_ref,
+                        verifier,
+                    );
+                    results.mixed_random = Some(continue_after_failure(
+                        config,
+                        test,
+                        result,
+                        callback_ref,
+                        || MixedTestResult {
+                            read_percentage: config.mixed_read_percentage,
+                            ..Default::default()
+                        },
+                    )?);
+                }
+                BenchmarkTest::SyncWrite => {
+                    sync_file_created = true;
+                    let result = tests::run_sync_write_test(config, &sync_file_path, callback_ref);
+                    results.sync_write = Some(continue_after_failure(
+                        config,
+                        test,
+                        result,
+                        callback_ref,
+                        TestResult::default,
+                    )?);
+                }
+                BenchmarkTest::Metadata => {
+                    metadata_dir_created = true;
+                    let result = tests::run_metadata_test(config, &metadata_dir_path, callback_ref);
+                    results.metadata = Some(continue_after_failure(
+                        config,
+                        test,
+                        result,
+                        callback_ref,
+                        MetadataResult::default,
+                    )?);
+                }
+                BenchmarkTest::SmallFiles => {
+                    small_files_dir_created = true;
+                    let result = tests::run_small_files_test(
+                        config,
+                        engine,
+                        &small_files_dir_path,
+                        callback_ref,
+                    );
+                    results.small_files = Some(continue_after_failure(
+                        config,
+                        test,
+                        result,
+                        callback_ref,
+                        SmallFilesResult::default,
+                    )?);
+                }
+                BenchmarkTest::SustainedWrite => {
+                    test_file_created = true;
+                    let result = tests::run_sustained_write_test(
+                        config,
+                        engine,
+                        &test_file_path,
+                        callback_ref,
+                        verifier,
+                    );
+                    results.sustained_write = Some(continue_after_failure(
+                        config,
+                        test,
+                        result,
+                        callback_ref,
+                        SustainedWriteResult::default,
+                    )?);
+                    // The file was recreated with the sustained write's data
+                    test_file_prepared = false;
+                }
+                BenchmarkTest::MemoryCopy => {
+                    // Independent of disk file, so should always work
+                    let result = tests::run_memory_copy_test(config, callback_ref);
+                    let curve =
+                        continue_after_failure(config, test, result, callback_ref, Vec::new)?;
+                    results.memory_copy = Some(
+                        curve
+                            .last()
+                            .map(|point| point.result.clone())
+                            .unwrap_or_default(),
+                    );
+                    results.memory_curve = Some(curve);
+                }
+                BenchmarkTest::MemoryStream => {
+                    let result = tests::run_memory_stream_test(config, callback_ref);
+                    results.memory_stream = Some(continue_after_failure(
+                        config,
+                        test,
+                        result,
+                        callback_ref,
+                        MemoryStreamResult::default,
+                    )?);
+                }
             }
         }
 
-        // Test 3: Random Write
-        // Uses the existing test file
-        match tests::run_random_write_test(&config, &test_file_path, callback_ref) {
-            Ok(result) => {
-                results.random_write = result;
-            }
-            Err(e) => {
-                // Log error but continue with other tests
-                eprintln!("Warning: Random write test failed: {}", e);
-                results.random_write = TestResult::default();
-            }
-        }
+        Ok(())
+    })();
 
-        // Test 4: Random Read
-        // Uses the existing test file
-        match tests::run_random_read_test(&config, &test_file_path, callback_ref) {
-            Ok(result) => {
-                results.random_read = result;
-            }
-            Err(e) => {
-                // Log error but continue with memory test
-                eprintln!("Warning: Random read test failed: {}", e);
-                results.random_read = TestResult::default();
-            }
+    // Cleanup: Always attempt to remove the test files and directories that were created
+    if test_file_created || sync_file_created || metadata_dir_created || small_files_dir_created {
+        if let Some(callback) = callback_ref {
+            callback.on_phase_change("Cleanup", TestPhase::Cleanup);
         }
-
-        // Test 5: Memory Copy
-        // Independent of disk file, so should always work
-        match tests::run_memory_copy_test(&config, callback_ref) {
-            Ok(result) => {
-                results.memory_copy = result;
-            }
-            Err(e) => {
-                // Log error but don't fail the entire benchmark
-                eprintln!("Warning: Memory copy test failed: {}", e);
-                results.memory_copy = TestResult::default();
+        let created_files = [
+            test_file_created.then_some(&test_file_path),
+            sync_file_created.then_some(&sync_file_path),
+            metadata_dir_created.then_some(&metadata_dir_path),
+            small_files_dir_created.then_some(&small_files_dir_path),
+        ];
+        for path in created_files.into_iter().flatten() {
+            let removed = if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            };
+            if let Err(cleanup_error) = removed {
+                // Log cleanup error but don't fail the benchmark
+                report_warning(
+                    callback_ref,
+                    &format!(
+                        "Failed to cleanup test file {}: {}",
+                        path.display(),
+                        cleanup_error
+                    ),
+                );
             }
         }
-
-        Ok(results)
-    })();
-
-    // Cleanup: Always attempt to remove the test file if it was created
-    if test_file_created {
-        if let Err(cleanup_error) = fs::remove_file(&test_file_path) {
-            // Log cleanup error but don't fail the benchmark
-            eprintln!(
-                "Warning: Failed to cleanup test file {}: {}",
-                test_file_path.display(),
-                cleanup_error
-            );
-        }
     }
 
     // Additional cleanup: Sync filesystem if cache bypassing was enabled
     if config.disable_os_cache {
         if let Err(sync_error) = platform::sync_file_system(&config.target_path) {
             // Log sync error but don't fail the benchmark
-            eprintln!("Warning: Failed to sync filesystem: {}", sync_error);
+            report_warning(
+                callback_ref,
+                &format!("Failed to sync filesystem: {}", sync_error),
+            );
+        }
+    }
+
+    match benchmark_result {
+        Ok(()) => Ok(results),
+        // Keep the tests that finished before the cancellation
+        Err(BenchmarkError::TestInterrupted(_)) if config.cancellation.is_cancelled() => {
+            results.interrupted = true;
+            Ok(results)
         }
+        Err(e) => Err(e),
     }
+}
 
-    benchmark_result
+/// Substitute `fallback` for a failed test's result so the remaining tests still run
+///
+/// A cancellation is passed on instead, ending the run.
+fn continue_after_failure<T>(
+    config: &BenchmarkConfig,
+    test: BenchmarkTest,
+    result: Result<T, BenchmarkError>,
+    callback_ref: Option<&dyn ProgressCallback>,
+    fallback: impl FnOnce() -> T,
+) -> Result<T, BenchmarkError> {
+    match result {
+        Ok(result) => Ok(result),
+        Err(e @ BenchmarkError::TestInterrupted(_)) if config.cancellation.is_cancelled() => Err(e),
+        Err(e) => {
+            report_warning(callback_ref, &format!("{} test failed: {}", test, e));
+            Ok(fallback())
+        }
+    }
+}
+
+/// Hand a problem that does not stop the benchmark to the progress callback,
+/// printing it to standard error when there is none
+pub(crate) fn report_warning(callback: Option<&dyn ProgressCallback>, message: &str) {
+    match callback {
+        Some(callback) => callback.on_warning(message),
+        None => eprintln!("Warning: {}", message),
+    }
 }
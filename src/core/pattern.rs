@@ -0,0 +1,220 @@
+//! Data patterns written by the write tests
+//!
+//! Storage that compresses or deduplicates data (btrfs/zfs with compression,
+//! SSD controllers with inline compression) reports very different numbers
+//! depending on what is written. [`TestDataPattern`] selects the content of the
+//! write buffers, from trivially compressible zeros to random data that is
+//! unique per sector.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Granularity at which [`TestDataPattern::Unique`] stamps offsets and sequence numbers
+pub const STAMP_SECTOR_SIZE: usize = 512;
+
+/// Granularity at which [`TestDataPattern::Compressible`] mixes random data and zeros
+pub const COMPRESSIBLE_CHUNK_SIZE: usize = 4096;
+
+/// Bytes of each stamp: the little-endian file offset followed by the sequence number
+const STAMP_LEN: usize = 16;
+
+/// Patterns for test data generation
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum TestDataPattern {
+    /// All zeros
+    Zeros,
+    /// All ones (0xFF)
+    Ones,
+    /// Random data with fixed seed for reproducibility
+    RandomSeeded(u64),
+    /// Truly random data, incompressible but identical for every write of a buffer
+    Random,
+    /// Alternating pattern (0xAA, 0x55, ...)
+    Alternating,
+    /// Sequential bytes (0x00, 0x01, 0x02, ...)
+    #[default]
+    Sequential,
+    /// Random data padded with zeros so that compressors reach roughly the given ratio,
+    /// e.g. `2.0` for 2:1
+    Compressible(f64),
+    /// Random data with every 512-byte sector stamped with its file offset and a
+    /// per-writer sequence number, so no two written sectors are identical
+    Unique,
+}
+
+impl TestDataPattern {
+    /// Fill a write buffer with the pattern's base content
+    ///
+    /// Write tests fill each buffer once and reuse it; patterns for which
+    /// [`stamps_blocks`](Self::stamps_blocks) is true must additionally be
+    /// [`stamp`](Self::stamp)ed before every write.
+    pub fn fill(&self, buffer: &mut [u8]) {
+        match self {
+            TestDataPattern::Zeros => buffer.fill(0x00),
+            TestDataPattern::Ones => buffer.fill(0xFF),
+            TestDataPattern::RandomSeeded(seed) => {
+                StdRng::seed_from_u64(*seed).fill_bytes(buffer);
+            }
+            TestDataPattern::Random | TestDataPattern::Unique => {
+                rand::thread_rng().fill_bytes(buffer);
+            }
+            TestDataPattern::Alternating => {
+                for (i, byte) in buffer.iter_mut().enumerate() {
+                    *byte = if i % 2 == 0 { 0xAA } else { 0x55 };
+                }
+            }
+            TestDataPattern::Sequential => {
+                for (i, byte) in buffer.iter_mut().enumerate() {
+                    *byte = (i % 256) as u8;
+                }
+            }
+            TestDataPattern::Compressible(ratio) => {
+                let mut rng = rand::thread_rng();
+                for chunk in buffer.chunks_mut(COMPRESSIBLE_CHUNK_SIZE) {
+                    let random_len =
+                        ((chunk.len() as f64 / ratio.max(1.0)).ceil() as usize).min(chunk.len());
+                    rng.fill_bytes(&mut chunk[..random_len]);
+                    chunk[random_len..].fill(0);
+                }
+            }
+        }
+    }
+
+    /// Whether every write needs a fresh [`stamp`](Self::stamp)
+    pub fn stamps_blocks(&self) -> bool {
+        matches!(self, TestDataPattern::Unique)
+    }
+
+    /// Stamp a buffer about to be written at `offset` with the writer's `sequence` number
+    ///
+    /// Each sector starts with its own file offset and `sequence`, both little-endian.
+    /// Does nothing for patterns that do not [`stamp blocks`](Self::stamps_blocks).
+    pub fn stamp(&self, buffer: &mut [u8], offset: u64, sequence: u64) {
+        if !self.stamps_blocks() {
+            return;
+        }
+
+        for (index, sector) in buffer.chunks_mut(STAMP_SECTOR_SIZE).enumerate() {
+            if sector.len() < STAMP_LEN {
+                break;
+            }
+            let sector_offset = offset + (index * STAMP_SECTOR_SIZE) as u64;
+            sector[..8].copy_from_slice(&sector_offset.to_le_bytes());
+            sector[8..STAMP_LEN].copy_from_slice(&sequence.to_le_bytes());
+        }
+    }
+
+    /// Check the pattern's parameters
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            TestDataPattern::Compressible(ratio) if !ratio.is_finite() || *ratio < 1.0 => Err(
+                format!("Compression ratio must be at least 1.0, got {}", ratio),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for TestDataPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TestDataPattern::Zeros => write!(f, "zeros"),
+            TestDataPattern::Ones => write!(f, "ones"),
+            TestDataPattern::RandomSeeded(seed) => write!(f, "random (seed {})", seed),
+            TestDataPattern::Random => write!(f, "random"),
+            TestDataPattern::Alternating => write!(f, "alternating"),
+            TestDataPattern::Sequential => write!(f, "sequential"),
+            TestDataPattern::Compressible(ratio) => write!(f, "compressible {:.1}:1", ratio),
+            TestDataPattern::Unique => write!(f, "unique"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_patterns() {
+        let mut buffer = vec![0x11u8; 1024];
+
+        TestDataPattern::Zeros.fill(&mut buffer);
+        assert!(buffer.iter().all(|&b| b == 0));
+
+        TestDataPattern::Ones.fill(&mut buffer);
+        assert!(buffer.iter().all(|&b| b == 0xFF));
+
+        TestDataPattern::Sequential.fill(&mut buffer);
+        assert_eq!(&buffer[254..258], &[254, 255, 0, 1]);
+
+        TestDataPattern::Alternating.fill(&mut buffer);
+        assert_eq!(&buffer[..4], &[0xAA, 0x55, 0xAA, 0x55]);
+    }
+
+    #[test]
+    fn test_seeded_pattern_is_reproducible() {
+        let mut first = vec![0u8; 4096];
+        let mut second = vec![0u8; 4096];
+        TestDataPattern::RandomSeeded(7).fill(&mut first);
+        TestDataPattern::RandomSeeded(7).fill(&mut second);
+        assert_eq!(first, second);
+
+        TestDataPattern::RandomSeeded(8).fill(&mut second);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_compressible_pattern_ratio() {
+        let mut buffer = vec![0xFFu8; 4 * COMPRESSIBLE_CHUNK_SIZE];
+        TestDataPattern::Compressible(4.0).fill(&mut buffer);
+
+        // The tail of every chunk is zeroed, leaving a quarter for random data
+        for chunk in buffer.chunks(COMPRESSIBLE_CHUNK_SIZE) {
+            assert!(chunk[COMPRESSIBLE_CHUNK_SIZE / 4..].iter().all(|&b| b == 0));
+            assert!(chunk[..COMPRESSIBLE_CHUNK_SIZE / 4].iter().any(|&b| b != 0));
+        }
+
+        // A ratio of 1.0 is fully random
+        TestDataPattern::Compressible(1.0).fill(&mut buffer);
+        let zeros = buffer.iter().filter(|&&b| b == 0).count();
+        assert!(zeros < buffer.len() / 64);
+    }
+
+    #[test]
+    fn test_unique_pattern_stamps_every_sector() {
+        let pattern = TestDataPattern::Unique;
+        assert!(pattern.stamps_blocks());
+
+        let mut buffer = vec![0u8; 4 * STAMP_SECTOR_SIZE];
+        pattern.fill(&mut buffer);
+        pattern.stamp(&mut buffer, 8192, 3);
+
+        for (index, sector) in buffer.chunks(STAMP_SECTOR_SIZE).enumerate() {
+            let offset = u64::from_le_bytes(sector[..8].try_into().unwrap());
+            let sequence = u64::from_le_bytes(sector[8..16].try_into().unwrap());
+            assert_eq!(offset, 8192 + (index * STAMP_SECTOR_SIZE) as u64);
+            assert_eq!(sequence, 3);
+        }
+
+        // Rewriting the same offset later produces different content
+        let before = buffer.clone();
+        pattern.stamp(&mut buffer, 8192, 4);
+        assert_ne!(before, buffer);
+    }
+
+    #[test]
+    fn test_stamp_ignored_for_plain_patterns() {
+        let mut buffer = vec![0u8; STAMP_SECTOR_SIZE];
+        TestDataPattern::Zeros.stamp(&mut buffer, 4096, 1);
+        assert!(buffer.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_pattern_validation() {
+        assert!(TestDataPattern::Compressible(2.0).validate().is_ok());
+        assert!(TestDataPattern::Compressible(0.5).validate().is_err());
+        assert!(TestDataPattern::Compressible(f64::NAN).validate().is_err());
+        assert!(TestDataPattern::Unique.validate().is_ok());
+    }
+}
@@ -4,9 +4,68 @@
 //! including thread-safe progress reporting and callback management.
 
 use crate::core::{ProgressCallback, TestResult};
+use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Stage a running test is in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestPhase {
+    /// Writing the test file before the tests that read it, or the sync write
+    /// test's log before it is overwritten
+    Prepare,
+    /// Issuing I/O that is not measured
+    Warmup,
+    /// Issuing the measured I/O
+    Measure,
+    /// Flushing written data to the device
+    Flush,
+    /// Deleting the test file once the run is over
+    Cleanup,
+}
+
+impl std::fmt::Display for TestPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TestPhase::Prepare => write!(f, "preparing"),
+            TestPhase::Warmup => write!(f, "warming up"),
+            TestPhase::Measure => write!(f, "measuring"),
+            TestPhase::Flush => write!(f, "flushing"),
+            TestPhase::Cleanup => write!(f, "cleaning up"),
+        }
+    }
+}
+
+/// Snapshot of a running test, passed to [`ProgressCallback::on_progress_update`]
+///
+/// Byte, operation and throughput figures cover the measured phase only, like
+/// the final [`TestResult`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressUpdate {
+    /// Name of the running test
+    pub test_name: String,
+    /// Stage the test is in
+    pub phase: TestPhase,
+    /// Time since the test started, including its warm-up
+    pub elapsed: Duration,
+    /// Length of the test including its warm-up, when a time limit or cap bounds it
+    pub total: Option<Duration>,
+    /// Share of the test's limit reached so far, from 0 to 1
+    pub fraction: f64,
+    /// Estimated time until the test stops, if it can be told yet
+    pub eta: Option<Duration>,
+    /// Bytes transferred so far
+    pub bytes: u64,
+    /// I/O operations completed so far
+    pub operations: u64,
+    /// Throughput of the latest sample in MB/s
+    pub current_speed_mbps: f64,
+    /// Throughput since the measurement started in MB/s
+    pub average_speed_mbps: f64,
+    /// Operations per second since the measurement started
+    pub iops: f64,
+}
+
 /// Thread-safe wrapper for progress callbacks
 ///
 /// This struct provides a thread-safe way to call progress callbacks from multiple threads
@@ -69,6 +128,30 @@ impl ProgressReporter {
         }
     }
 
+    /// Report that a run of a repeated benchmark is starting
+    ///
+    /// # Arguments
+    ///
+    /// * `run` - Number of the run that is starting, from 1
+    /// * `total_runs` - Number of runs in the benchmark
+    pub fn on_run_start(&self, run: usize, total_runs: usize) {
+        if let Some(ref callback) = self.callback {
+            callback.on_run_start(run, total_runs);
+        }
+    }
+
+    /// Report that a test is warming up before its measurement starts
+    ///
+    /// # Arguments
+    ///
+    /// * `test_name` - Name of the test that is warming up
+    /// * `warmup` - Length of the warm-up
+    pub fn on_warmup(&self, test_name: &str, warmup: Duration) {
+        if let Some(ref callback) = self.callback {
+            callback.on_warmup(test_name, warmup);
+        }
+    }
+
     /// Report progress update, respecting the progress interval
     ///
     /// This method will only call the underlying callback if enough time has passed
@@ -99,6 +182,55 @@ impl ProgressReporter {
         }
     }
 
+    /// Report a progress snapshot, respecting the progress interval
+    ///
+    /// # Arguments
+    ///
+    /// * `update` - Progress of the currently running test
+    ///
+    /// # Returns
+    ///
+    /// `true` if the progress callback was called, `false` if it was throttled
+    pub fn on_progress_update(&self, update: &ProgressUpdate) -> bool {
+        if let Some(ref callback) = self.callback {
+            let mut last_time = self.last_progress_time.lock().unwrap();
+            let now = Instant::now();
+
+            if now.duration_since(*last_time) >= self.progress_interval {
+                callback.on_progress_update(update);
+                *last_time = now;
+                true
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    /// Report that a test moved on to another phase
+    ///
+    /// # Arguments
+    ///
+    /// * `test_name` - Name of the test changing phase
+    /// * `phase` - Phase the test is entering
+    pub fn on_phase_change(&self, test_name: &str, phase: TestPhase) {
+        if let Some(ref callback) = self.callback {
+            callback.on_phase_change(test_name, phase);
+        }
+    }
+
+    /// Report a problem that does not stop the benchmark
+    ///
+    /// Without a callback the warning is printed to standard error.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Description of the problem
+    pub fn on_warning(&self, message: &str) {
+        crate::core::report_warning(self.callback.as_deref(), message);
+    }
+
     /// Force a progress update regardless of timing
     ///
     /// This method bypasses the progress interval and always calls the callback
@@ -169,22 +301,116 @@ impl ProgressCallback for NoOpProgressCallback {
     }
 }
 
+/// A progress callback that sends every event into a channel
+///
+/// Sending never blocks, so a slow receiver cannot stall the benchmark. Events are
+/// discarded once the receiver has been dropped.
+#[derive(Debug)]
+pub struct ChannelProgressCallback {
+    sender: Mutex<Sender<ProgressEvent>>,
+}
+
+impl ChannelProgressCallback {
+    /// Create a callback that sends its events to `sender`
+    pub fn new(sender: Sender<ProgressEvent>) -> Self {
+        Self {
+            sender: Mutex::new(sender),
+        }
+    }
+
+    fn send(&self, event: ProgressEvent) {
+        let sender = self.sender.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = sender.send(event);
+    }
+}
+
+impl ProgressCallback for ChannelProgressCallback {
+    fn on_run_start(&self, run: usize, total_runs: usize) {
+        self.send(ProgressEvent::RunStart { run, total_runs });
+    }
+
+    fn on_test_start(&self, test_name: &str) {
+        self.send(ProgressEvent::TestStart {
+            test_name: test_name.to_string(),
+        });
+    }
+
+    fn on_warmup(&self, test_name: &str, warmup: Duration) {
+        self.send(ProgressEvent::Warmup {
+            test_name: test_name.to_string(),
+            duration: warmup,
+        });
+    }
+
+    fn on_progress(&self, test_name: &str, current_speed_mbps: f64) {
+        self.send(ProgressEvent::Progress {
+            test_name: test_name.to_string(),
+            speed_mbps: current_speed_mbps,
+        });
+    }
+
+    fn on_progress_update(&self, update: &ProgressUpdate) {
+        self.send(ProgressEvent::Update(update.clone()));
+    }
+
+    fn on_phase_change(&self, test_name: &str, phase: TestPhase) {
+        self.send(ProgressEvent::PhaseChange {
+            test_name: test_name.to_string(),
+            phase,
+        });
+    }
+
+    fn on_warning(&self, message: &str) {
+        self.send(ProgressEvent::Warning {
+            message: message.to_string(),
+        });
+    }
+
+    fn on_test_complete(&self, test_name: &str, result: &TestResult) {
+        self.send(ProgressEvent::TestComplete {
+            test_name: test_name.to_string(),
+            result: result.clone(),
+        });
+    }
+}
+
 /// A progress callback that collects all events for testing purposes
 #[derive(Debug, Default)]
 pub struct TestProgressCallback {
     events: Arc<Mutex<Vec<ProgressEvent>>>,
 }
 
-/// Events that can be captured by TestProgressCallback
+/// Events reported during a benchmark
+///
+/// Captured by [`TestProgressCallback`] and sent by [`ChannelProgressCallback`],
+/// one event per progress callback method. Both record the benchmark's periodic
+/// progress as [`ProgressEvent::Update`]; `Progress` only arrives from callers of
+/// [`ProgressCallback::on_progress`] itself.
 #[derive(Debug, Clone)]
 pub enum ProgressEvent {
+    RunStart {
+        run: usize,
+        total_runs: usize,
+    },
     TestStart {
         test_name: String,
     },
+    Warmup {
+        test_name: String,
+        duration: Duration,
+    },
     Progress {
         test_name: String,
         speed_mbps: f64,
     },
+    Update(ProgressUpdate),
+    PhaseChange {
+        test_name: String,
+        phase: TestPhase,
+    },
+    Warning {
+        message: String,
+    },
     TestComplete {
         test_name: String,
         result: TestResult,
@@ -233,20 +459,79 @@ impl TestProgressCallback {
             .collect()
     }
 
-    /// Get all progress events for a specific test
+    /// Get all run start events as run number and total runs
+    pub fn run_start_events(&self) -> Vec<(usize, usize)> {
+        self.events_of_type(|e| matches!(e, ProgressEvent::RunStart { .. }))
+            .into_iter()
+            .filter_map(|e| match e {
+                ProgressEvent::RunStart { run, total_runs } => Some((run, total_runs)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get all warm-up events as test name and warm-up length
+    pub fn warmup_events(&self) -> Vec<(String, Duration)> {
+        self.events_of_type(|e| matches!(e, ProgressEvent::Warmup { .. }))
+            .into_iter()
+            .filter_map(|e| match e {
+                ProgressEvent::Warmup {
+                    test_name,
+                    duration,
+                } => Some((test_name, duration)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the speeds of all progress events and updates for a specific test
     pub fn progress_events_for_test(&self, test_name: &str) -> Vec<f64> {
-        self.events_of_type(|e| match e {
-            ProgressEvent::Progress {
-                test_name: name, ..
-            } => name == test_name,
-            _ => false,
-        })
-        .into_iter()
-        .filter_map(|e| match e {
-            ProgressEvent::Progress { speed_mbps, .. } => Some(speed_mbps),
-            _ => None,
-        })
-        .collect()
+        self.events()
+            .into_iter()
+            .filter_map(|e| match e {
+                ProgressEvent::Progress {
+                    test_name: name,
+                    speed_mbps,
+                } if name == test_name => Some(speed_mbps),
+                ProgressEvent::Update(update) if update.test_name == test_name => {
+                    Some(update.current_speed_mbps)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get all progress updates for a specific test
+    pub fn progress_updates_for_test(&self, test_name: &str) -> Vec<ProgressUpdate> {
+        self.events()
+            .into_iter()
+            .filter_map(|e| match e {
+                ProgressEvent::Update(update) if update.test_name == test_name => Some(update),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get all phase change events as test name and phase
+    pub fn phase_change_events(&self) -> Vec<(String, TestPhase)> {
+        self.events()
+            .into_iter()
+            .filter_map(|e| match e {
+                ProgressEvent::PhaseChange { test_name, phase } => Some((test_name, phase)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get all warning messages
+    pub fn warning_events(&self) -> Vec<String> {
+        self.events()
+            .into_iter()
+            .filter_map(|e| match e {
+                ProgressEvent::Warning { message } => Some(message),
+                _ => None,
+            })
+            .collect()
     }
 
     /// Get all test complete events
@@ -262,6 +547,11 @@ impl TestProgressCallback {
 }
 
 impl ProgressCallback for TestProgressCallback {
+    fn on_run_start(&self, run: usize, total_runs: usize) {
+        let event = ProgressEvent::RunStart { run, total_runs };
+        self.events.lock().unwrap().push(event);
+    }
+
     fn on_test_start(&self, test_name: &str) {
         let event = ProgressEvent::TestStart {
             test_name: test_name.to_string(),
@@ -269,6 +559,14 @@ impl ProgressCallback for TestProgressCallback {
         self.events.lock().unwrap().push(event);
     }
 
+    fn on_warmup(&self, test_name: &str, warmup: Duration) {
+        let event = ProgressEvent::Warmup {
+            test_name: test_name.to_string(),
+            duration: warmup,
+        };
+        self.events.lock().unwrap().push(event);
+    }
+
     fn on_progress(&self, test_name: &str, current_speed_mbps: f64) {
         let event = ProgressEvent::Progress {
             test_name: test_name.to_string(),
@@ -277,6 +575,26 @@ impl ProgressCallback for TestProgressCallback {
         self.events.lock().unwrap().push(event);
     }
 
+    fn on_progress_update(&self, update: &ProgressUpdate) {
+        let event = ProgressEvent::Update(update.clone());
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn on_phase_change(&self, test_name: &str, phase: TestPhase) {
+        let event = ProgressEvent::PhaseChange {
+            test_name: test_name.to_string(),
+            phase,
+        };
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn on_warning(&self, message: &str) {
+        let event = ProgressEvent::Warning {
+            message: message.to_string(),
+        };
+        self.events.lock().unwrap().push(event);
+    }
+
     fn on_test_complete(&self, test_name: &str, result: &TestResult) {
         let event = ProgressEvent::TestComplete {
             test_name: test_name.to_string(),
@@ -415,6 +733,120 @@ mod tests {
         assert_eq!(complete_events.len(), 2);
     }
 
+    #[test]
+    fn test_warmup_events() {
+        let reporter = ProgressReporter::new(Some(Box::new(TestProgressCallback::new())));
+        // Forwarding must not panic with or without a callback
+        reporter.on_warmup("Test", Duration::from_secs(1));
+        ProgressReporter::new(None).on_warmup("Test", Duration::from_secs(1));
+
+        let callback = TestProgressCallback::new();
+        callback.on_test_start("Sequential Read");
+        callback.on_warmup("Sequential Read", Duration::from_secs(2));
+        callback.on_progress("Sequential Read", 200.0);
+
+        assert_eq!(
+            callback.warmup_events(),
+            vec![("Sequential Read".to_string(), Duration::from_secs(2))]
+        );
+        assert!(matches!(callback.events()[1], ProgressEvent::Warmup { .. }));
+
+        // Callbacks that ignore warm-ups keep the default no-op
+        NoOpProgressCallback.on_warmup("Test", Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_run_start_events() {
+        ProgressReporter::new(None).on_run_start(1, 3);
+        ProgressReporter::new(Some(Box::new(TestProgressCallback::new()))).on_run_start(1, 3);
+
+        let callback = TestProgressCallback::new();
+        callback.on_run_start(1, 2);
+        callback.on_test_start("Memory Copy");
+        callback.on_run_start(2, 2);
+        callback.on_test_start("Memory Copy");
+
+        assert_eq!(callback.run_start_events(), vec![(1, 2), (2, 2)]);
+        assert_eq!(callback.test_start_events().len(), 2);
+    }
+
+    fn sample_update(test_name: &str) -> ProgressUpdate {
+        ProgressUpdate {
+            test_name: test_name.to_string(),
+            phase: TestPhase::Measure,
+            elapsed: Duration::from_secs(3),
+            total: Some(Duration::from_secs(10)),
+            fraction: 0.3,
+            eta: Some(Duration::from_secs(7)),
+            bytes: 300 * 1024 * 1024,
+            operations: 300,
+            current_speed_mbps: 110.0,
+            average_speed_mbps: 100.0,
+            iops: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_update_phase_and_warning_events() {
+        let callback = TestProgressCallback::new();
+        callback.on_phase_change("Sequential Read", TestPhase::Measure);
+        callback.on_progress_update(&sample_update("Sequential Read"));
+        callback.on_warning("Failed to sync filesystem");
+
+        assert_eq!(
+            callback.phase_change_events(),
+            vec![("Sequential Read".to_string(), TestPhase::Measure)]
+        );
+        assert_eq!(
+            callback.progress_updates_for_test("Sequential Read"),
+            vec![sample_update("Sequential Read")]
+        );
+        assert_eq!(
+            callback.progress_events_for_test("Sequential Read"),
+            vec![110.0]
+        );
+        assert_eq!(
+            callback.warning_events(),
+            vec!["Failed to sync filesystem".to_string()]
+        );
+
+        let reporter = ProgressReporter::with_interval(
+            Some(Box::new(TestProgressCallback::new())),
+            Duration::ZERO,
+        );
+        reporter.on_phase_change("Test", TestPhase::Flush);
+        reporter.on_warning("Test warning");
+        assert!(reporter.on_progress_update(&sample_update("Test")));
+        assert!(!ProgressReporter::new(None).on_progress_update(&sample_update("Test")));
+    }
+
+    #[test]
+    fn test_progress_update_defaults_to_on_progress() {
+        // Callbacks written before progress updates existed still see the speed
+        struct SpeedOnly(Mutex<Vec<(String, f64)>>);
+
+        impl ProgressCallback for SpeedOnly {
+            fn on_test_start(&self, _test_name: &str) {}
+
+            fn on_progress(&self, test_name: &str, current_speed_mbps: f64) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((test_name.to_string(), current_speed_mbps));
+            }
+
+            fn on_test_complete(&self, _test_name: &str, _result: &TestResult) {}
+        }
+
+        let callback = SpeedOnly(Mutex::new(Vec::new()));
+        callback.on_progress_update(&sample_update("Random Read"));
+        callback.on_phase_change("Random Read", TestPhase::Measure);
+        assert_eq!(
+            *callback.0.lock().unwrap(),
+            vec![("Random Read".to_string(), 110.0)]
+        );
+    }
+
     #[test]
     fn test_test_progress_callback_clear() {
         let callback = TestProgressCallback::new();
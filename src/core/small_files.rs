@@ -0,0 +1,119 @@
+//! Results and file layout of the small-file test
+//!
+//! Copying source trees or unpacking container layers moves many files of a few
+//! kilobytes to a megabyte, where opening, creating and closing each file costs as
+//! much as its data. The small-file test writes a tree of such files and reads it
+//! back, reporting files per second and throughput for both directions along with
+//! how long each file took to open on the read-back.
+
+use crate::core::config::FileSizeShare;
+use crate::core::{LatencyStats, TestResult};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+/// Most files the small-file test puts in one directory
+pub const SMALL_FILES_PER_DIRECTORY: usize = 100;
+
+/// Seed of the file sizes and read order, so every run works on the same tree
+const SMALL_FILES_SEED: u64 = 0x5EED_F11E;
+
+/// Results of the small-file test
+///
+/// An operation is one whole file, so `latency.iops` of the write and read results
+/// is their rate in files per second.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmallFilesResult {
+    /// Number of files written and read back
+    pub file_count: u64,
+    /// Number of directories the files were spread across
+    pub directory_count: u64,
+    /// Creating, writing and closing every file, including the final flush to disk
+    pub write: TestResult,
+    /// Opening, reading and closing every file, in a different order than written
+    pub read: TestResult,
+    /// Time to open each file for the read-back, after the whole tree was written
+    pub open_latency: LatencyStats,
+}
+
+impl SmallFilesResult {
+    /// Files written per second
+    pub fn write_files_per_second(&self) -> f64 {
+        self.write.latency.iops
+    }
+
+    /// Files read back per second
+    pub fn read_files_per_second(&self) -> f64 {
+        self.read.latency.iops
+    }
+}
+
+/// Sizes of the test's files and the order they are read back in
+pub(crate) struct SmallFilePlan {
+    /// Size of each file, by index
+    pub(crate) sizes: Vec<usize>,
+    /// File indices in read-back order
+    pub(crate) read_order: Vec<usize>,
+}
+
+impl SmallFilePlan {
+    /// Draw `count` file sizes from `distribution` and shuffle the read order
+    ///
+    /// Reading the files in another order than they were written keeps the
+    /// read-back from profiting from readahead across neighbouring files.
+    pub(crate) fn new(distribution: &[FileSizeShare], count: usize) -> Self {
+        let mut rng = StdRng::seed_from_u64(SMALL_FILES_SEED);
+        let sizes = match WeightedIndex::new(distribution.iter().map(|share| share.weight)) {
+            Ok(index) => (0..count)
+                .map(|_| distribution[index.sample(&mut rng)].size)
+                .collect(),
+            // Validation rejects empty distributions and zero weights
+            Err(_) => Vec::new(),
+        };
+        let mut read_order: Vec<usize> = (0..sizes.len()).collect();
+        read_order.shuffle(&mut rng);
+        Self { sizes, read_order }
+    }
+
+    /// Number of directories the files are spread across
+    pub(crate) fn directory_count(&self) -> usize {
+        (self.sizes.len() + SMALL_FILES_PER_DIRECTORY - 1) / SMALL_FILES_PER_DIRECTORY
+    }
+
+    /// Size of the largest file
+    pub(crate) fn largest(&self) -> usize {
+        self.sizes.iter().copied().max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_follows_distribution() {
+        let distribution = [FileSizeShare::new(4096, 3), FileSizeShare::new(65536, 1)];
+        let plan = SmallFilePlan::new(&distribution, 4000);
+        assert_eq!(plan.sizes.len(), 4000);
+        assert_eq!(plan.directory_count(), 40);
+        assert_eq!(plan.largest(), 65536);
+
+        let small = plan.sizes.iter().filter(|&&size| size == 4096).count();
+        assert!((2800..3200).contains(&small), "{} small files", small);
+
+        let mut order = plan.read_order.clone();
+        assert_ne!(order, (0..4000).collect::<Vec<_>>());
+        order.sort_unstable();
+        assert_eq!(order, (0..4000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_plan_is_reproducible() {
+        let first = SmallFilePlan::new(&FileSizeShare::DEFAULT, 100);
+        let second = SmallFilePlan::new(&FileSizeShare::DEFAULT, 100);
+        assert_eq!(first.sizes, second.sizes);
+        assert_eq!(first.read_order, second.read_order);
+    }
+}
@@ -1,8 +1,40 @@
 //! Statistics collection and calculation for benchmark results
 
+use crate::core::verify::CorruptionEvent;
+use crate::core::CancellationToken;
+use hdrhistogram::Histogram;
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 
+/// Why a test stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    /// The configured test duration elapsed
+    #[default]
+    Duration,
+    /// The byte limit was reached
+    ByteLimit,
+    /// The operation limit was reached
+    OperationLimit,
+    /// The requested number of passes over the file was completed
+    PassLimit,
+    /// The time cap elapsed before the byte, operation or pass limit was reached
+    TimeCap,
+}
+
+impl std::fmt::Display for StopReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StopReason::Duration => write!(f, "duration"),
+            StopReason::ByteLimit => write!(f, "byte limit"),
+            StopReason::OperationLimit => write!(f, "operation limit"),
+            StopReason::PassLimit => write!(f, "pass limit"),
+            StopReason::TimeCap => write!(f, "time cap"),
+        }
+    }
+}
+
 /// Results from a single test execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestResult {
@@ -12,7 +44,7 @@ pub struct TestResult {
     /// Maximum speed recorded during the test (MB/s)
     pub max_speed_mbps: f64,
 
-    /// Average speed across all samples (MB/s)
+    /// Effective throughput: total bytes divided by the test's wall-clock time (MB/s)
     pub avg_speed_mbps: f64,
 
     /// Total duration of the test
@@ -20,6 +52,43 @@ pub struct TestResult {
 
     /// Number of samples collected
     pub sample_count: usize,
+
+    /// Mean of the per-block or per-interval speed samples (MB/s)
+    ///
+    /// Slow blocks weigh as much as fast ones, so with variable latency this is
+    /// higher than the effective throughput in `avg_speed_mbps`.
+    #[serde(default)]
+    pub sample_avg_speed_mbps: f64,
+
+    /// Total bytes transferred during the test
+    #[serde(default)]
+    pub total_bytes: u64,
+
+    /// Total operations (blocks read, written or copied) completed during the test
+    #[serde(default)]
+    pub total_operations: u64,
+
+    /// Per-operation latency and operation rate
+    #[serde(default)]
+    pub latency: LatencyStats,
+
+    /// What ended the test
+    #[serde(default)]
+    pub stop_reason: StopReason,
+
+    /// Sectors that failed verification; always zero unless `verify` is enabled
+    #[serde(default)]
+    pub corrupted_sectors: u64,
+
+    /// The first corrupted sectors found, with their offsets
+    /// (at most [`MAX_CORRUPTION_EVENTS`](crate::core::verify::MAX_CORRUPTION_EVENTS))
+    #[serde(default)]
+    pub corruption_events: Vec<CorruptionEvent>,
+
+    /// Throughput and latency of every sampling interval, in order
+    /// (empty unless `record_time_series` is enabled)
+    #[serde(default)]
+    pub time_series: Vec<IntervalSample>,
 }
 
 impl Default for TestResult {
@@ -30,12 +99,22 @@ impl Default for TestResult {
             avg_speed_mbps: 0.0,
             test_duration: Duration::from_secs(0),
             sample_count: 0,
+            sample_avg_speed_mbps: 0.0,
+            total_bytes: 0,
+            total_operations: 0,
+            latency: LatencyStats::default(),
+            stop_reason: StopReason::Duration,
+            corrupted_sectors: 0,
+            corruption_events: Vec::new(),
+            time_series: Vec::new(),
         }
     }
 }
 
 impl TestResult {
     /// Create a new test result with the given values
+    ///
+    /// The sample mean is taken to equal `avg_speed_mbps` and no totals are recorded.
     pub fn new(
         min_speed_mbps: f64,
         max_speed_mbps: f64,
@@ -49,8 +128,202 @@ impl TestResult {
             avg_speed_mbps,
             test_duration,
             sample_count,
+            sample_avg_speed_mbps: avg_speed_mbps,
+            total_bytes: 0,
+            total_operations: 0,
+            latency: LatencyStats::default(),
+            stop_reason: StopReason::Duration,
+            corrupted_sectors: 0,
+            corruption_events: Vec::new(),
+            time_series: Vec::new(),
         }
     }
+
+    /// Attach the mean of the speed samples, when it differs from the effective throughput
+    pub fn with_sample_average(mut self, sample_avg_speed_mbps: f64) -> Self {
+        self.sample_avg_speed_mbps = sample_avg_speed_mbps;
+        self
+    }
+
+    /// Attach the total bytes and operations of the test
+    pub fn with_totals(mut self, total_bytes: u64, total_operations: u64) -> Self {
+        self.total_bytes = total_bytes;
+        self.total_operations = total_operations;
+        self
+    }
+
+    /// Attach latency statistics to the result
+    pub fn with_latency(mut self, latency: LatencyStats) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Record what ended the test
+    pub fn with_stop_reason(mut self, stop_reason: StopReason) -> Self {
+        self.stop_reason = stop_reason;
+        self
+    }
+
+    /// Attach the number of corrupted sectors and the events logged for them
+    pub fn with_corruption(mut self, corrupted_sectors: u64, events: Vec<CorruptionEvent>) -> Self {
+        self.corrupted_sectors = corrupted_sectors;
+        self.corruption_events = events;
+        self
+    }
+
+    /// Attach the per-interval samples of the test
+    pub fn with_time_series(mut self, time_series: Vec<IntervalSample>) -> Self {
+        self.time_series = time_series;
+        self
+    }
+
+    /// Whether verification found corrupted data
+    pub fn has_corruption(&self) -> bool {
+        self.corrupted_sectors > 0
+    }
+}
+
+/// Throughput and latency of one sampling interval of a test
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct IntervalSample {
+    /// Measured time at the end of the interval, excluding the warm-up
+    pub elapsed: Duration,
+
+    /// Bytes transferred during the interval
+    pub bytes: u64,
+
+    /// Operations completed during the interval
+    pub operations: u64,
+
+    /// Throughput during the interval (MB/s)
+    pub speed_mbps: f64,
+
+    /// Mean latency of the operations completed during the interval (µs),
+    /// or `None` when none completed
+    pub mean_latency_us: Option<f64>,
+}
+
+/// Per-operation latency statistics of a test, in microseconds
+///
+/// An operation is one block read, written or copied; for queued backends its
+/// latency spans submission to completion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyStats {
+    /// Number of completed operations
+    pub operations: u64,
+
+    /// Completed operations per second over the whole test
+    pub iops: f64,
+
+    /// Mean latency (µs)
+    pub mean_us: f64,
+
+    /// Median latency (µs)
+    pub p50_us: f64,
+
+    /// 90th percentile latency (µs)
+    pub p90_us: f64,
+
+    /// 99th percentile latency (µs)
+    pub p99_us: f64,
+
+    /// 99.9th percentile latency (µs)
+    pub p999_us: f64,
+
+    /// Maximum latency (µs)
+    pub max_us: f64,
+}
+
+/// Records per-operation latencies into a histogram
+///
+/// Latencies are kept in nanoseconds with three significant digits, so memory use
+/// is fixed no matter how many operations a test performs. Each thread of a test
+/// keeps its own recorder, and they are [merged](Self::merge) when the test ends.
+#[derive(Debug, Clone)]
+pub struct LatencyRecorder {
+    histogram: Histogram<u64>,
+}
+
+impl LatencyRecorder {
+    /// Longest latency tracked exactly; slower operations are clamped to it (1 minute)
+    const MAX_TRACKED_NANOS: u64 = 60_000_000_000;
+
+    /// Create an empty recorder
+    pub fn new() -> Self {
+        let histogram = Histogram::new_with_bounds(1, Self::MAX_TRACKED_NANOS, 3)
+            .expect("valid histogram bounds");
+        Self { histogram }
+    }
+
+    /// Record the latency of one operation
+    pub fn record(&mut self, latency: Duration) {
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        self.histogram.saturating_record(nanos);
+    }
+
+    /// Add every operation recorded by `other`
+    pub fn merge(&mut self, other: &LatencyRecorder) {
+        // Both histograms share the same bounds, so adding cannot fail
+        let _ = self.histogram.add(&other.histogram);
+    }
+
+    /// Number of operations recorded so far
+    pub fn operations(&self) -> u64 {
+        self.histogram.len()
+    }
+
+    /// Forget every recorded operation, keeping the histogram's memory
+    pub fn reset(&mut self) {
+        self.histogram.reset();
+    }
+
+    /// Summarize the recorded latencies, computing IOPS over `elapsed`
+    pub fn finalize(&self, elapsed: Duration) -> LatencyStats {
+        let operations = self.operations();
+        if operations == 0 {
+            return LatencyStats::default();
+        }
+
+        let micros = |nanos: u64| nanos as f64 / 1_000.0;
+        let iops = if elapsed.is_zero() {
+            0.0
+        } else {
+            operations as f64 / elapsed.as_secs_f64()
+        };
+
+        LatencyStats {
+            operations,
+            iops,
+            mean_us: self.histogram.mean() / 1_000.0,
+            p50_us: micros(self.histogram.value_at_quantile(0.50)),
+            p90_us: micros(self.histogram.value_at_quantile(0.90)),
+            p99_us: micros(self.histogram.value_at_quantile(0.99)),
+            p999_us: micros(self.histogram.value_at_quantile(0.999)),
+            max_us: micros(self.histogram.max()),
+        }
+    }
+}
+
+impl Default for LatencyRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Results from a mixed random read/write test
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MixedTestResult {
+    /// Share of requests that were reads, in percent
+    pub read_percentage: u8,
+
+    /// Throughput of the read share
+    pub read: TestResult,
+
+    /// Throughput of the write share
+    pub write: TestResult,
+
+    /// Combined read and write throughput
+    pub combined: TestResult,
 }
 
 /// Collects performance statistics during test execution
@@ -60,13 +333,99 @@ pub struct StatisticsCollector {
     last_sample_time: Instant,
     bytes_transferred: u64,
     sample_interval: Duration,
+    clock: Clock,
+}
+
+/// Clock statistics are timed on, standing still while its token is paused
+#[derive(Debug, Clone, Default)]
+struct Clock(Option<CancellationToken>);
+
+impl Clock {
+    fn now(&self) -> Instant {
+        self.0
+            .as_ref()
+            .map_or_else(Instant::now, CancellationToken::now)
+    }
+
+    /// Time passed since `earlier`, an instant taken from this clock
+    fn since(&self, earlier: Instant) -> Duration {
+        self.now().saturating_duration_since(earlier)
+    }
+}
+
+/// Running count and summed latency of completed operations
+#[derive(Debug, Clone, Copy, Default)]
+struct OperationTotals {
+    operations: u64,
+    latency: Duration,
+}
+
+impl OperationTotals {
+    /// Operations completed since `earlier`
+    fn since(self, earlier: OperationTotals) -> OperationTotals {
+        OperationTotals {
+            operations: self.operations.saturating_sub(earlier.operations),
+            latency: self.latency.saturating_sub(earlier.latency),
+        }
+    }
+}
+
+/// Splits a test into sampling intervals as it runs
+#[derive(Debug, Default)]
+struct TimeSeries {
+    samples: Vec<IntervalSample>,
+    /// Measured time at the start of the current interval
+    start: Duration,
+    /// Bytes measured at the start of the current interval
+    start_bytes: u64,
+    /// Operations measured at the start of the current interval
+    start_operations: OperationTotals,
+}
+
+impl TimeSeries {
+    /// End the current interval at `end` and start the next one
+    fn close_interval(&mut self, end: Duration, bytes: u64, operations: OperationTotals) {
+        let interval = operations.since(self.start_operations);
+        let bytes_in_interval = bytes.saturating_sub(self.start_bytes);
+        self.samples.push(IntervalSample {
+            elapsed: end,
+            bytes: bytes_in_interval,
+            operations: interval.operations,
+            speed_mbps: StatisticsCollector::calculate_speed_mbps(
+                bytes_in_interval,
+                end.saturating_sub(self.start),
+            ),
+            mean_latency_us: (interval.operations > 0)
+                .then(|| interval.latency.as_secs_f64() * 1e6 / interval.operations as f64),
+        });
+        self.start = end;
+        self.start_bytes = bytes;
+        self.start_operations = operations;
+    }
 }
 
 /// Real-time statistics tracker for ongoing operations
+///
+/// With a [warm-up](Self::with_warmup) the tracker discards everything it is fed
+/// until the warm-up ends, then starts measuring from scratch.
 pub struct RealTimeStatsTracker {
     collector: StatisticsCollector,
+    latency: LatencyRecorder,
     last_bytes: u64,
     last_sample_time: Instant,
+    /// End of the warm-up, until measurement starts
+    warmup_end: Option<Instant>,
+    clock: Clock,
+    /// Byte total reported by `update_progress` when measurement started
+    baseline_bytes: u64,
+    /// Operations measured so far, for the time series
+    operations: OperationTotals,
+    /// Latest totals reported by `update_operations`, including the warm-up
+    reported_operations: OperationTotals,
+    /// Totals reported by `update_operations` when measurement started
+    baseline_operations: OperationTotals,
+    /// Per-interval samples, when enabled with [`with_time_series`](Self::with_time_series)
+    time_series: Option<TimeSeries>,
 }
 
 impl StatisticsCollector {
@@ -77,13 +436,18 @@ impl StatisticsCollector {
 
     /// Create a new statistics collector with custom sampling interval
     pub fn with_sample_interval(sample_interval: Duration) -> Self {
-        let now = Instant::now();
+        Self::with_clock(sample_interval, Clock::default())
+    }
+
+    fn with_clock(sample_interval: Duration, clock: Clock) -> Self {
+        let now = clock.now();
         Self {
             samples: Vec::new(),
             start_time: now,
             last_sample_time: now,
             bytes_transferred: 0,
             sample_interval,
+            clock,
         }
     }
 
@@ -110,7 +474,7 @@ impl StatisticsCollector {
 
     /// Check if it's time to take a sample based on the sampling interval
     pub fn should_sample(&self) -> bool {
-        self.last_sample_time.elapsed() >= self.sample_interval
+        self.clock.since(self.last_sample_time) >= self.sample_interval
     }
 
     /// Take a sample if the sampling interval has elapsed
@@ -134,7 +498,7 @@ impl StatisticsCollector {
     /// Add a speed sample (in MB/s)
     pub fn add_sample(&mut self, speed_mbps: f64) {
         self.samples.push(speed_mbps);
-        self.last_sample_time = Instant::now();
+        self.last_sample_time = self.clock.now();
     }
 
     /// Get the number of samples collected
@@ -163,7 +527,7 @@ impl StatisticsCollector {
 
     /// Get the elapsed time since collection started
     pub fn elapsed(&self) -> Duration {
-        self.start_time.elapsed()
+        self.clock.since(self.start_time)
     }
 
     /// Compute percentile using the nearest-rank method
@@ -197,8 +561,12 @@ impl StatisticsCollector {
     }
 
     /// Finalize collection and return test results
+    ///
+    /// The headline average is the effective throughput, bytes transferred over the
+    /// elapsed wall-clock time, as reported by tools like fio and dd. Collectors fed
+    /// only speed samples, with no byte count, fall back to the sample mean.
     pub fn finalize(self) -> TestResult {
-        if self.samples.is_empty() {
+        if self.samples.is_empty() && self.bytes_transferred == 0 {
             return TestResult::default();
         }
 
@@ -206,15 +574,17 @@ impl StatisticsCollector {
         let min_speed = Self::percentile_nearest_rank(self.samples.clone(), 5.0);
         // Use P95 instead of absolute maximum
         let max_speed = Self::percentile_nearest_rank(self.samples.clone(), 95.0);
-        let avg_speed = self.samples.iter().sum::<f64>() / self.samples.len() as f64;
-
-        TestResult::new(
-            min_speed,
-            max_speed,
-            avg_speed,
-            self.elapsed(),
-            self.samples.len(),
-        )
+        let sample_avg_speed = self.current_average();
+        let elapsed = self.elapsed();
+        let avg_speed = if self.bytes_transferred > 0 {
+            Self::calculate_speed_mbps(self.bytes_transferred, elapsed)
+        } else {
+            sample_avg_speed
+        };
+
+        TestResult::new(min_speed, max_speed, avg_speed, elapsed, self.samples.len())
+            .with_sample_average(sample_avg_speed)
+            .with_totals(self.bytes_transferred, 0)
     }
 
     /// Calculate speed in MB/s from bytes and duration
@@ -240,8 +610,16 @@ impl RealTimeStatsTracker {
     pub fn new() -> Self {
         Self {
             collector: StatisticsCollector::new(),
+            latency: LatencyRecorder::new(),
             last_bytes: 0,
             last_sample_time: Instant::now(),
+            warmup_end: None,
+            clock: Clock::default(),
+            baseline_bytes: 0,
+            operations: OperationTotals::default(),
+            reported_operations: OperationTotals::default(),
+            baseline_operations: OperationTotals::default(),
+            time_series: None,
         }
     }
 
@@ -249,21 +627,110 @@ impl RealTimeStatsTracker {
     pub fn with_sample_interval(sample_interval: Duration) -> Self {
         Self {
             collector: StatisticsCollector::with_sample_interval(sample_interval),
+            latency: LatencyRecorder::new(),
             last_bytes: 0,
             last_sample_time: Instant::now(),
+            warmup_end: None,
+            clock: Clock::default(),
+            baseline_bytes: 0,
+            operations: OperationTotals::default(),
+            reported_operations: OperationTotals::default(),
+            baseline_operations: OperationTotals::default(),
+            time_series: None,
+        }
+    }
+
+    /// Discard all blocks, byte totals and latencies for `warmup` from now
+    ///
+    /// The first update after the warm-up ends starts the measurement: the test's
+    /// elapsed time, byte total and samples all count from that point. The
+    /// operation reported by that update began during the warm-up, so it is
+    /// discarded as well. A zero warm-up measures from the start.
+    pub fn with_warmup(mut self, warmup: Duration) -> Self {
+        self.warmup_end = (!warmup.is_zero()).then(|| self.clock.now() + warmup);
+        self
+    }
+
+    /// Time the test on `token`'s clock, leaving out the time it spends paused
+    ///
+    /// Paused time counts neither toward the elapsed time nor toward any sample,
+    /// so a pause does not lower the measured throughput.
+    pub fn with_pauses(mut self, token: &CancellationToken) -> Self {
+        let clock = Clock(Some(token.clone()));
+        let now = clock.now();
+        let wall_now = Instant::now();
+        self.warmup_end = self
+            .warmup_end
+            .map(|end| now + end.saturating_duration_since(wall_now));
+        self.last_sample_time = now;
+        self.collector.start_time = now;
+        self.collector.last_sample_time = now;
+        self.collector.clock = clock.clone();
+        self.clock = clock;
+        self
+    }
+
+    /// Keep the throughput, operations and mean latency of every sampling interval
+    ///
+    /// The samples end up in [`TestResult::time_series`]; nothing is kept when
+    /// `enabled` is false.
+    pub fn with_time_series(mut self, enabled: bool) -> Self {
+        self.time_series = enabled.then(TimeSeries::default);
+        self
+    }
+
+    /// Whether the tracker is still discarding updates
+    pub fn is_warming_up(&self) -> bool {
+        self.warmup_end.is_some()
+    }
+
+    /// Check for the end of the warm-up, starting the measurement once it is over
+    ///
+    /// Returns `true` while the update being processed must be discarded.
+    fn discard_warmup_update(&mut self, total_bytes: u64) -> bool {
+        let Some(warmup_end) = self.warmup_end else {
+            return false;
+        };
+
+        let now = self.clock.now();
+        if now >= warmup_end {
+            self.collector =
+                StatisticsCollector::with_clock(self.collector.sample_interval, self.clock.clone());
+            self.latency.reset();
+            self.baseline_bytes = total_bytes;
+            self.last_bytes = 0;
+            self.last_sample_time = now;
+            self.warmup_end = None;
+            self.operations = OperationTotals::default();
+            self.baseline_operations = self.reported_operations;
+            if let Some(time_series) = &mut self.time_series {
+                *time_series = TimeSeries::default();
+            }
         }
+        true
     }
 
     /// Record a single block operation as a sample using its size and duration
+    /// The duration is also recorded as the operation's latency
     /// Returns the block speed (MB/s) if it's time to report progress, otherwise None
     pub fn record_block(&mut self, bytes: usize, duration: Duration) -> Option<f64> {
+        if self.discard_warmup_update(0) {
+            return None;
+        }
+        if bytes > 0 {
+            self.latency.record(duration);
+            self.collector.add_bytes_transferred(bytes as u64);
+            self.operations.operations += 1;
+            self.operations.latency += duration;
+        }
+        self.sample_interval_if_due();
         if duration.is_zero() || bytes == 0 {
             return None;
         }
         let speed = StatisticsCollector::calculate_speed_mbps(bytes as u64, duration);
         self.collector.add_sample(speed);
-        if self.last_sample_time.elapsed() >= self.collector.sample_interval {
-            self.last_sample_time = Instant::now();
+        if self.clock.since(self.last_sample_time) >= self.collector.sample_interval {
+            self.last_sample_time = self.clock.now();
             Some(speed)
         } else {
             None
@@ -273,12 +740,17 @@ impl RealTimeStatsTracker {
     /// Update progress and automatically sample if interval has elapsed
     /// Returns the current instantaneous speed if a sample was taken
     pub fn update_progress(&mut self, total_bytes: u64) -> Option<f64> {
+        if self.discard_warmup_update(total_bytes) {
+            return None;
+        }
+        let total_bytes = total_bytes.saturating_sub(self.baseline_bytes);
         self.collector.update_bytes_transferred(total_bytes);
+        self.sample_interval_if_due();
 
         if self.collector.should_sample() {
             // Calculate instantaneous speed since last sample
             let bytes_since_last = total_bytes.saturating_sub(self.last_bytes);
-            let time_since_last = self.last_sample_time.elapsed();
+            let time_since_last = self.clock.since(self.last_sample_time);
             let instantaneous_speed =
                 StatisticsCollector::calculate_speed_mbps(bytes_since_last, time_since_last);
 
@@ -287,7 +759,7 @@ impl RealTimeStatsTracker {
 
             // Update tracking variables
             self.last_bytes = total_bytes;
-            self.last_sample_time = Instant::now();
+            self.last_sample_time = self.clock.now();
 
             Some(instantaneous_speed)
         } else {
@@ -295,6 +767,92 @@ impl RealTimeStatsTracker {
         }
     }
 
+    /// Record the trailing partial interval left over after `update_progress` sampling
+    ///
+    /// The tail is only kept when it spans at least half a sampling interval, or when
+    /// no samples were taken at all, so very short tails don't skew the percentiles.
+    /// Returns the speed of the recorded interval, if any.
+    pub fn flush_progress(&mut self, total_bytes: u64) -> Option<f64> {
+        // Nothing was measured if the test ended during the warm-up
+        if self.is_warming_up() {
+            return None;
+        }
+        let total_bytes = total_bytes.saturating_sub(self.baseline_bytes);
+        self.collector.update_bytes_transferred(total_bytes);
+
+        let bytes_since_last = total_bytes.saturating_sub(self.last_bytes);
+        let time_since_last = self.clock.since(self.last_sample_time);
+        if bytes_since_last == 0 || time_since_last.is_zero() {
+            return None;
+        }
+        if self.collector.sample_count() > 0 && time_since_last < self.collector.sample_interval / 2
+        {
+            return None;
+        }
+
+        let speed = StatisticsCollector::calculate_speed_mbps(bytes_since_last, time_since_last);
+        self.collector.add_sample(speed);
+        self.last_bytes = total_bytes;
+        self.last_sample_time = self.clock.now();
+
+        Some(speed)
+    }
+
+    /// Record the latency of one operation whose bytes are reported via `update_progress`
+    pub fn record_latency(&mut self, latency: Duration) {
+        if self.is_warming_up() {
+            return;
+        }
+        self.latency.record(latency);
+        self.operations.operations += 1;
+        self.operations.latency += latency;
+    }
+
+    /// Report the running operation count and summed latency, including the warm-up,
+    /// of a test whose latencies are only merged once it ends
+    ///
+    /// Only the time series uses these totals; call this before `update_progress`
+    /// so that both count from the same point when the warm-up ends.
+    pub fn update_operations(&mut self, total_operations: u64, total_latency: Duration) {
+        self.reported_operations = OperationTotals {
+            operations: total_operations,
+            latency: total_latency,
+        };
+        if !self.is_warming_up() {
+            self.operations = self.reported_operations.since(self.baseline_operations);
+        }
+    }
+
+    /// Add latencies recorded separately, e.g. by worker threads
+    ///
+    /// The caller is responsible for leaving out operations from the warm-up.
+    pub fn merge_latency(&mut self, recorder: &LatencyRecorder) {
+        self.latency.merge(recorder);
+    }
+
+    /// Close the current time series interval once it spans a sampling interval
+    fn sample_interval_if_due(&mut self) {
+        let Some(time_series) = &mut self.time_series else {
+            return;
+        };
+        let elapsed = self.collector.elapsed();
+        if elapsed.saturating_sub(time_series.start) >= self.collector.sample_interval {
+            time_series.close_interval(
+                elapsed,
+                self.collector.get_bytes_transferred(),
+                self.operations,
+            );
+        }
+    }
+
+    /// Bytes and operations measured so far, excluding the warm-up
+    pub fn measured_totals(&self) -> (u64, u64) {
+        (
+            self.collector.get_bytes_transferred(),
+            self.latency.operations(),
+        )
+    }
+
     /// Get the current overall average speed
     pub fn current_average_speed(&self) -> f64 {
         self.collector.current_speed_mbps()
@@ -306,7 +864,7 @@ impl RealTimeStatsTracker {
             .collector
             .get_bytes_transferred()
             .saturating_sub(self.last_bytes);
-        let time_since_last = self.last_sample_time.elapsed();
+        let time_since_last = self.clock.since(self.last_sample_time);
         StatisticsCollector::calculate_speed_mbps(bytes_since_last, time_since_last)
     }
 
@@ -321,9 +879,30 @@ impl RealTimeStatsTracker {
     }
 
     /// Finalize and get test results
+    ///
+    /// The time series ends with the partial interval left since the last full one.
     pub fn finalize(self) -> TestResult {
         // Do not force a synthetic final sample; rely on recorded block samples
-        self.collector.finalize()
+        let elapsed = self.collector.elapsed();
+        let latency = self.latency.finalize(elapsed);
+        let time_series = match self.time_series {
+            Some(mut time_series) if self.warmup_end.is_none() => {
+                let bytes = self.collector.get_bytes_transferred();
+                if bytes > time_series.start_bytes
+                    || self.operations.operations > time_series.start_operations.operations
+                {
+                    time_series.close_interval(elapsed, bytes, self.operations);
+                }
+                time_series.samples
+            }
+            _ => Vec::new(),
+        };
+        let result = self.collector.finalize();
+        let total_bytes = result.total_bytes;
+        result
+            .with_totals(total_bytes, latency.operations)
+            .with_latency(latency)
+            .with_time_series(time_series)
     }
 }
 
@@ -521,6 +1100,277 @@ mod tests {
         assert!(speed < 10000.0); // Should be reasonable
     }
 
+    #[test]
+    fn test_flush_progress_records_tail() {
+        let mut tracker = RealTimeStatsTracker::with_sample_interval(Duration::from_secs(10));
+
+        // Nothing transferred: nothing to flush
+        assert!(tracker.flush_progress(0).is_none());
+
+        // Short run that never reached a sampling interval still yields one sample
+        thread::sleep(Duration::from_millis(5));
+        assert!(tracker.flush_progress(1024 * 1024).is_some());
+        let (_min, _max, _avg, count) = tracker.current_stats();
+        assert_eq!(count, 1);
+
+        // A tail shorter than half an interval is dropped once samples exist
+        assert!(tracker.flush_progress(2 * 1024 * 1024).is_none());
+    }
+
+    #[test]
+    fn test_latency_recorder_percentiles() {
+        let mut recorder = LatencyRecorder::new();
+        for micros in 1..=1000u64 {
+            recorder.record(Duration::from_micros(micros));
+        }
+        assert_eq!(recorder.operations(), 1000);
+
+        let stats = recorder.finalize(Duration::from_secs(2));
+        assert_eq!(stats.operations, 1000);
+        assert!((stats.iops - 500.0).abs() < 1e-9);
+        assert!((stats.mean_us - 500.5).abs() < 1.0);
+        // Three significant digits leave at most 0.1% error
+        assert!((stats.p50_us - 500.0).abs() <= 1.0);
+        assert!((stats.p90_us - 900.0).abs() <= 1.0);
+        assert!((stats.p99_us - 990.0).abs() <= 1.0);
+        assert!((stats.p999_us - 999.0).abs() <= 1.0);
+        assert!((stats.max_us - 1000.0).abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_latency_recorder_merge_and_empty() {
+        let empty = LatencyRecorder::new().finalize(Duration::from_secs(1));
+        assert_eq!(empty, LatencyStats::default());
+
+        let mut first = LatencyRecorder::new();
+        let mut second = LatencyRecorder::new();
+        first.record(Duration::from_micros(10));
+        second.record(Duration::from_micros(30));
+        first.merge(&second);
+
+        let stats = first.finalize(Duration::ZERO);
+        assert_eq!(stats.operations, 2);
+        assert_eq!(stats.iops, 0.0);
+        assert!((stats.max_us - 30.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_latency_recorder_reset_and_clamp() {
+        let mut recorder = LatencyRecorder::new();
+        recorder.record(Duration::from_secs(3600));
+        let stats = recorder.finalize(Duration::from_secs(1));
+        assert!((stats.max_us / 1e6 - 60.0).abs() < 0.1);
+
+        recorder.reset();
+        assert_eq!(recorder.operations(), 0);
+        recorder.record(Duration::from_micros(10));
+        assert!((recorder.finalize(Duration::ZERO).max_us - 10.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_tracker_reports_block_latency() {
+        let mut tracker = RealTimeStatsTracker::new();
+        tracker.record_block(4096, Duration::from_micros(100));
+        tracker.record_block(4096, Duration::from_micros(300));
+        tracker.record_latency(Duration::from_micros(200));
+
+        let result = tracker.finalize();
+        assert_eq!(result.latency.operations, 3);
+        assert!((result.latency.p50_us - 200.0).abs() < 0.5);
+        assert!(result.latency.iops > 0.0);
+    }
+
+    #[test]
+    fn test_effective_throughput_uses_wall_time() {
+        let mut tracker = RealTimeStatsTracker::new();
+        // One fast and one slow block: the sample mean is dominated by the fast one
+        tracker.record_block(1024 * 1024, Duration::from_millis(1));
+        tracker.record_block(1024 * 1024, Duration::from_millis(99));
+        std::thread::sleep(Duration::from_millis(50));
+
+        let result = tracker.finalize();
+        assert_eq!(result.total_bytes, 2 * 1024 * 1024);
+        assert_eq!(result.total_operations, 2);
+        assert!((result.sample_avg_speed_mbps - (1000.0 + 1000.0 / 99.0) / 2.0).abs() < 0.01);
+
+        let expected =
+            StatisticsCollector::calculate_speed_mbps(2 * 1024 * 1024, result.test_duration);
+        assert!((result.avg_speed_mbps - expected).abs() < 1e-9);
+        assert!(result.avg_speed_mbps < result.sample_avg_speed_mbps);
+    }
+
+    #[test]
+    fn test_effective_throughput_from_progress_totals() {
+        let mut tracker = RealTimeStatsTracker::with_sample_interval(Duration::from_millis(1));
+        std::thread::sleep(Duration::from_millis(5));
+        tracker.update_progress(4096);
+        tracker.flush_progress(8192);
+
+        let result = tracker.finalize();
+        assert_eq!(result.total_bytes, 8192);
+        let expected = StatisticsCollector::calculate_speed_mbps(8192, result.test_duration);
+        assert!((result.avg_speed_mbps - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_warmup_blocks_are_discarded() {
+        let mut tracker = RealTimeStatsTracker::new().with_warmup(Duration::from_millis(30));
+        assert!(tracker.is_warming_up());
+        assert!(tracker
+            .record_block(1024 * 1024, Duration::from_millis(1))
+            .is_none());
+        tracker.record_latency(Duration::from_millis(1));
+
+        // The first block after the warm-up only starts the measurement
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(tracker
+            .record_block(1024 * 1024, Duration::from_millis(1))
+            .is_none());
+        assert!(!tracker.is_warming_up());
+
+        tracker.record_block(4096, Duration::from_millis(1));
+        let result = tracker.finalize();
+        assert_eq!(result.sample_count, 1);
+        assert_eq!(result.total_bytes, 4096);
+        assert_eq!(result.total_operations, 1);
+        assert!(result.test_duration < Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_paused_time_is_not_measured() {
+        let token = CancellationToken::new();
+        let mut tracker = RealTimeStatsTracker::with_sample_interval(Duration::from_millis(1))
+            .with_pauses(&token)
+            .with_warmup(Duration::from_millis(10));
+
+        // A pause during the warm-up postpones its end
+        token.pause();
+        std::thread::sleep(Duration::from_millis(30));
+        token.resume();
+        assert!(tracker.update_progress(1024).is_none());
+        assert!(tracker.is_warming_up());
+        std::thread::sleep(Duration::from_millis(15));
+        assert!(tracker.update_progress(1024).is_none());
+        assert!(!tracker.is_warming_up());
+
+        std::thread::sleep(Duration::from_millis(5));
+        token.pause();
+        std::thread::sleep(Duration::from_millis(200));
+        token.resume();
+        let speed = tracker.update_progress(1024 + 1024 * 1024).unwrap();
+        tracker.flush_progress(1024 + 1024 * 1024);
+
+        let result = tracker.finalize();
+        assert_eq!(result.total_bytes, 1024 * 1024);
+        assert!(result.test_duration < Duration::from_millis(100));
+        let paused_speed =
+            StatisticsCollector::calculate_speed_mbps(1024 * 1024, Duration::from_millis(200));
+        assert!(speed > paused_speed);
+    }
+
+    #[test]
+    fn test_warmup_progress_counts_from_baseline() {
+        let mut tracker = RealTimeStatsTracker::with_sample_interval(Duration::from_millis(1))
+            .with_warmup(Duration::from_millis(10));
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(tracker.update_progress(1000).is_none());
+
+        std::thread::sleep(Duration::from_millis(15));
+        assert!(tracker.update_progress(5000).is_none());
+        std::thread::sleep(Duration::from_millis(5));
+        tracker.flush_progress(7000);
+
+        let result = tracker.finalize();
+        assert_eq!(result.total_bytes, 2000);
+        assert_eq!(result.sample_count, 1);
+    }
+
+    #[test]
+    fn test_test_ending_during_warmup_has_no_statistics() {
+        let mut tracker = RealTimeStatsTracker::new().with_warmup(Duration::from_secs(60));
+        tracker.update_progress(4096);
+        assert!(tracker.flush_progress(8192).is_none());
+
+        let result = tracker.finalize();
+        assert_eq!(result.total_bytes, 0);
+        assert_eq!(result.sample_count, 0);
+    }
+
+    #[test]
+    fn test_time_series_records_each_interval() {
+        let mut tracker = RealTimeStatsTracker::with_sample_interval(Duration::from_millis(20))
+            .with_time_series(true);
+        tracker.record_block(4096, Duration::from_micros(100));
+        tracker.record_block(4096, Duration::from_micros(300));
+        std::thread::sleep(Duration::from_millis(25));
+        // Closes the first interval, which now holds three blocks
+        tracker.record_block(8192, Duration::from_micros(200));
+        tracker.record_block(1024, Duration::from_micros(400));
+
+        let result = tracker.finalize();
+        let series = &result.time_series;
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].bytes, 16384);
+        assert_eq!(series[0].operations, 3);
+        assert!((series[0].mean_latency_us.unwrap() - 200.0).abs() < 0.001);
+        assert!(series[0].speed_mbps > 0.0);
+        assert!(series[0].elapsed >= Duration::from_millis(20));
+        // The trailing partial interval is kept
+        assert_eq!(series[1].bytes, 1024);
+        assert_eq!(series[1].operations, 1);
+        assert!(series[1].elapsed >= series[0].elapsed);
+        assert_eq!(
+            series.iter().map(|sample| sample.bytes).sum::<u64>(),
+            result.total_bytes
+        );
+    }
+
+    #[test]
+    fn test_time_series_is_off_by_default() {
+        let mut tracker = RealTimeStatsTracker::with_sample_interval(Duration::from_millis(1));
+        tracker.record_block(4096, Duration::from_micros(100));
+        std::thread::sleep(Duration::from_millis(2));
+        tracker.record_block(4096, Duration::from_micros(100));
+
+        assert!(tracker.finalize().time_series.is_empty());
+    }
+
+    #[test]
+    fn test_time_series_reported_operations_count_from_warmup_end() {
+        let mut tracker = RealTimeStatsTracker::with_sample_interval(Duration::from_secs(60))
+            .with_warmup(Duration::from_millis(10))
+            .with_time_series(true);
+        tracker.update_operations(4, Duration::from_millis(4));
+        tracker.update_progress(4000);
+
+        std::thread::sleep(Duration::from_millis(15));
+        tracker.update_operations(10, Duration::from_millis(10));
+        tracker.update_progress(10_000);
+        assert!(!tracker.is_warming_up());
+
+        tracker.update_operations(13, Duration::from_millis(16));
+        tracker.update_progress(13_000);
+
+        let result = tracker.finalize();
+        assert_eq!(result.time_series.len(), 1);
+        let sample = result.time_series[0];
+        assert_eq!(sample.bytes, 3000);
+        assert_eq!(sample.operations, 3);
+        assert!((sample.mean_latency_us.unwrap() - 2000.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_sample_only_collector_falls_back_to_sample_mean() {
+        let mut collector = StatisticsCollector::new();
+        collector.add_sample(10.0);
+        collector.add_sample(30.0);
+
+        let result = collector.finalize();
+        assert_eq!(result.total_bytes, 0);
+        assert_eq!(result.avg_speed_mbps, 20.0);
+        assert_eq!(result.sample_avg_speed_mbps, 20.0);
+    }
+
     #[test]
     fn test_edge_case_zero_bytes() {
         let mut collector = StatisticsCollector::new();
@@ -294,8 +294,10 @@ mod tests {
 
         assert_eq!(result.sample_count, 5);
         assert!(result.avg_speed_mbps > 0.0);
-        assert!(result.min_speed_mbps <= result.avg_speed_mbps);
-        assert!(result.avg_speed_mbps <= result.max_speed_mbps);
+        assert!(result.min_speed_mbps <= result.sample_avg_speed_mbps);
+        assert!(result.sample_avg_speed_mbps <= result.max_speed_mbps);
+        assert_eq!(result.total_bytes, 1024 * (1 + 2 + 3 + 4 + 5));
+        assert_eq!(result.total_operations, 5);
     }
 
     #[test]
@@ -0,0 +1,385 @@
+//! Throughput over time of the sustained write test
+//!
+//! Consumer SSDs absorb writes in a fast SLC cache and slow down sharply once it is
+//! full; drives also throttle when they get hot. A single average hides both, so the
+//! sustained write test samples its throughput at fixed intervals and splits the
+//! samples into phases of steady speed, reporting the speed before the first drop,
+//! how much was written before it, and the speed the drive settles at.
+
+use crate::core::{StatisticsCollector, TestResult};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Relative speed difference between adjacent phases that counts as a step change
+pub const STEP_CHANGE: f64 = 0.2;
+
+/// Fewest samples a phase may span, so single slow intervals are not taken for a step
+pub const MIN_PHASE_SAMPLES: usize = 5;
+
+/// Throughput of one sampling interval
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputSample {
+    /// Time since the test started at the end of the interval
+    pub elapsed: Duration,
+    /// Bytes written since the test started, at the end of the interval
+    pub bytes: u64,
+    /// Throughput during the interval (MB/s)
+    pub speed_mbps: f64,
+}
+
+/// Run of samples with a steady speed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputPhase {
+    /// Time since the test started when the phase began
+    pub start: Duration,
+    /// Time since the test started when the phase ended
+    pub end: Duration,
+    /// Bytes written since the test started when the phase began
+    pub start_bytes: u64,
+    /// Bytes written since the test started when the phase ended
+    pub end_bytes: u64,
+    /// Throughput over the whole phase (MB/s)
+    pub speed_mbps: f64,
+}
+
+impl ThroughputPhase {
+    /// Phase spanning `samples`, which follow `previous` (or the start of the test)
+    fn spanning(previous: Option<&ThroughputSample>, samples: &[ThroughputSample]) -> Self {
+        let (start, start_bytes) = previous.map_or((Duration::ZERO, 0), |s| (s.elapsed, s.bytes));
+        let last = samples.last().copied().unwrap_or(ThroughputSample {
+            elapsed: start,
+            bytes: start_bytes,
+            speed_mbps: 0.0,
+        });
+        Self {
+            start,
+            end: last.elapsed,
+            start_bytes,
+            end_bytes: last.bytes,
+            speed_mbps: StatisticsCollector::calculate_speed_mbps(
+                last.bytes - start_bytes,
+                last.elapsed.saturating_sub(start),
+            ),
+        }
+    }
+
+    /// Bytes written during the phase
+    pub fn bytes(&self) -> u64 {
+        self.end_bytes - self.start_bytes
+    }
+
+    /// Phase from the start of `self` to the end of the last of `phases`
+    fn merged(self, phases: &[ThroughputPhase]) -> Self {
+        let Some(last) = phases.last() else {
+            return self;
+        };
+        Self {
+            end: last.end,
+            end_bytes: last.end_bytes,
+            speed_mbps: StatisticsCollector::calculate_speed_mbps(
+                last.end_bytes - self.start_bytes,
+                last.end.saturating_sub(self.start),
+            ),
+            ..self
+        }
+    }
+}
+
+/// Results from a sustained write test
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SustainedWriteResult {
+    /// Statistics over the whole test
+    pub result: TestResult,
+
+    /// Throughput of every sampling interval, in order
+    pub samples: Vec<ThroughputSample>,
+
+    /// Runs of steady speed; adjacent phases differ by at least [`STEP_CHANGE`]
+    pub phases: Vec<ThroughputPhase>,
+
+    /// Throughput until the first drop, or over the whole test without one (MB/s)
+    pub fast_speed_mbps: f64,
+
+    /// Bytes written before the first drop; `None` if the speed never dropped
+    pub bytes_before_drop: Option<u64>,
+
+    /// Time into the test of the first drop; `None` if the speed never dropped
+    pub time_before_drop: Option<Duration>,
+
+    /// Throughput of the last phase, which the drive settled at (MB/s)
+    pub steady_speed_mbps: f64,
+}
+
+impl SustainedWriteResult {
+    /// Split `samples` into phases and find the first drop in speed
+    pub fn from_samples(result: TestResult, samples: Vec<ThroughputSample>) -> Self {
+        let phases = detect_phases(&samples);
+        let drop = phases
+            .windows(2)
+            .position(|pair| pair[1].speed_mbps < pair[0].speed_mbps);
+        let fast = match drop {
+            Some(index) => phases[0].merged(&phases[..=index]),
+            None => ThroughputPhase::spanning(None, &samples),
+        };
+
+        Self {
+            fast_speed_mbps: fast.speed_mbps,
+            bytes_before_drop: drop.map(|index| phases[index].end_bytes),
+            time_before_drop: drop.map(|index| phases[index].end),
+            steady_speed_mbps: phases.last().map_or(0.0, |phase| phase.speed_mbps),
+            result,
+            samples,
+            phases,
+        }
+    }
+
+    /// Whether the speed dropped by at least [`STEP_CHANGE`] during the test
+    pub fn has_drop(&self) -> bool {
+        self.bytes_before_drop.is_some()
+    }
+}
+
+/// Split samples into phases of steady speed by binary segmentation
+///
+/// Each run of samples is split where two constant speeds fit it best, as long as
+/// both sides span [`MIN_PHASE_SAMPLES`] and their median speeds differ by
+/// [`STEP_CHANGE`]; neighbours that end up closer than that are merged again.
+pub fn detect_phases(samples: &[ThroughputSample]) -> Vec<ThroughputPhase> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    let mut bounds = vec![0, samples.len()];
+    let mut pending = vec![(0, samples.len())];
+    while let Some((start, end)) = pending.pop() {
+        if let Some(split) = best_split(&samples[start..end]) {
+            bounds.push(start + split);
+            pending.push((start, start + split));
+            pending.push((start + split, end));
+        }
+    }
+    bounds.sort_unstable();
+
+    // Splits in separate runs may leave similar neighbours
+    let mut merged = vec![0];
+    for &end in &bounds[1..] {
+        if let [.., before, start] = merged[..] {
+            if !is_step(
+                median_speed(&samples[before..start]),
+                median_speed(&samples[start..end]),
+            ) {
+                merged.pop();
+            }
+        }
+        merged.push(end);
+    }
+
+    merged
+        .windows(2)
+        .map(|pair| {
+            let previous = pair[0].checked_sub(1).map(|index| &samples[index]);
+            ThroughputPhase::spanning(previous, &samples[pair[0]..pair[1]])
+        })
+        .collect()
+}
+
+/// Index splitting `samples` into the two runs with the least squared error
+/// around their means, if they differ by a step
+fn best_split(samples: &[ThroughputSample]) -> Option<usize> {
+    if samples.len() < 2 * MIN_PHASE_SAMPLES {
+        return None;
+    }
+
+    let mut sums = Vec::with_capacity(samples.len() + 1);
+    let mut squares = Vec::with_capacity(samples.len() + 1);
+    let (mut sum, mut square) = (0.0, 0.0);
+    sums.push(sum);
+    squares.push(square);
+    for sample in samples {
+        sum += sample.speed_mbps;
+        square += sample.speed_mbps * sample.speed_mbps;
+        sums.push(sum);
+        squares.push(square);
+    }
+    // Squared error of samples[from..to] around their mean
+    let error = |from: usize, to: usize| {
+        let (sum, count) = (sums[to] - sums[from], (to - from) as f64);
+        squares[to] - squares[from] - sum * sum / count
+    };
+
+    let n = samples.len();
+    let split = (MIN_PHASE_SAMPLES..=n - MIN_PHASE_SAMPLES)
+        .min_by(|&a, &b| (error(0, a) + error(a, n)).total_cmp(&(error(0, b) + error(b, n))))?;
+    is_step(
+        median_speed(&samples[..split]),
+        median_speed(&samples[split..]),
+    )
+    .then_some(split)
+}
+
+/// Median speed of a run, which a few outlying intervals do not move
+fn median_speed(samples: &[ThroughputSample]) -> f64 {
+    let mut speeds: Vec<f64> = samples.iter().map(|sample| sample.speed_mbps).collect();
+    speeds.sort_unstable_by(f64::total_cmp);
+    speeds[speeds.len() / 2]
+}
+
+/// Whether two speeds differ by at least [`STEP_CHANGE`] of the faster one
+fn is_step(a: f64, b: f64) -> bool {
+    (a - b).abs() >= STEP_CHANGE * a.max(b)
+}
+
+/// Records throughput per fixed interval from a running byte total
+#[derive(Debug)]
+pub struct ThroughputTimeline {
+    started: Instant,
+    interval: Duration,
+    /// End of the last recorded interval, since `started`
+    last_elapsed: Duration,
+    /// Byte total at the end of the last recorded interval
+    last_bytes: u64,
+    samples: Vec<ThroughputSample>,
+}
+
+impl ThroughputTimeline {
+    /// Start recording intervals of `interval` from now
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            interval,
+            last_elapsed: Duration::ZERO,
+            last_bytes: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Update the byte total, recording a sample if the interval has elapsed
+    ///
+    /// Returns the speed of the interval that was recorded, if any.
+    pub fn record(&mut self, total_bytes: u64) -> Option<f64> {
+        let elapsed = self.started.elapsed();
+        if elapsed.saturating_sub(self.last_elapsed) < self.interval {
+            return None;
+        }
+        Some(self.push(elapsed, total_bytes))
+    }
+
+    /// Finish recording, keeping the last partial interval if it spans at least
+    /// half an interval
+    pub fn finish(mut self, total_bytes: u64) -> Vec<ThroughputSample> {
+        let elapsed = self.started.elapsed();
+        if elapsed.saturating_sub(self.last_elapsed) >= self.interval / 2
+            && total_bytes > self.last_bytes
+        {
+            self.push(elapsed, total_bytes);
+        }
+        self.samples
+    }
+
+    fn push(&mut self, elapsed: Duration, total_bytes: u64) -> f64 {
+        let speed_mbps = StatisticsCollector::calculate_speed_mbps(
+            total_bytes - self.last_bytes,
+            elapsed - self.last_elapsed,
+        );
+        self.samples.push(ThroughputSample {
+            elapsed,
+            bytes: total_bytes,
+            speed_mbps,
+        });
+        self.last_elapsed = elapsed;
+        self.last_bytes = total_bytes;
+        speed_mbps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    /// One-second samples with the given speeds in MB/s
+    fn samples(speeds: &[f64]) -> Vec<ThroughputSample> {
+        let mut bytes = 0;
+        speeds
+            .iter()
+            .enumerate()
+            .map(|(index, &speed)| {
+                bytes += (speed * MB as f64) as u64;
+                ThroughputSample {
+                    elapsed: Duration::from_secs(index as u64 + 1),
+                    bytes,
+                    speed_mbps: speed,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_steady_speed_is_one_phase() {
+        let speeds = [
+            500.0, 520.0, 480.0, 510.0, 490.0, 505.0, 495.0, 515.0, 485.0, 500.0,
+        ];
+        let result = SustainedWriteResult::from_samples(TestResult::default(), samples(&speeds));
+
+        assert_eq!(result.phases.len(), 1);
+        assert!(!result.has_drop());
+        assert_eq!(result.time_before_drop, None);
+        assert!((result.fast_speed_mbps - 500.0).abs() < 1.0);
+        assert_eq!(result.fast_speed_mbps, result.steady_speed_mbps);
+    }
+
+    #[test]
+    fn test_cache_exhaustion_is_detected() {
+        let mut speeds = vec![2000.0; 8];
+        speeds.extend([400.0, 420.0, 380.0, 410.0, 390.0, 400.0]);
+        let result = SustainedWriteResult::from_samples(TestResult::default(), samples(&speeds));
+
+        assert_eq!(result.phases.len(), 2);
+        assert!(result.has_drop());
+        assert_eq!(result.bytes_before_drop, Some(8 * 2000 * MB));
+        assert_eq!(result.time_before_drop, Some(Duration::from_secs(8)));
+        assert!((result.fast_speed_mbps - 2000.0).abs() < 1.0);
+        assert!((result.steady_speed_mbps - 400.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_cache_exhaustion_then_throttling() {
+        let mut speeds = vec![3000.0; 6];
+        speeds.extend([1000.0; 6]);
+        speeds.extend([300.0; 6]);
+        let result = SustainedWriteResult::from_samples(TestResult::default(), samples(&speeds));
+
+        let phase_speeds: Vec<f64> = result.phases.iter().map(|p| p.speed_mbps.round()).collect();
+        assert_eq!(phase_speeds, [3000.0, 1000.0, 300.0]);
+        assert_eq!(result.time_before_drop, Some(Duration::from_secs(6)));
+        assert!((result.steady_speed_mbps - 300.0).abs() < 1.0);
+        assert_eq!(result.phases[1].bytes(), 6 * 1000 * MB);
+    }
+
+    #[test]
+    fn test_short_dips_are_not_phases() {
+        let mut speeds = vec![800.0; 12];
+        speeds[5] = 100.0;
+        speeds[6] = 120.0;
+        let result = SustainedWriteResult::from_samples(TestResult::default(), samples(&speeds));
+
+        assert_eq!(result.phases.len(), 1);
+        assert!(!result.has_drop());
+    }
+
+    #[test]
+    fn test_timeline_records_intervals() {
+        let mut timeline = ThroughputTimeline::new(Duration::from_millis(100));
+        assert_eq!(timeline.record(MB), None);
+        std::thread::sleep(Duration::from_millis(110));
+        assert!(timeline.record(2 * MB).is_some());
+        std::thread::sleep(Duration::from_millis(60));
+
+        let samples = timeline.finish(3 * MB);
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].bytes, 2 * MB);
+        assert_eq!(samples[1].bytes, 3 * MB);
+        assert!(samples[1].elapsed > samples[0].elapsed);
+    }
+}
@@ -1,486 +1,2190 @@
 //! Test implementations for different benchmark types
 
-use crate::core::{BenchmarkConfig, BenchmarkError, ProgressCallback, TestResult};
-use std::fs::{File, OpenOptions};
+use crate::core::buffer::AlignedBuffer;
+use crate::core::engine::{IoEngine, IoHandle, IoOpenOptions};
+use crate::core::memory::{
+    working_set_sizes, MemoryBandwidthPoint, MemoryStreamResult, StreamBarrier, StreamKernel,
+    MIN_TIMED_COPY,
+};
+use crate::core::metadata::{FileTree, MetadataOperation, MetadataResult};
+use crate::core::small_files::{SmallFilePlan, SmallFilesResult};
+use crate::core::sustained::{SustainedWriteResult, ThroughputTimeline};
+use crate::core::{
+    report_warning, BenchmarkConfig, BenchmarkError, CancellationToken, IoBackend, LatencyRecorder,
+    MixedTestResult, ProgressCallback, ProgressUpdate, StatisticsCollector, StopReason, SyncMethod,
+    TestDataVerifier, TestLimit, TestPhase, TestResult, ThreadPinning,
+};
+use crate::platform::DirectIoAlignment;
 use std::path::Path;
 
+/// Test duration set by the fast-test override env var, if any
+fn fast_test_duration() -> Option<std::time::Duration> {
+    let ms = std::env::var("DISK_SPEED_TEST_FAST_TEST_MS")
+        .ok()?
+        .parse::<u64>()
+        .ok()?;
+    (ms > 0).then(|| std::time::Duration::from_millis(ms))
+}
+
 /// Determine effective test duration, honoring fast-test override via env var
 fn effective_test_duration(config: &BenchmarkConfig) -> std::time::Duration {
-    if let Ok(ms_str) = std::env::var("DISK_SPEED_TEST_FAST_TEST_MS") {
-        if let Ok(ms) = ms_str.parse::<u64>() {
-            if ms > 0 {
-                return std::time::Duration::from_millis(ms);
-            }
-        }
+    fast_test_duration()
+        .unwrap_or_else(|| std::time::Duration::from_secs(config.test_duration_seconds))
+}
+
+/// Tell the progress callback a test moved on to `phase`
+fn report_phase(
+    progress_callback: Option<&dyn ProgressCallback>,
+    test_name: &str,
+    phase: TestPhase,
+) {
+    if let Some(callback) = progress_callback {
+        callback.on_phase_change(test_name, phase);
     }
-    std::time::Duration::from_secs(config.test_duration_seconds)
 }
 
-/// Create a file for I/O operations, choosing between direct I/O and buffered I/O based on config
-fn create_io_file(
-    config: &BenchmarkConfig,
-    path: &Path, 
-    size: u64
-) -> Result<File, BenchmarkError> {
-    if config.disable_direct_io {
-        // Use standard buffered I/O
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent).map_err(BenchmarkError::IoError)?;
+/// Progress reporting of one test, deriving its completion and ETA from its stop rule
+struct TestProgress<'a> {
+    test_name: &'a str,
+    callback: Option<&'a dyn ProgressCallback>,
+    /// Start of the test, before any warm-up
+    started: std::time::Instant,
+    rule: StopRule,
+    /// Phase the test is in once the warm-up is over
+    measured_phase: TestPhase,
+    /// Whether `measured_phase` has been reported
+    measuring: bool,
+    /// Whether any progress update has been reported
+    reported: bool,
+}
+
+impl<'a> TestProgress<'a> {
+    /// Start reporting a test limited by `config`, announcing its warm-up if it has one
+    ///
+    /// Tests issue I/O for the warm-up followed by their full measured duration.
+    fn start(
+        config: &BenchmarkConfig,
+        test_name: &'a str,
+        callback: Option<&'a dyn ProgressCallback>,
+    ) -> Self {
+        let warmup = std::time::Duration::from_secs(config.warmup_seconds);
+        let started = config.cancellation.now();
+        if !warmup.is_zero() {
+            if let Some(callback) = callback {
+                callback.on_warmup(test_name, warmup);
+            }
+            report_phase(callback, test_name, TestPhase::Warmup);
         }
-        
-        let file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(path)
-            .map_err(BenchmarkError::IoError)?;
-            
-        // Pre-allocate the file size for consistency with direct I/O path
-        file.set_len(size).map_err(BenchmarkError::IoError)?;
-        Ok(file)
-    } else {
-        // Use direct I/O
-        crate::platform::create_direct_io_file(path, size)
-            .map_err(|e| {
-                // If direct I/O fails, provide a helpful error message
-                match e {
-                    crate::platform::PlatformError::DirectIoNotSupported => {
-                        BenchmarkError::PlatformError(e)
-                    }
-                    _ => BenchmarkError::PlatformError(e)
-                }
-            })
+
+        let mut progress = Self {
+            test_name,
+            callback,
+            started,
+            rule: StopRule::new(config, started + warmup),
+            measured_phase: TestPhase::Measure,
+            measuring: false,
+            reported: false,
+        };
+        progress.poll_phase();
+        progress
     }
-}
 
-/// Open a file for I/O operations, choosing between direct I/O and buffered I/O based on config
-fn open_io_file(
-    config: &BenchmarkConfig,
-    path: &Path,
-    write: bool
-) -> Result<File, BenchmarkError> {
-    if config.disable_direct_io {
-        // Use standard buffered I/O
-        let mut options = OpenOptions::new();
-        if write {
-            options.write(true);
-        } else {
-            options.read(true);
+    /// Start reporting the preparation, which writes `file_size` bytes once
+    fn preparation(
+        config: &BenchmarkConfig,
+        file_size: u64,
+        callback: Option<&'a dyn ProgressCallback>,
+    ) -> Self {
+        let started = config.cancellation.now();
+        let mut progress = Self {
+            test_name: "Preparation",
+            callback,
+            started,
+            rule: StopRule::single_pass(config, started, file_size),
+            measured_phase: TestPhase::Prepare,
+            measuring: false,
+            reported: false,
+        };
+        progress.poll_phase();
+        progress
+    }
+
+    /// Start reporting a test that measures for `duration` straight away
+    fn timed(
+        config: &BenchmarkConfig,
+        test_name: &'a str,
+        duration: std::time::Duration,
+        callback: Option<&'a dyn ProgressCallback>,
+    ) -> Self {
+        let started = config.cancellation.now();
+        let mut progress = Self {
+            test_name,
+            callback,
+            started,
+            rule: StopRule::timed(config, started, duration),
+            measured_phase: TestPhase::Measure,
+            measuring: false,
+            reported: false,
+        };
+        progress.poll_phase();
+        progress
+    }
+
+    /// Start reporting a test that measures `operations` operations straight away
+    fn counted(
+        config: &BenchmarkConfig,
+        test_name: &'a str,
+        operations: u64,
+        callback: Option<&'a dyn ProgressCallback>,
+    ) -> Self {
+        let started = config.cancellation.now();
+        let mut progress = Self {
+            test_name,
+            callback,
+            started,
+            rule: StopRule::counted(config, started, operations),
+            measured_phase: TestPhase::Measure,
+            measuring: false,
+            reported: false,
+        };
+        progress.poll_phase();
+        progress
+    }
+
+    /// Length of the warm-up preceding the measurement
+    fn warmup(&self) -> std::time::Duration {
+        self.rule.measure_from.duration_since(self.started)
+    }
+
+    /// Report the measured phase once the warm-up is over
+    fn poll_phase(&mut self) {
+        if !self.measuring && self.rule.now() >= self.rule.measure_from {
+            self.measuring = true;
+            report_phase(self.callback, self.test_name, self.measured_phase);
         }
-        options.open(path).map_err(BenchmarkError::IoError)
-    } else {
-        // Use direct I/O
-        crate::platform::open_direct_io_file(path, write)
-            .map_err(|e| {
-                // If direct I/O fails, provide a helpful error message
-                match e {
-                    crate::platform::PlatformError::DirectIoNotSupported => {
-                        BenchmarkError::PlatformError(e)
-                    }
-                    _ => BenchmarkError::PlatformError(e)
-                }
-            })
     }
-}
 
-/// Get the appropriate block size, aligned for direct I/O if needed
-fn get_block_size(config: &BenchmarkConfig, requested_size: usize) -> usize {
-    if config.disable_direct_io {
-        // For buffered I/O, use the requested size as-is
-        requested_size
-    } else {
-        // For direct I/O, ensure size is aligned
-        crate::platform::align_block_size_for_direct_io(requested_size)
+    /// Report the speed of the latest sample along with the measured totals
+    fn report(&mut self, current_speed_mbps: f64, bytes: u64, operations: u64) {
+        self.poll_phase();
+        let Some(callback) = self.callback else {
+            return;
+        };
+
+        let now = self.rule.now();
+        let measured = now.saturating_duration_since(self.rule.measure_from);
+        let fraction = self.rule.fraction(bytes, operations, now);
+        callback.on_progress_update(&ProgressUpdate {
+            test_name: self.test_name.to_string(),
+            phase: if self.measuring {
+                self.measured_phase
+            } else {
+                TestPhase::Warmup
+            },
+            elapsed: now.duration_since(self.started),
+            total: self.rule.time_limit.map(|limit| self.warmup() + limit),
+            fraction,
+            eta: self.rule.eta(fraction, now),
+            bytes,
+            operations,
+            current_speed_mbps,
+            average_speed_mbps: StatisticsCollector::calculate_speed_mbps(bytes, measured),
+            iops: if measured.is_zero() {
+                0.0
+            } else {
+                operations as f64 / measured.as_secs_f64()
+            },
+        });
+        self.reported = true;
+    }
+
+    /// Report the final result if the test was too short to report any progress
+    fn finish(&mut self, result: &TestResult) {
+        if !self.reported {
+            self.report(
+                result.avg_speed_mbps,
+                result.total_bytes,
+                result.total_operations,
+            );
+        }
     }
 }
 
-/// Sequential write test implementation
-pub fn run_sequential_write_test(
-    config: &BenchmarkConfig,
-    test_file_path: &Path,
-    progress_callback: Option<&dyn ProgressCallback>,
-) -> Result<TestResult, BenchmarkError> {
-    use crate::core::RealTimeStatsTracker;
-    use std::io::Write;
-    use std::time::Instant;
+/// Decides when a test stops, from `config.test_limit` and the time cap
+///
+/// Time counts from the end of the warm-up on the clock of `config.cancellation`,
+/// which stands still while the run is paused, and the byte and operation totals
+/// passed in must cover the measured phase only.
+#[derive(Debug, Clone)]
+struct StopRule {
+    /// End of the warm-up, when measurement starts
+    measure_from: std::time::Instant,
+    /// Longest the measured phase may run, if bounded
+    time_limit: Option<std::time::Duration>,
+    /// Reason recorded when the time limit ends the test
+    time_reason: StopReason,
+    byte_limit: Option<u64>,
+    /// Reason recorded when the byte limit ends the test
+    byte_reason: StopReason,
+    operation_limit: Option<u64>,
+    /// Clock the rule is timed on
+    clock: CancellationToken,
+}
 
-    // Report test start
-    if let Some(callback) = progress_callback {
-        callback.on_test_start("Sequential Write");
+impl StopRule {
+    fn new(config: &BenchmarkConfig, measure_from: std::time::Instant) -> Self {
+        let mut rule = Self {
+            measure_from,
+            time_limit: config.time_cap_seconds.map(std::time::Duration::from_secs),
+            time_reason: StopReason::TimeCap,
+            byte_limit: None,
+            byte_reason: StopReason::ByteLimit,
+            operation_limit: None,
+            clock: config.cancellation.clone(),
+        };
+        match config.test_limit {
+            TestLimit::Duration => {
+                rule.time_limit = Some(effective_test_duration(config));
+                rule.time_reason = StopReason::Duration;
+            }
+            TestLimit::Bytes(bytes) => rule.byte_limit = Some(bytes),
+            TestLimit::Operations(operations) => rule.operation_limit = Some(operations),
+            TestLimit::Passes(passes) => {
+                rule.byte_limit = Some(passes.saturating_mul(config.file_size_bytes()));
+                rule.byte_reason = StopReason::PassLimit;
+            }
+        }
+        rule
     }
 
-    // We'll handle progress reporting directly since we have a reference
+    /// Count passes over `pass_bytes` instead of the test file
+    fn with_pass_bytes(mut self, config: &BenchmarkConfig, pass_bytes: u64) -> Self {
+        if let TestLimit::Passes(passes) = config.test_limit {
+            self.byte_limit = Some(passes.saturating_mul(pass_bytes));
+        }
+        self
+    }
 
-    // Create the test file (direct I/O or buffered I/O based on config)
-    let mut file = create_io_file(config, test_file_path, config.file_size_bytes())?;
+    /// Rule for one of `parts` parts of a test, measured from `measure_from`
+    ///
+    /// The duration and the time cap are split evenly across the parts, so the test
+    /// as a whole keeps to them. Byte and operation limits apply to each part, and
+    /// passes are made over each part's own `pass_bytes`.
+    fn share(
+        config: &BenchmarkConfig,
+        measure_from: std::time::Instant,
+        parts: usize,
+        pass_bytes: u64,
+    ) -> Self {
+        let rule = Self::new(config, measure_from).with_pass_bytes(config, pass_bytes);
+        Self {
+            time_limit: rule.time_limit.map(|limit| limit / parts.max(1) as u32),
+            ..rule
+        }
+    }
 
-    // Create buffer with the configured block size (aligned for direct I/O if needed)
-    let block_size = get_block_size(config, config.sequential_block_size);
-    let mut buffer = vec![0u8; block_size];
+    /// Rule covering all `parts` parts of a test limited by [`StopRule::share`]
+    ///
+    /// `pass_bytes` is the bytes of one pass over every part.
+    fn whole(
+        config: &BenchmarkConfig,
+        measure_from: std::time::Instant,
+        parts: usize,
+        pass_bytes: u64,
+    ) -> Self {
+        let rule = Self::new(config, measure_from).with_pass_bytes(config, pass_bytes);
+        let multiply = |limit: u64| limit.saturating_mul(parts as u64);
+        Self {
+            byte_limit: match config.test_limit {
+                TestLimit::Passes(_) => rule.byte_limit,
+                _ => rule.byte_limit.map(multiply),
+            },
+            operation_limit: rule.operation_limit.map(multiply),
+            ..rule
+        }
+    }
 
-    // Fill buffer with test pattern (alternating bytes to avoid compression)
-    for (i, byte) in buffer.iter_mut().enumerate() {
-        *byte = (i % 256) as u8;
+    /// Rule for transferring `bytes` exactly once, measured from `measure_from`
+    fn single_pass(config: &BenchmarkConfig, measure_from: std::time::Instant, bytes: u64) -> Self {
+        Self {
+            measure_from,
+            time_limit: None,
+            time_reason: StopReason::PassLimit,
+            byte_limit: Some(bytes),
+            byte_reason: StopReason::PassLimit,
+            operation_limit: None,
+            clock: config.cancellation.clone(),
+        }
     }
 
-    // Initialize statistics tracking
-    let mut stats_tracker = RealTimeStatsTracker::new();
-    let test_start = Instant::now();
-    let test_duration = effective_test_duration(config);
-    let mut emitted_progress = false;
+    /// Rule for measuring from `measure_from` until `limit` has elapsed
+    fn timed(
+        config: &BenchmarkConfig,
+        measure_from: std::time::Instant,
+        limit: std::time::Duration,
+    ) -> Self {
+        Self {
+            measure_from,
+            time_limit: Some(limit),
+            time_reason: StopReason::Duration,
+            byte_limit: None,
+            byte_reason: StopReason::ByteLimit,
+            operation_limit: None,
+            clock: config.cancellation.clone(),
+        }
+    }
 
-    // Track bytes written for wrap-around logic
-    let mut bytes_written: u64 = 0;
-    let file_size = config.file_size_bytes();
+    /// Rule for performing `operations` operations, measured from `measure_from`
+    fn counted(
+        config: &BenchmarkConfig,
+        measure_from: std::time::Instant,
+        operations: u64,
+    ) -> Self {
+        Self {
+            measure_from,
+            time_limit: None,
+            time_reason: StopReason::OperationLimit,
+            byte_limit: None,
+            byte_reason: StopReason::ByteLimit,
+            operation_limit: Some(operations),
+            clock: config.cancellation.clone(),
+        }
+    }
 
-    // Main write loop - continue until test duration elapsed or file is full
-    while test_start.elapsed() < test_duration && bytes_written < file_size {
-        let write_start = Instant::now();
+    /// Current time on the clock the rule is timed on
+    fn now(&self) -> std::time::Instant {
+        self.clock.now()
+    }
 
-        // Calculate how much to write this iteration
-        let remaining_file_space = file_size - bytes_written;
-        let bytes_to_write = std::cmp::min(block_size as u64, remaining_file_space) as usize;
+    /// Time at which the time limit ends the test, if bounded
+    fn deadline(&self) -> Option<std::time::Instant> {
+        self.time_limit.map(|limit| self.measure_from + limit)
+    }
 
-        // Write the block
-        let bytes_written_this_iteration = file.write(&buffer[..bytes_to_write])?;
-        bytes_written += bytes_written_this_iteration as u64;
+    /// Share of the nearest limit reached at `now`, from 0 to 1
+    fn fraction(&self, bytes: u64, operations: u64, now: std::time::Instant) -> f64 {
+        if now < self.measure_from {
+            return 0.0;
+        }
+        let share =
+            |done: u64, limit: Option<u64>| limit.map_or(0.0, |limit| done as f64 / limit as f64);
+        let time = self.time_limit.map_or(0.0, |limit| {
+            now.duration_since(self.measure_from).as_secs_f64() / limit.as_secs_f64()
+        });
+        share(bytes, self.byte_limit)
+            .max(share(operations, self.operation_limit))
+            .max(time)
+            .min(1.0)
+    }
 
-        // Record per-block speed and report progress periodically
-        let elapsed = write_start.elapsed();
-        if let Some(current_speed) =
-            stats_tracker.record_block(bytes_written_this_iteration, elapsed)
-        {
-            if let Some(callback) = progress_callback {
-                callback.on_progress("Sequential Write", current_speed);
-                emitted_progress = true;
-            }
+    /// Estimated time left at `now`, extrapolating the rate at which `fraction` was reached
+    ///
+    /// Until the measurement has made progress only a time limit gives an estimate.
+    fn eta(&self, fraction: f64, now: std::time::Instant) -> Option<std::time::Duration> {
+        let until_deadline = self
+            .deadline()
+            .map(|deadline| deadline.saturating_duration_since(now));
+        if now < self.measure_from || fraction <= 0.0 {
+            return until_deadline;
         }
 
-        // If we couldn't write the full block, we're likely at EOF
-        if bytes_written_this_iteration < bytes_to_write {
-            break;
+        let measured = now.duration_since(self.measure_from).as_secs_f64();
+        let estimate =
+            std::time::Duration::try_from_secs_f64(measured * (1.0 - fraction) / fraction).ok();
+        match (estimate, until_deadline) {
+            (Some(estimate), Some(until_deadline)) => Some(estimate.min(until_deadline)),
+            (estimate, until_deadline) => estimate.or(until_deadline),
         }
+    }
 
-        // If we've filled the file, seek back to beginning to continue writing
-        if bytes_written >= file_size {
-            use std::io::Seek;
-            file.seek(std::io::SeekFrom::Start(0))?;
-            bytes_written = 0; // Reset counter but keep stats tracking total throughput
+    /// Reason to stop instead of issuing another request, if any
+    ///
+    /// `bytes` and `operations` are the measured totals so far; nothing stops the
+    /// test during the warm-up.
+    fn check(&self, bytes: u64, operations: u64) -> Option<StopReason> {
+        let now = self.now();
+        if now < self.measure_from {
+            return None;
+        }
+        if self.byte_limit.is_some_and(|limit| bytes >= limit) {
+            return Some(self.byte_reason);
+        }
+        if self
+            .operation_limit
+            .is_some_and(|limit| operations >= limit)
+        {
+            return Some(StopReason::OperationLimit);
         }
+        self.deadline()
+            .filter(|deadline| now >= *deadline)
+            .map(|_| self.time_reason)
     }
+}
 
-    // Ensure data is written to disk
-    file.flush()?;
-    if config.disable_os_cache {
-        crate::platform::sync_file_system(test_file_path)?;
+/// Underlying file of a handle, required by the io_uring backend
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+fn uring_file(handle: &dyn IoHandle) -> Result<&std::fs::File, BenchmarkError> {
+    handle.as_file().ok_or_else(|| {
+        BenchmarkError::ConfigurationError(
+            "The io_uring backend needs an I/O engine that exposes its file descriptor".to_string(),
+        )
+    })
+}
+
+/// Alignment the test file needs for buffers and offsets
+///
+/// Buffered I/O has no requirements; for direct I/O the platform is asked for the
+/// device's real limits, falling back to a conservative default.
+fn io_alignment(config: &BenchmarkConfig, test_file_path: &Path) -> DirectIoAlignment {
+    if config.disable_direct_io {
+        DirectIoAlignment::NONE
+    } else {
+        crate::platform::direct_io_alignment(test_file_path).unwrap_or_default()
     }
+}
 
-    // Finalize statistics
-    let result = stats_tracker.finalize();
+/// Size and alignment of the blocks a test transfers
+#[derive(Debug, Clone, Copy)]
+struct IoBlock {
+    /// Block size, a multiple of the offset alignment
+    size: usize,
+    /// Required alignment of buffer addresses
+    memory_alignment: usize,
+}
 
-    // Fallback: ensure at least one progress emission for very short tests
-    if !emitted_progress {
-        if let Some(callback) = progress_callback {
-            callback.on_progress("Sequential Write", result.avg_speed_mbps);
+impl IoBlock {
+    /// Round the requested block size up to the alignment direct I/O needs
+    fn new(requested_size: usize, alignment: DirectIoAlignment) -> Self {
+        Self {
+            size: crate::platform::align_block_size_for_direct_io(requested_size, alignment),
+            memory_alignment: alignment.memory,
         }
     }
 
-    // Report test completion
-    if let Some(callback) = progress_callback {
-        callback.on_test_complete("Sequential Write", &result);
+    /// Allocate a zeroed buffer for one block
+    fn buffer(&self) -> AlignedBuffer {
+        AlignedBuffer::new(self.size, self.memory_alignment)
     }
+}
 
-    Ok(result)
+/// Data verification of one test: the verifier and the generation its writes carry
+#[derive(Debug, Clone, Copy)]
+struct Verification<'a> {
+    verifier: &'a TestDataVerifier,
+    generation: u64,
 }
 
-/// Sequential read test implementation
-pub fn run_sequential_read_test(
-    config: &BenchmarkConfig,
-    test_file_path: &Path,
-    progress_callback: Option<&dyn ProgressCallback>,
-) -> Result<TestResult, BenchmarkError> {
-    use crate::core::RealTimeStatsTracker;
-    use std::io::Read;
-    use std::time::Instant;
+impl<'a> Verification<'a> {
+    /// Start a new generation for a write test; `recreated` if it just created the test file
+    fn writes(verifier: Option<&'a TestDataVerifier>, recreated: bool) -> Option<Self> {
+        verifier.map(|verifier| {
+            if recreated {
+                verifier.reset();
+            }
+            Self {
+                verifier,
+                generation: verifier.begin_generation(),
+            }
+        })
+    }
 
-    // Report test start
-    if let Some(callback) = progress_callback {
-        callback.on_test_start("Sequential Read");
+    /// Check what a read test reads against the generations written so far
+    fn reads(verifier: Option<&'a TestDataVerifier>) -> Option<Self> {
+        verifier.map(|verifier| Self {
+            verifier,
+            generation: 0,
+        })
     }
 
-    // Open the test file (direct I/O or buffered I/O based on config)
-    let mut file = open_io_file(config, test_file_path, false)?;
+    /// Seal a block about to be written at `offset`
+    fn seal(&self, buffer: &mut [u8], offset: u64) {
+        TestDataVerifier::seal(buffer, offset, self.generation);
+    }
 
-    // Create buffer with the configured block size (aligned for direct I/O if needed)
-    let block_size = get_block_size(config, config.sequential_block_size);
-    let mut buffer = vec![0u8; block_size];
+    /// Record a completed write of `len` bytes at `offset`
+    fn wrote(&self, offset: u64, len: usize) {
+        self.verifier.record_write(offset, len, self.generation);
+    }
 
-    // Initialize statistics tracking
-    let mut stats_tracker = RealTimeStatsTracker::new();
-    let test_start = Instant::now();
-    let test_duration = effective_test_duration(config);
-    let mut emitted_progress = false;
+    /// Check a completed read from `offset`
+    fn check(&self, data: &[u8], offset: u64) {
+        self.verifier.check(data, offset);
+    }
+}
 
-    // Track bytes read only for wrap-around checks
-    let mut bytes_read: u64 = 0;
-    let file_size = config.file_size_bytes();
+/// Attach the corruption a read test found to its result, warning about it
+fn attach_corruption(
+    verification: Option<Verification>,
+    result: TestResult,
+    test_name: &str,
+    progress_callback: Option<&dyn ProgressCallback>,
+) -> TestResult {
+    let Some(verification) = verification else {
+        return result;
+    };
+    let (corrupted_sectors, events) = verification.verifier.take_corruption();
+    if corrupted_sectors > 0 {
+        let first = events
+            .first()
+            .map(|event| format!(", first at offset {} ({})", event.offset, event.kind))
+            .unwrap_or_default();
+        report_warning(
+            progress_callback,
+            &format!(
+                "{}: {} corrupted sectors{}",
+                test_name, corrupted_sectors, first
+            ),
+        );
+    }
+    result.with_corruption(corrupted_sectors, events)
+}
 
-    // Main read loop - continue until test duration elapsed or we've read enough data
-    while test_start.elapsed() < test_duration {
-        let read_start = Instant::now();
+/// Sequential pass over the test file on an io_uring queue, wrapping around until time runs out
+///
+/// Completions may arrive out of order, so throughput is sampled per interval from the
+/// running byte total instead of per block. Writes flush the file before the results
+/// are finalized, counting the writeback as the sync backend does.
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+fn run_sequential_uring(
+    config: &BenchmarkConfig,
+    handle: &dyn IoHandle,
+    test_file_path: &Path,
+    op: crate::core::uring::UringOp,
+    test_name: &str,
+    progress_callback: Option<&dyn ProgressCallback>,
+    verification: Option<Verification>,
+) -> Result<TestResult, BenchmarkError> {
+    use crate::core::uring::{UringOp, UringQueue};
+    use crate::core::RealTimeStatsTracker;
 
-        // Read the block
-        match file.read(&mut buffer) {
-            Ok(bytes_read_this_iteration) => {
-                // If we read 0 bytes, we've reached EOF
-                if bytes_read_this_iteration == 0 {
-                    // Seek back to beginning to continue reading
-                    use std::io::Seek;
-                    file.seek(std::io::SeekFrom::Start(0))?;
-                    continue;
-                }
+    let file = uring_file(handle)?;
+    let block = IoBlock::new(
+        config.sequential_block_size,
+        io_alignment(config, test_file_path),
+    );
+    let block_size = block.size;
+    let file_size = config.file_size_bytes();
 
-                bytes_read += bytes_read_this_iteration as u64;
+    let pattern = config.data_pattern;
+    let mut queue = UringQueue::new(config.queue_depth, block_size, block.memory_alignment)?;
+    if op == UringOp::Write {
+        queue.fill_buffers(|buffer| pattern.fill(buffer));
+    }
 
-                // Record per-block speed and report progress periodically
-                let elapsed = read_start.elapsed();
-                if let Some(current_speed) =
-                    stats_tracker.record_block(bytes_read_this_iteration, elapsed)
-                {
-                    if let Some(callback) = progress_callback {
-                        callback.on_progress("Sequential Read", current_speed);
-                        emitted_progress = true;
-                    }
+    // Initialize statistics tracking, discarding the warm-up
+    let mut progress = TestProgress::start(config, test_name, progress_callback);
+    let mut stats_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_warmup(progress.warmup())
+        .with_time_series(config.record_time_series);
+    let stop_rule = progress.rule.clone();
+    let mut stop_reason = stop_rule.time_reason;
+
+    let mut next_offset: u64 = 0;
+    let mut total_bytes: u64 = 0;
+    let mut sequence: u64 = 0;
+    // Requests submitted after the warm-up, counted against the test limit
+    let mut measured_bytes: u64 = 0;
+    let mut measured_operations: u64 = 0;
+
+    queue.run(
+        file,
+        |buffer| {
+            if let Some(reason) = stop_rule.check(measured_bytes, measured_operations) {
+                stop_reason = reason;
+                return None;
+            }
+
+            // Wrap around to the beginning once the whole file has been covered
+            if next_offset >= file_size {
+                next_offset = 0;
+            }
+            let len = std::cmp::min(block_size as u64, file_size - next_offset);
+            if stop_rule.now() >= stop_rule.measure_from {
+                measured_bytes += len;
+                measured_operations += 1;
+            }
+            let offset = next_offset;
+            next_offset += len;
+            if op == UringOp::Write {
+                pattern.stamp(&mut buffer[..len as usize], offset, sequence);
+                sequence += 1;
+                if let Some(verification) = &verification {
+                    verification.seal(&mut buffer[..len as usize], offset);
                 }
             }
-            Err(e) => {
-                // Handle read errors
-                return Err(BenchmarkError::IoError(e));
+            Some((op, offset, len as usize))
+        },
+        || config.cancellation.check(test_name).is_ok(),
+        |op, offset, data, latency| {
+            if let Some(verification) = &verification {
+                match op {
+                    UringOp::Write => verification.wrote(offset, data.len()),
+                    UringOp::Read => verification.check(data, offset),
+                }
             }
-        }
-
-        // If we've read a full file's worth of data, reset counter but keep stats tracking total throughput
-        if bytes_read >= file_size {
-            use std::io::Seek;
-            file.seek(std::io::SeekFrom::Start(0))?;
-            bytes_read = 0; // Reset counter but keep stats tracking total throughput
+            total_bytes += data.len() as u64;
+            stats_tracker.record_latency(latency);
+            if let Some(current_speed) = stats_tracker.update_progress(total_bytes) {
+                let (bytes, operations) = stats_tracker.measured_totals();
+                progress.report(current_speed, bytes, operations);
+            }
+        },
+    )?;
+    config.cancellation.check(test_name)?;
+
+    // Account for I/O completed since the last sampling interval
+    stats_tracker.flush_progress(total_bytes);
+
+    // Ensure written data is on disk, counting the writeback in the test's wall time
+    if op == UringOp::Write {
+        report_phase(progress_callback, test_name, TestPhase::Flush);
+        handle.flush()?;
+        if config.disable_os_cache {
+            crate::platform::sync_file_system(test_file_path)?;
         }
     }
 
     // Finalize statistics
-    let result = stats_tracker.finalize();
+    let result = stats_tracker.finalize().with_stop_reason(stop_reason);
 
     // Fallback: ensure at least one progress emission for very short tests
-    if !emitted_progress {
-        if let Some(callback) = progress_callback {
-            callback.on_progress("Sequential Read", result.avg_speed_mbps);
+    progress.finish(&result);
+
+    Ok(result)
+}
+
+/// Preparation phase: write the whole test file once with real data
+///
+/// A freshly created file is sparse, and reading its holes never touches the media.
+/// This writes every block sequentially regardless of the test duration, so read
+/// tests measure allocated extents. It reports progress like a test, under the
+/// name "Preparation".
+pub fn run_preparation(
+    config: &BenchmarkConfig,
+    engine: &dyn IoEngine,
+    test_file_path: &Path,
+    progress_callback: Option<&dyn ProgressCallback>,
+    verifier: Option<&TestDataVerifier>,
+) -> Result<TestResult, BenchmarkError> {
+    use crate::core::RealTimeStatsTracker;
+    use std::time::Instant;
+
+    // Report phase start
+    if let Some(callback) = progress_callback {
+        callback.on_test_start("Preparation");
+    }
+    let verification = Verification::writes(verifier, true);
+
+    // Create the test file (direct I/O or buffered I/O based on config)
+    let handle = engine.open(
+        test_file_path,
+        &IoOpenOptions::create(config.file_size_bytes(), !config.disable_direct_io),
+    )?;
+
+    // Large sequential blocks, aligned for direct I/O if needed
+    let block = IoBlock::new(
+        config.sequential_block_size,
+        io_alignment(config, test_file_path),
+    );
+    let mut buffer = block.buffer();
+    config.data_pattern.fill(&mut buffer);
+
+    // Initialize statistics tracking
+    let file_size = config.file_size_bytes();
+    let mut progress = TestProgress::preparation(config, file_size, progress_callback);
+    let mut stats_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_time_series(config.record_time_series);
+
+    // Write every block exactly once, however long it takes
+    let mut offset: u64 = 0;
+    let mut sequence: u64 = 0;
+    while offset < file_size {
+        config.cancellation.check("Preparation")?;
+        let write_start = Instant::now();
+
+        let bytes_to_write = std::cmp::min(block.size as u64, file_size - offset) as usize;
+        config
+            .data_pattern
+            .stamp(&mut buffer[..bytes_to_write], offset, sequence);
+        sequence += 1;
+        if let Some(verification) = &verification {
+            verification.seal(&mut buffer[..bytes_to_write], offset);
+        }
+        let bytes_written = handle.write_at(&buffer[..bytes_to_write], offset)?;
+        if bytes_written == 0 {
+            return Err(BenchmarkError::IoError(std::io::Error::new(
+                std::io::ErrorKind::WriteZero,
+                format!("Failed to populate test file at offset {}", offset),
+            )));
+        }
+        if let Some(verification) = &verification {
+            verification.wrote(offset, bytes_written);
+        }
+        offset += bytes_written as u64;
+
+        // Record per-block speed and report progress periodically
+        if let Some(current_speed) =
+            stats_tracker.record_block(bytes_written, write_start.elapsed())
+        {
+            let (bytes, operations) = stats_tracker.measured_totals();
+            progress.report(current_speed, bytes, operations);
         }
     }
 
-    // Report test completion
+    // Ensure data is on disk before the read tests start
+    report_phase(progress_callback, "Preparation", TestPhase::Flush);
+    handle.flush()?;
+    if config.disable_os_cache {
+        crate::platform::sync_file_system(test_file_path)?;
+    }
+
+    // Finalize statistics; the file is always written exactly once
+    let result = stats_tracker
+        .finalize()
+        .with_stop_reason(StopReason::PassLimit);
+
+    // Fallback: ensure at least one progress emission for very short runs
+    progress.finish(&result);
+
+    // Report phase completion
     if let Some(callback) = progress_callback {
-        callback.on_test_complete("Sequential Read", &result);
+        callback.on_test_complete("Preparation", &result);
     }
 
     Ok(result)
 }
 
-/// Random write test implementation
-pub fn run_random_write_test(
+/// Sequential write test implementation
+pub fn run_sequential_write_test(
     config: &BenchmarkConfig,
+    engine: &dyn IoEngine,
     test_file_path: &Path,
     progress_callback: Option<&dyn ProgressCallback>,
+    verifier: Option<&TestDataVerifier>,
 ) -> Result<TestResult, BenchmarkError> {
     use crate::core::RealTimeStatsTracker;
-    use rand::Rng;
-    use std::io::{Seek, SeekFrom, Write};
     use std::time::Instant;
 
     // Report test start
     if let Some(callback) = progress_callback {
-        callback.on_test_start("Random Write");
+        callback.on_test_start("Sequential Write");
     }
 
-    // Open the test file (direct I/O or buffered I/O based on config)
-    let mut file = open_io_file(config, test_file_path, true)?;
+    // We'll handle progress reporting directly since we have a reference
 
-    // Create buffer with the configured random block size (aligned for direct I/O if needed)
-    let block_size = get_block_size(config, config.random_block_size);
-    let mut buffer = vec![0u8; block_size];
+    // Create the test file (direct I/O or buffered I/O based on config)
+    let handle = engine.open(
+        test_file_path,
+        &IoOpenOptions::create(config.file_size_bytes(), !config.disable_direct_io),
+    )?;
+    let verification = Verification::writes(verifier, true);
+
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    if config.io_backend == IoBackend::IoUring {
+        let result = run_sequential_uring(
+            config,
+            handle.as_ref(),
+            test_file_path,
+            crate::core::uring::UringOp::Write,
+            "Sequential Write",
+            progress_callback,
+            verification,
+        )?;
+
+        // Report test completion
+        if let Some(callback) = progress_callback {
+            callback.on_test_complete("Sequential Write", &result);
+        }
 
-    // Fill buffer with test pattern (alternating bytes to avoid compression)
-    for (i, byte) in buffer.iter_mut().enumerate() {
-        *byte = (i % 256) as u8;
+        return Ok(result);
     }
 
-    // Initialize statistics tracking
-    let mut stats_tracker = RealTimeStatsTracker::new();
-    let test_start = Instant::now();
-    let test_duration = effective_test_duration(config);
-    let mut emitted_progress = false;
-
-    let mut _bytes_written = 0u64;
+    // Block size and buffers aligned for direct I/O if needed
+    let block = IoBlock::new(
+        config.sequential_block_size,
+        io_alignment(config, test_file_path),
+    );
+
+    // Create buffer with the configured block size
+    let block_size = block.size;
+    let mut buffer = block.buffer();
+    config.data_pattern.fill(&mut buffer);
+
+    // Initialize statistics tracking, discarding the warm-up
+    let mut progress = TestProgress::start(config, "Sequential Write", progress_callback);
+    let mut stats_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_warmup(progress.warmup())
+        .with_time_series(config.record_time_series);
+    let stop_rule = progress.rule.clone();
+    let mut stop_reason = stop_rule.time_reason;
+
+    // Track the write position for wrap-around logic
+    let mut offset: u64 = 0;
+    let mut sequence: u64 = 0;
     let file_size = config.file_size_bytes();
 
-    // Calculate the number of possible block positions in the file
-    let max_blocks = (file_size / block_size as u64).max(1);
-    let mut rng = rand::thread_rng();
-
-    // Main random write loop - continue until test duration elapsed
-    while test_start.elapsed() < test_duration {
-        // Generate random block position within the file
-        let random_block = rng.gen_range(0..max_blocks);
-        let seek_position = random_block * block_size as u64;
-
-        // Seek to random position
-        file.seek(SeekFrom::Start(seek_position))?;
+    // Main write loop - continue until the test limit is reached or the file is full
+    while offset < file_size {
+        config.cancellation.check("Sequential Write")?;
+        let (bytes, operations) = stats_tracker.measured_totals();
+        if let Some(reason) = stop_rule.check(bytes, operations) {
+            stop_reason = reason;
+            break;
+        }
+        progress.poll_phase();
+        let write_start = Instant::now();
 
-        // Calculate how much to write (handle case where we're near end of file)
-        let remaining_file_space = file_size.saturating_sub(seek_position);
+        // Calculate how much to write this iteration
+        let remaining_file_space = file_size - offset;
         let bytes_to_write = std::cmp::min(block_size as u64, remaining_file_space) as usize;
 
-        if bytes_to_write == 0 {
-            continue; // Skip if we somehow ended up past the file end
+        // Write the block, stamped first if the data pattern requires it
+        config
+            .data_pattern
+            .stamp(&mut buffer[..bytes_to_write], offset, sequence);
+        sequence += 1;
+        if let Some(verification) = &verification {
+            verification.seal(&mut buffer[..bytes_to_write], offset);
         }
-
-        // Write the block
-        let write_start = Instant::now();
-        let bytes_written_this_iteration = file.write(&buffer[..bytes_to_write])?;
-        _bytes_written += bytes_written_this_iteration as u64;
+        let bytes_written_this_iteration = handle.write_at(&buffer[..bytes_to_write], offset)?;
+        if let Some(verification) = &verification {
+            verification.wrote(offset, bytes_written_this_iteration);
+        }
+        offset += bytes_written_this_iteration as u64;
 
         // Record per-block speed and report progress periodically
         let elapsed = write_start.elapsed();
         if let Some(current_speed) =
             stats_tracker.record_block(bytes_written_this_iteration, elapsed)
         {
-            if let Some(callback) = progress_callback {
-                callback.on_progress("Random Write", current_speed);
-                emitted_progress = true;
-            }
+            let (bytes, operations) = stats_tracker.measured_totals();
+            progress.report(current_speed, bytes, operations);
         }
 
-        // If we couldn't write the full block, there might be an issue
+        // If we couldn't write the full block, we're likely at EOF
         if bytes_written_this_iteration < bytes_to_write {
-            // This shouldn't happen with random writes, but handle gracefully
-            continue;
+            break;
+        }
+
+        // If we've filled the file, go back to the beginning to continue writing
+        if offset >= file_size {
+            offset = 0; // Reset position but keep stats tracking total throughput
         }
     }
 
     // Ensure data is written to disk
-    file.flush()?;
+    report_phase(progress_callback, "Sequential Write", TestPhase::Flush);
+    handle.flush()?;
     if config.disable_os_cache {
         crate::platform::sync_file_system(test_file_path)?;
     }
 
     // Finalize statistics
-    let result = stats_tracker.finalize();
+    let result = stats_tracker.finalize().with_stop_reason(stop_reason);
 
     // Fallback: ensure at least one progress emission for very short tests
-    if !emitted_progress {
-        if let Some(callback) = progress_callback {
-            callback.on_progress("Random Write", result.avg_speed_mbps);
-        }
-    }
+    progress.finish(&result);
 
     // Report test completion
     if let Some(callback) = progress_callback {
-        callback.on_test_complete("Random Write", &result);
+        callback.on_test_complete("Sequential Write", &result);
     }
 
     Ok(result)
 }
 
-/// Random read test implementation
-pub fn run_random_read_test(
+/// Sustained write test implementation
+///
+/// Writes the test file sequentially, wrapping around, for `sustained_write_seconds`
+/// and samples the throughput at fixed intervals, so that a drop once the drive's
+/// write cache is full or it throttles shows up in the result. There is no warm-up,
+/// and the test always runs on the sync backend.
+pub fn run_sustained_write_test(
     config: &BenchmarkConfig,
+    engine: &dyn IoEngine,
     test_file_path: &Path,
     progress_callback: Option<&dyn ProgressCallback>,
-) -> Result<TestResult, BenchmarkError> {
+    verifier: Option<&TestDataVerifier>,
+) -> Result<SustainedWriteResult, BenchmarkError> {
     use crate::core::RealTimeStatsTracker;
-    use rand::Rng;
-    use std::io::{Read, Seek, SeekFrom};
-    use std::time::Instant;
+    use std::time::{Duration, Instant};
 
-    // Report test start
     if let Some(callback) = progress_callback {
-        callback.on_test_start("Random Read");
+        callback.on_test_start("Sustained Write");
     }
 
-    // Open the test file (direct I/O or buffered I/O based on config)
-    let mut file = open_io_file(config, test_file_path, false)?;
+    let handle = engine.open(
+        test_file_path,
+        &IoOpenOptions::create(config.file_size_bytes(), !config.disable_direct_io),
+    )?;
+    let verification = Verification::writes(verifier, true);
+
+    let block = IoBlock::new(
+        config.sequential_block_size,
+        io_alignment(config, test_file_path),
+    );
+    let block_size = block.size;
+    let mut buffer = block.buffer();
+    config.data_pattern.fill(&mut buffer);
+
+    let duration =
+        fast_test_duration().unwrap_or_else(|| Duration::from_secs(config.sustained_write_seconds));
+    let mut progress = TestProgress::timed(config, "Sustained Write", duration, progress_callback);
+    let mut stats_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_time_series(config.record_time_series);
+    // About 100 samples for short tests, one per second for long ones
+    let mut timeline = ThroughputTimeline::new(
+        (duration / 100).clamp(Duration::from_millis(10), Duration::from_secs(1)),
+    );
+    let stop_rule = progress.rule.clone();
+    let mut stop_reason = stop_rule.time_reason;
+
+    let mut offset: u64 = 0;
+    let mut sequence: u64 = 0;
+    let mut written: u64 = 0;
+    let file_size = config.file_size_bytes();
 
-    // Create buffer with the configured random block size (aligned for direct I/O if needed)
-    let block_size = get_block_size(config, config.random_block_size);
-    let mut buffer = vec![0u8; block_size];
+    loop {
+        config.cancellation.check("Sustained Write")?;
+        let (bytes, operations) = stats_tracker.measured_totals();
+        if let Some(reason) = stop_rule.check(bytes, operations) {
+            stop_reason = reason;
+            break;
+        }
+        let write_start = Instant::now();
 
-    // Initialize statistics tracking
-    let mut stats_tracker = RealTimeStatsTracker::new();
-    let test_start = Instant::now();
-    let test_duration = effective_test_duration(config);
-    let mut emitted_progress = false;
+        let bytes_to_write = std::cmp::min(block_size as u64, file_size - offset) as usize;
+        config
+            .data_pattern
+            .stamp(&mut buffer[..bytes_to_write], offset, sequence);
+        sequence += 1;
+        if let Some(verification) = &verification {
+            verification.seal(&mut buffer[..bytes_to_write], offset);
+        }
+        let bytes_written = handle.write_at(&buffer[..bytes_to_write], offset)?;
+        if let Some(verification) = &verification {
+            verification.wrote(offset, bytes_written);
+        }
+        offset += bytes_written as u64;
+        written += bytes_written as u64;
+
+        // Progress follows the sampled intervals rather than single blocks
+        stats_tracker.record_block(bytes_written, write_start.elapsed());
+        if let Some(interval_speed) = timeline.record(written) {
+            let (bytes, operations) = stats_tracker.measured_totals();
+            progress.report(interval_speed, bytes, operations);
+        }
 
-    let _bytes_read = 0u64;
-    let file_size = config.file_size_bytes();
+        // If we couldn't write the full block, we're likely at EOF
+        if bytes_written < bytes_to_write {
+            break;
+        }
+        if offset >= file_size {
+            offset = 0;
+        }
+    }
+    let samples = timeline.finish(written);
 
-    // Calculate the number of possible block positions in the file
-    let max_blocks = (file_size / block_size as u64).max(1);
-    let mut rng = rand::thread_rng();
+    report_phase(progress_callback, "Sustained Write", TestPhase::Flush);
+    handle.flush()?;
+    if config.disable_os_cache {
+        crate::platform::sync_file_system(test_file_path)?;
+    }
 
-    // Main random read loop - continue until test duration elapsed
-    while test_start.elapsed() < test_duration {
-        // Generate random block position within the file
-        let random_block = rng.gen_range(0..max_blocks);
-        let seek_position = random_block * block_size as u64;
+    let result = stats_tracker.finalize().with_stop_reason(stop_reason);
+    progress.finish(&result);
+    if let Some(callback) = progress_callback {
+        callback.on_test_complete("Sustained Write", &result);
+    }
 
-        // Seek to random position
-        file.seek(SeekFrom::Start(seek_position))?;
+    Ok(SustainedWriteResult::from_samples(result, samples))
+}
 
-        // Calculate how much to read (handle case where we're near end of file)
-        let remaining_file_space = file_size.saturating_sub(seek_position);
-        let bytes_to_read = std::cmp::min(block_size as u64, remaining_file_space) as usize;
+/// Records in the log the sync write test overwrites, unless it appends
+pub const SYNC_WRITE_LOG_RECORDS: u64 = 1024;
+
+/// Open the sync write test's file, write-through for [`SyncMethod::Dsync`]
+///
+/// Buffered I/O is used whatever `disable_direct_io` says, as the flush is what
+/// makes each record durable.
+fn open_sync_write_file(path: &Path, method: SyncMethod) -> Result<std::fs::File, BenchmarkError> {
+    let mut options = std::fs::OpenOptions::new();
+    options.create(true).write(true).truncate(true);
+    if method == SyncMethod::Dsync {
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.custom_flags(libc::O_DSYNC);
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::OpenOptionsExt;
+            options.custom_flags(winapi::um::winbase::FILE_FLAG_WRITE_THROUGH);
+        }
+    }
+    Ok(options.open(path)?)
+}
 
-        if bytes_to_read == 0 {
-            continue; // Skip if we somehow ended up past the file end
+/// Write all of `record` at `offset`
+fn write_record(file: &std::fs::File, record: &[u8], offset: u64) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileExt;
+        file.write_all_at(record, offset)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::FileExt;
+        let mut written = 0;
+        while written < record.len() {
+            match file.seek_write(&record[written..], offset + written as u64)? {
+                0 => return Err(std::io::ErrorKind::WriteZero.into()),
+                n => written += n,
+            }
         }
+        Ok(())
+    }
+}
 
-        // Read the block
+/// Make the records written so far durable with `method`
+fn sync_records(file: &std::fs::File, method: SyncMethod) -> std::io::Result<()> {
+    match method {
+        SyncMethod::Fsync => file.sync_all(),
+        SyncMethod::Fdatasync => file.sync_data(),
+        // Each write was durable when it returned
+        SyncMethod::Dsync => Ok(()),
+    }
+}
+
+/// Sync write test implementation
+///
+/// Commits records of `sync_write_size` bytes one at a time, each made durable
+/// with `sync_method` before the next is written, as a database commits its log.
+/// Records are appended to a new file, or overwrite a log of
+/// [`SYNC_WRITE_LOG_RECORDS`] records that is written once before measuring.
+/// An operation is one commit: the result's IOPS are commits per second and its
+/// latency is the commit latency, write and flush together.
+pub fn run_sync_write_test(
+    config: &BenchmarkConfig,
+    sync_file_path: &Path,
+    progress_callback: Option<&dyn ProgressCallback>,
+) -> Result<TestResult, BenchmarkError> {
+    use crate::core::RealTimeStatsTracker;
+    use std::time::Instant;
+
+    if let Some(callback) = progress_callback {
+        callback.on_test_start("Sync Write");
+    }
+
+    let file = open_sync_write_file(sync_file_path, config.sync_method)?;
+    let record_size = config.sync_write_size;
+    let mut record = vec![0u8; record_size];
+    config.data_pattern.fill(&mut record);
+
+    // Overwrites land on allocated blocks, so only the data needs flushing
+    if !config.sync_write_append {
+        report_phase(progress_callback, "Sync Write", TestPhase::Prepare);
+        for index in 0..SYNC_WRITE_LOG_RECORDS {
+            config.cancellation.check("Sync Write")?;
+            write_record(&file, &record, index * record_size as u64)?;
+        }
+        file.sync_all()?;
+    }
+
+    let mut progress = TestProgress::start(config, "Sync Write", progress_callback);
+    let mut stats_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_warmup(progress.warmup())
+        .with_time_series(config.record_time_series);
+    let stop_rule = progress.rule.clone();
+    let stop_reason;
+
+    let mut sequence: u64 = 0;
+    loop {
+        config.cancellation.check("Sync Write")?;
+        let (bytes, operations) = stats_tracker.measured_totals();
+        if let Some(reason) = stop_rule.check(bytes, operations) {
+            stop_reason = reason;
+            break;
+        }
+        progress.poll_phase();
+
+        let slot = if config.sync_write_append {
+            sequence
+        } else {
+            sequence % SYNC_WRITE_LOG_RECORDS
+        };
+        let offset = slot * record_size as u64;
+        config.data_pattern.stamp(&mut record, offset, sequence);
+        sequence += 1;
+
+        let commit_start = Instant::now();
+        write_record(&file, &record, offset)?;
+        sync_records(&file, config.sync_method)?;
+        if let Some(current_speed) = stats_tracker.record_block(record_size, commit_start.elapsed())
+        {
+            let (bytes, operations) = stats_tracker.measured_totals();
+            progress.report(current_speed, bytes, operations);
+        }
+    }
+
+    let result = stats_tracker.finalize().with_stop_reason(stop_reason);
+    progress.finish(&result);
+    if let Some(callback) = progress_callback {
+        callback.on_test_complete("Sync Write", &result);
+    }
+
+    Ok(result)
+}
+
+/// Metadata test implementation
+///
+/// Creates `metadata_file_count` empty files spread across `metadata_fanout`
+/// directories under `metadata_dir`, then stats, renames, lists and deletes them,
+/// timing each operation type on its own. It ignores `test_limit`, as the file
+/// count sets the work. The emptied directories are left for the caller to remove
+/// along with anything a failed or cancelled run left behind.
+pub fn run_metadata_test(
+    config: &BenchmarkConfig,
+    metadata_dir: &Path,
+    progress_callback: Option<&dyn ProgressCallback>,
+) -> Result<MetadataResult, BenchmarkError> {
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    if let Some(callback) = progress_callback {
+        callback.on_test_start("Metadata");
+    }
+
+    let tree = FileTree::new(metadata_dir, config.metadata_fanout);
+    report_phase(progress_callback, "Metadata", TestPhase::Prepare);
+    tree.create_directories()?;
+
+    let file_count = config.metadata_file_count;
+    let directory_count = tree.directory_count();
+    let count = |operation: MetadataOperation| match operation {
+        MetadataOperation::List => directory_count,
+        _ => file_count,
+    };
+    let total_operations = MetadataOperation::ALL
+        .into_iter()
+        .map(|operation| count(operation) as u64)
+        .sum();
+    let mut progress =
+        TestProgress::counted(config, "Metadata", total_operations, progress_callback);
+    let mut completed: u64 = 0;
+    let mut last_report = Instant::now();
+
+    let mut all_operations = LatencyRecorder::new();
+    let mut all_duration = Duration::ZERO;
+    let mut results = MetadataResult {
+        file_count: file_count as u64,
+        directory_count: directory_count as u64,
+        ..Default::default()
+    };
+
+    for operation in MetadataOperation::ALL {
+        let mut latencies = LatencyRecorder::new();
+        let operation_start = config.cancellation.now();
+        for index in 0..count(operation) {
+            config.cancellation.check("Metadata")?;
+            let start = Instant::now();
+            match operation {
+                MetadataOperation::Create => {
+                    fs::OpenOptions::new()
+                        .write(true)
+                        .create_new(true)
+                        .open(tree.file(index))?;
+                }
+                MetadataOperation::Stat => {
+                    fs::metadata(tree.file(index))?;
+                }
+                MetadataOperation::Rename => fs::rename(tree.file(index), tree.renamed(index))?,
+                MetadataOperation::List => {
+                    for entry in fs::read_dir(tree.directory(index))? {
+                        entry?;
+                    }
+                }
+                MetadataOperation::Delete => fs::remove_file(tree.renamed(index))?,
+            }
+            latencies.record(start.elapsed());
+            completed += 1;
+
+            if last_report.elapsed() >= Duration::from_millis(100) {
+                last_report = Instant::now();
+                progress.report(0.0, 0, completed);
+            }
+        }
+
+        let elapsed = config
+            .cancellation
+            .now()
+            .saturating_duration_since(operation_start);
+        all_operations.merge(&latencies);
+        all_duration += elapsed;
+        let result = TestResult::new(0.0, 0.0, 0.0, elapsed, 0)
+            .with_totals(0, latencies.operations())
+            .with_latency(latencies.finalize(elapsed))
+            .with_stop_reason(StopReason::OperationLimit);
+        match operation {
+            MetadataOperation::Create => results.create = result,
+            MetadataOperation::Stat => results.stat = result,
+            MetadataOperation::Rename => results.rename = result,
+            MetadataOperation::List => results.list = result,
+            MetadataOperation::Delete => results.delete = result,
+        }
+    }
+
+    // All operation types together, for the callback
+    let result = TestResult::new(0.0, 0.0, 0.0, all_duration, 0)
+        .with_totals(0, all_operations.operations())
+        .with_latency(all_operations.finalize(all_duration))
+        .with_stop_reason(StopReason::OperationLimit);
+    progress.finish(&result);
+    if let Some(callback) = progress_callback {
+        callback.on_test_complete("Metadata", &result);
+    }
+
+    Ok(results)
+}
+
+/// Small-file test implementation
+///
+/// Writes `small_file_count` files with sizes drawn from `small_file_sizes` into a
+/// tree under `tree_dir`, then reads them back in shuffled order. Writes use
+/// buffered I/O like a file copy and are flushed to disk at the end unless
+/// `disable_os_cache` is off; reads open each file through `engine`, with direct
+/// I/O unless it is disabled. Each file is one operation, timed from opening to
+/// closing it, and the two directions are reported as "Small File Write" and
+/// "Small File Read". It ignores `test_limit` and the I/O backend. The tree is left
+/// for the caller to remove.
+pub fn run_small_files_test(
+    config: &BenchmarkConfig,
+    engine: &dyn IoEngine,
+    tree_dir: &Path,
+    progress_callback: Option<&dyn ProgressCallback>,
+) -> Result<SmallFilesResult, BenchmarkError> {
+    use crate::core::RealTimeStatsTracker;
+    use std::io::Write;
+    use std::time::Instant;
+
+    if let Some(callback) = progress_callback {
+        callback.on_test_start("Small File Write");
+    }
+
+    let plan = SmallFilePlan::new(&config.small_file_sizes, config.small_file_count);
+    let tree = FileTree::new(tree_dir, plan.directory_count());
+    report_phase(progress_callback, "Small File Write", TestPhase::Prepare);
+    tree.create_directories()?;
+    let file_count = plan.sizes.len() as u64;
+
+    let mut progress =
+        TestProgress::counted(config, "Small File Write", file_count, progress_callback);
+    let mut stats_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_time_series(config.record_time_series);
+    let mut buffer = vec![0u8; plan.largest()];
+    config.data_pattern.fill(&mut buffer);
+    let mut offset: u64 = 0;
+
+    for (index, &size) in plan.sizes.iter().enumerate() {
+        config.cancellation.check("Small File Write")?;
+        let data = &mut buffer[..size];
+        config.data_pattern.stamp(data, offset, index as u64);
+        offset += size as u64;
+
+        let write_start = Instant::now();
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(tree.file(index))?;
+        file.write_all(data)?;
+        drop(file);
+        if let Some(current_speed) = stats_tracker.record_block(size, write_start.elapsed()) {
+            let (bytes, operations) = stats_tracker.measured_totals();
+            progress.report(current_speed, bytes, operations);
+        }
+    }
+
+    // The files sit in the OS cache until flushed, so the flush counts towards the writes
+    if config.disable_os_cache {
+        report_phase(progress_callback, "Small File Write", TestPhase::Flush);
+        if let Err(e) = crate::platform::sync_file_system(tree_dir) {
+            report_warning(
+                progress_callback,
+                &format!("Failed to flush the small files: {}", e),
+            );
+        }
+    }
+
+    let write = stats_tracker
+        .finalize()
+        .with_stop_reason(StopReason::PassLimit);
+    progress.finish(&write);
+    if let Some(callback) = progress_callback {
+        callback.on_test_complete("Small File Write", &write);
+        callback.on_test_start("Small File Read");
+    }
+
+    let block = IoBlock::new(plan.largest(), io_alignment(config, &tree.file(0)));
+    let mut buffer = block.buffer();
+    let open_options = IoOpenOptions::read(!config.disable_direct_io);
+    let mut open_latency = LatencyRecorder::new();
+    let mut progress =
+        TestProgress::counted(config, "Small File Read", file_count, progress_callback);
+    let mut stats_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_time_series(config.record_time_series);
+
+    for &index in &plan.read_order {
+        config.cancellation.check("Small File Read")?;
+        let read_start = Instant::now();
+        let handle = engine.open(&tree.file(index), &open_options)?;
+        open_latency.record(read_start.elapsed());
+
+        let size = plan.sizes[index];
+        let mut read = 0;
+        while read < size {
+            let bytes_read = handle.read_at(&mut buffer[read..], read as u64)?;
+            if bytes_read == 0 {
+                break;
+            }
+            read += bytes_read;
+        }
+        drop(handle);
+        if let Some(current_speed) = stats_tracker.record_block(read, read_start.elapsed()) {
+            let (bytes, operations) = stats_tracker.measured_totals();
+            progress.report(current_speed, bytes, operations);
+        }
+    }
+
+    let read = stats_tracker
+        .finalize()
+        .with_stop_reason(StopReason::PassLimit);
+    progress.finish(&read);
+    if let Some(callback) = progress_callback {
+        callback.on_test_complete("Small File Read", &read);
+    }
+
+    Ok(SmallFilesResult {
+        file_count,
+        directory_count: tree.directory_count() as u64,
+        open_latency: open_latency.finalize(read.test_duration),
+        write,
+        read,
+    })
+}
+
+/// Sequential read test implementation
+pub fn run_sequential_read_test(
+    config: &BenchmarkConfig,
+    engine: &dyn IoEngine,
+    test_file_path: &Path,
+    progress_callback: Option<&dyn ProgressCallback>,
+    verifier: Option<&TestDataVerifier>,
+) -> Result<TestResult, BenchmarkError> {
+    use crate::core::RealTimeStatsTracker;
+    use std::time::Instant;
+
+    // Report test start
+    if let Some(callback) = progress_callback {
+        callback.on_test_start("Sequential Read");
+    }
+    let verification = Verification::reads(verifier);
+
+    // Open the test file (direct I/O or buffered I/O based on config)
+    let handle = engine.open(
+        test_file_path,
+        &IoOpenOptions::read(!config.disable_direct_io),
+    )?;
+
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    if config.io_backend == IoBackend::IoUring {
+        let result = run_sequential_uring(
+            config,
+            handle.as_ref(),
+            test_file_path,
+            crate::core::uring::UringOp::Read,
+            "Sequential Read",
+            progress_callback,
+            verification,
+        )?;
+        let result = attach_corruption(verification, result, "Sequential Read", progress_callback);
+
+        // Report test completion
+        if let Some(callback) = progress_callback {
+            callback.on_test_complete("Sequential Read", &result);
+        }
+
+        return Ok(result);
+    }
+
+    // Block size and buffers aligned for direct I/O if needed
+    let block = IoBlock::new(
+        config.sequential_block_size,
+        io_alignment(config, test_file_path),
+    );
+
+    // Create buffer with the configured block size
+    let mut buffer = block.buffer();
+
+    // Initialize statistics tracking, discarding the warm-up
+    let mut progress = TestProgress::start(config, "Sequential Read", progress_callback);
+    let mut stats_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_warmup(progress.warmup())
+        .with_time_series(config.record_time_series);
+    let stop_rule = progress.rule.clone();
+    let stop_reason;
+
+    // Track the read position only for wrap-around checks
+    let mut offset: u64 = 0;
+    let file_size = config.file_size_bytes();
+
+    // Main read loop - continue until the test limit is reached
+    loop {
+        config.cancellation.check("Sequential Read")?;
+        let (bytes, operations) = stats_tracker.measured_totals();
+        if let Some(reason) = stop_rule.check(bytes, operations) {
+            stop_reason = reason;
+            break;
+        }
+        progress.poll_phase();
         let read_start = Instant::now();
-        match file.read(&mut buffer[..bytes_to_read]) {
+
+        // Read the block
+        match handle.read_at(&mut buffer, offset) {
             Ok(bytes_read_this_iteration) => {
-                // If we read 0 bytes unexpectedly, there might be an issue
+                // If we read 0 bytes, we've reached EOF
                 if bytes_read_this_iteration == 0 {
-                    continue; // Skip this iteration
+                    // Go back to the beginning to continue reading
+                    offset = 0;
+                    continue;
                 }
 
-                // Record per-block speed and report progress periodically
+                // Record the block's time before checking its contents
                 let elapsed = read_start.elapsed();
+                if let Some(verification) = &verification {
+                    verification.check(&buffer[..bytes_read_this_iteration], offset);
+                }
+                offset += bytes_read_this_iteration as u64;
+
+                // Record per-block speed and report progress periodically
                 if let Some(current_speed) =
                     stats_tracker.record_block(bytes_read_this_iteration, elapsed)
                 {
-                    if let Some(callback) = progress_callback {
-                        callback.on_progress("Random Read", current_speed);
-                        emitted_progress = true;
-                    }
+                    let (bytes, operations) = stats_tracker.measured_totals();
+                    progress.report(current_speed, bytes, operations);
                 }
             }
             Err(e) => {
-                // Handle read errors gracefully - continue with next random position
-                eprintln!("Warning: Read error at position {}: {}", seek_position, e);
-                continue;
+                // Handle read errors
+                return Err(BenchmarkError::IoError(e));
             }
         }
+
+        // If we've read a full file's worth of data, reset position but keep stats tracking total throughput
+        if offset >= file_size {
+            offset = 0;
+        }
     }
 
     // Finalize statistics
-    let result = stats_tracker.finalize();
+    let result = stats_tracker.finalize().with_stop_reason(stop_reason);
+    let result = attach_corruption(verification, result, "Sequential Read", progress_callback);
 
     // Fallback: ensure at least one progress emission for very short tests
-    if !emitted_progress {
-        if let Some(callback) = progress_callback {
-            callback.on_progress("Random Read", result.avg_speed_mbps);
+    progress.finish(&result);
+
+    // Report test completion
+    if let Some(callback) = progress_callback {
+        callback.on_test_complete("Sequential Read", &result);
+    }
+
+    Ok(result)
+}
+
+/// Request mix of a random I/O test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RandomIoKind {
+    Read,
+    Write,
+    /// Reads and writes interleaved, `read_percentage` percent of requests being reads
+    Mixed {
+        read_percentage: u8,
+    },
+}
+
+impl RandomIoKind {
+    /// Pick the direction of the next request
+    fn next_is_read(self, rng: &mut impl rand::Rng) -> bool {
+        match self {
+            RandomIoKind::Read => true,
+            RandomIoKind::Write => false,
+            RandomIoKind::Mixed { read_percentage } => rng.gen_range(0..100) < read_percentage,
+        }
+    }
+
+    /// Whether the test writes to the file, and so has to flush it before finishing
+    fn writes(self) -> bool {
+        self != RandomIoKind::Read
+    }
+}
+
+/// Bytes completed by the workers of a random test, split by direction
+///
+/// Requests and their summed latency are counted as well, for the time series.
+#[derive(Default)]
+struct RandomIoCounters {
+    read: std::sync::atomic::AtomicU64,
+    written: std::sync::atomic::AtomicU64,
+    reads: std::sync::atomic::AtomicU64,
+    writes: std::sync::atomic::AtomicU64,
+    read_latency_nanos: std::sync::atomic::AtomicU64,
+    write_latency_nanos: std::sync::atomic::AtomicU64,
+}
+
+impl RandomIoCounters {
+    /// Count a finished request
+    fn add(&self, is_read: bool, bytes: usize, latency: std::time::Duration) {
+        use std::sync::atomic::Ordering;
+
+        let (counter, requests, latency_nanos) = if is_read {
+            (&self.read, &self.reads, &self.read_latency_nanos)
+        } else {
+            (&self.written, &self.writes, &self.write_latency_nanos)
+        };
+        counter.fetch_add(bytes as u64, Ordering::Relaxed);
+        requests.fetch_add(1, Ordering::Relaxed);
+        latency_nanos.fetch_add(
+            u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Current `(read, written)` totals
+    fn load(&self) -> (u64, u64) {
+        use std::sync::atomic::Ordering;
+
+        (
+            self.read.load(Ordering::Relaxed),
+            self.written.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Current request count and summed latency of the reads and of the writes
+    fn load_requests(&self) -> [(u64, std::time::Duration); 2] {
+        use std::sync::atomic::Ordering;
+
+        let load = |requests: &std::sync::atomic::AtomicU64,
+                    latency_nanos: &std::sync::atomic::AtomicU64| {
+            (
+                requests.load(Ordering::Relaxed),
+                std::time::Duration::from_nanos(latency_nanos.load(Ordering::Relaxed)),
+            )
+        };
+        [
+            load(&self.reads, &self.read_latency_nanos),
+            load(&self.writes, &self.write_latency_nanos),
+        ]
+    }
+
+    /// Report the requests completed so far to the trackers of a random test
+    fn update_trackers(
+        &self,
+        read_tracker: &mut crate::core::RealTimeStatsTracker,
+        write_tracker: &mut crate::core::RealTimeStatsTracker,
+        combined_tracker: &mut crate::core::RealTimeStatsTracker,
+    ) {
+        let [(reads, read_latency), (writes, write_latency)] = self.load_requests();
+        read_tracker.update_operations(reads, read_latency);
+        write_tracker.update_operations(writes, write_latency);
+        combined_tracker.update_operations(reads + writes, read_latency + write_latency);
+    }
+}
+
+/// Per-operation latencies recorded by the workers of one thread, split by direction
+#[derive(Default)]
+struct RandomIoLatencies {
+    read: LatencyRecorder,
+    write: LatencyRecorder,
+}
+
+impl RandomIoLatencies {
+    /// Record a finished request
+    fn record(&mut self, is_read: bool, latency: std::time::Duration) {
+        let recorder = if is_read {
+            &mut self.read
+        } else {
+            &mut self.write
+        };
+        recorder.record(latency);
+    }
+}
+
+/// Latencies a worker has yet to add to the histograms of its thread
+///
+/// Blocking I/O emulates a thread's queue depth with one worker per request in
+/// flight. Those workers share the thread's histograms rather than each keeping
+/// their own, and take the lock once per batch rather than once per request.
+struct LatencyBatch<'a> {
+    histograms: &'a std::sync::Mutex<RandomIoLatencies>,
+    pending: Vec<(bool, std::time::Duration)>,
+}
+
+impl<'a> LatencyBatch<'a> {
+    /// Requests recorded between two flushes
+    const SIZE: usize = 256;
+
+    fn new(histograms: &'a std::sync::Mutex<RandomIoLatencies>) -> Self {
+        Self {
+            histograms,
+            pending: Vec::with_capacity(Self::SIZE),
+        }
+    }
+
+    /// Record a finished request
+    fn record(&mut self, is_read: bool, latency: std::time::Duration) {
+        self.pending.push((is_read, latency));
+        if self.pending.len() >= Self::SIZE {
+            self.flush();
+        }
+    }
+
+    /// Add the pending latencies to the thread's histograms
+    fn flush(&mut self) {
+        let mut histograms = self
+            .histograms
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        for (is_read, latency) in self.pending.drain(..) {
+            histograms.record(is_read, latency);
+        }
+    }
+}
+
+impl Drop for LatencyBatch<'_> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// Requests the workers of a random test may still issue, shared between them
+///
+/// Requests are claimed before they are issued, so byte and operation limits hold
+/// across workers. Warm-up requests are not counted.
+struct RandomIoBudget {
+    rule: StopRule,
+    bytes: std::sync::atomic::AtomicU64,
+    operations: std::sync::atomic::AtomicU64,
+    /// Limit that refused the first claim
+    reason: std::sync::OnceLock<StopReason>,
+}
+
+impl RandomIoBudget {
+    fn new(rule: StopRule) -> Self {
+        Self {
+            rule,
+            bytes: Default::default(),
+            operations: Default::default(),
+            reason: Default::default(),
         }
     }
 
+    /// End of the warm-up; latencies of requests started earlier are not recorded
+    fn measure_from(&self) -> std::time::Instant {
+        self.rule.measure_from
+    }
+
+    /// Whether the warm-up is over, so requests started now are measured
+    fn measuring(&self) -> bool {
+        self.rule.now() >= self.rule.measure_from
+    }
+
+    /// Claim a request of `bytes`, returning `false` once the test must stop
+    fn claim(&self, bytes: usize) -> bool {
+        use std::sync::atomic::Ordering;
+
+        if !self.measuring() {
+            return true;
+        }
+        let operations = self.operations.fetch_add(1, Ordering::Relaxed);
+        let claimed = self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        match self.rule.check(claimed, operations) {
+            Some(reason) => {
+                let _ = self.reason.set(reason);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Requests claimed after the warm-up
+    fn operations(&self) -> u64 {
+        self.operations.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// What ended the test, once every worker has stopped
+    fn stop_reason(&self) -> StopReason {
+        self.reason.get().copied().unwrap_or(self.rule.time_reason)
+    }
+}
+
+/// State the workers of a random test share with the thread sampling them
+struct RandomIoShared<'a> {
+    budget: RandomIoBudget,
+    /// Set to stop the workers early, e.g. after one of them failed
+    stop: std::sync::atomic::AtomicBool,
+    counters: RandomIoCounters,
+    test_name: &'a str,
+    /// Receives warnings about failed requests the workers skip
+    progress_callback: Option<&'a dyn ProgressCallback>,
+    verification: Option<Verification<'a>>,
+}
+
+impl RandomIoShared<'_> {
+    /// Whether the workers should keep issuing requests, waiting while the run is paused
+    fn running(&self, config: &BenchmarkConfig) -> bool {
+        !self.stop.load(std::sync::atomic::Ordering::Relaxed)
+            && config.cancellation.check(self.test_name).is_ok()
+    }
+
+    /// Seal a block about to be written at `offset`, if verifying
+    fn seal(&self, buffer: &mut [u8], offset: u64) {
+        if let Some(verification) = &self.verification {
+            verification.seal(buffer, offset);
+        }
+    }
+
+    /// Record a completed write or check a completed read, if verifying
+    ///
+    /// Reads of the mixed test are not checked: they race with the test's own
+    /// writes to the same blocks, so their contents are not known in advance.
+    fn completed(&self, kind: RandomIoKind, is_read: bool, data: &[u8], offset: u64) {
+        let Some(verification) = &self.verification else {
+            return;
+        };
+        if !is_read {
+            verification.wrote(offset, data.len());
+        } else if kind == RandomIoKind::Read {
+            verification.check(data, offset);
+        }
+    }
+}
+
+/// Random I/O loop for a single worker, issuing positioned I/O until the budget runs
+/// out, the stop flag is set or the run is cancelled
+fn random_io_worker(
+    config: &BenchmarkConfig,
+    handle: &dyn IoHandle,
+    kind: RandomIoKind,
+    block: IoBlock,
+    shared: &RandomIoShared,
+    histograms: &std::sync::Mutex<RandomIoLatencies>,
+) -> Result<(), BenchmarkError> {
+    use std::time::Instant;
+
+    // Separate buffers so reads never clobber the write pattern
+    let block_size = block.size;
+    let file_size = config.file_size_bytes();
+    let pattern = config.data_pattern;
+    let mut read_buffer = block.buffer();
+    let mut write_buffer = block.buffer();
+    pattern.fill(&mut write_buffer);
+    let mut sequence: u64 = 0;
+
+    // Calculate the number of possible block positions in the file
+    let max_blocks = (file_size / block_size as u64).max(1);
+    let mut rng = rand::thread_rng();
+    let mut latencies = LatencyBatch::new(histograms);
+
+    while shared.running(config) {
+        use rand::Rng;
+
+        // Generate random block position within the file
+        let random_block = rng.gen_range(0..max_blocks);
+        let offset = random_block * block_size as u64;
+
+        // Calculate how much to transfer (handle case where we're near end of file)
+        let remaining_file_space = file_size.saturating_sub(offset);
+        let bytes_to_transfer = std::cmp::min(block_size as u64, remaining_file_space) as usize;
+
+        if bytes_to_transfer == 0 {
+            continue; // Skip if we somehow ended up past the file end
+        }
+        if !shared.budget.claim(bytes_to_transfer) {
+            break;
+        }
+
+        let is_read = kind.next_is_read(&mut rng);
+        let measured = shared.budget.measuring();
+        let request_start = Instant::now();
+        let (transferred, buffer) = if is_read {
+            match handle.read_at(&mut read_buffer[..bytes_to_transfer], offset) {
+                Ok(bytes_read) => (bytes_read, &read_buffer),
+                Err(e) => {
+                    // Handle read errors gracefully - continue with next random position
+                    report_warning(
+                        shared.progress_callback,
+                        &format!(
+                            "{}: read error at position {}: {}",
+                            shared.test_name, offset, e
+                        ),
+                    );
+                    continue;
+                }
+            }
+        } else {
+            pattern.stamp(&mut write_buffer[..bytes_to_transfer], offset, sequence);
+            sequence += 1;
+            shared.seal(&mut write_buffer[..bytes_to_transfer], offset);
+            let bytes_written = handle.write_at(&write_buffer[..bytes_to_transfer], offset)?;
+            (bytes_written, &write_buffer)
+        };
+
+        let latency = request_start.elapsed();
+        shared.completed(kind, is_read, &buffer[..transferred], offset);
+        if measured {
+            latencies.record(is_read, latency);
+        }
+        shared.counters.add(is_read, transferred, latency);
+    }
+
+    Ok(())
+}
+
+/// Random I/O loop for a single io_uring worker, keeping `queue_depth` requests in flight
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+fn random_uring_worker(
+    config: &BenchmarkConfig,
+    file: &std::fs::File,
+    kind: RandomIoKind,
+    block: IoBlock,
+    shared: &RandomIoShared,
+    histograms: &std::sync::Mutex<RandomIoLatencies>,
+) -> Result<(), BenchmarkError> {
+    use crate::core::uring::{UringOp, UringQueue};
+    use rand::Rng;
+
+    let block_size = block.size;
+    let pattern = config.data_pattern;
+    let mut queue = UringQueue::new(config.queue_depth, block_size, block.memory_alignment)?;
+    if kind != RandomIoKind::Read {
+        queue.fill_buffers(|buffer| pattern.fill(buffer));
+    }
+    let mut sequence: u64 = 0;
+
+    let file_size = config.file_size_bytes();
+    let max_blocks = (file_size / block_size as u64).max(1);
+    let mut rng = rand::thread_rng();
+    let mut latencies = LatencyBatch::new(histograms);
+
+    queue.run(
+        file,
+        |buffer| {
+            let offset = rng.gen_range(0..max_blocks) * block_size as u64;
+            let len = std::cmp::min(block_size as u64, file_size.saturating_sub(offset));
+            if !shared.budget.claim(len as usize) {
+                return None;
+            }
+            let op = if kind.next_is_read(&mut rng) {
+                UringOp::Read
+            } else {
+                pattern.stamp(&mut buffer[..len as usize], offset, sequence);
+                sequence += 1;
+                shared.seal(&mut buffer[..len as usize], offset);
+                UringOp::Write
+            };
+            Some((op, offset, len as usize))
+        },
+        || shared.running(config),
+        |op, offset, data, latency| {
+            let submitted = shared.budget.rule.now().checked_sub(latency);
+            if submitted.is_some_and(|submitted| submitted >= shared.budget.measure_from()) {
+                latencies.record(op == UringOp::Read, latency);
+            }
+            shared.completed(kind, op == UringOp::Read, data, offset);
+            shared
+                .counters
+                .add(op == UringOp::Read, data.len(), latency);
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Dispatch a random I/O worker to the configured backend
+fn run_random_worker(
+    config: &BenchmarkConfig,
+    handle: &dyn IoHandle,
+    kind: RandomIoKind,
+    block: IoBlock,
+    shared: &RandomIoShared,
+    histograms: &std::sync::Mutex<RandomIoLatencies>,
+) -> Result<(), BenchmarkError> {
+    match config.io_backend {
+        IoBackend::Sync => random_io_worker(config, handle, kind, block, shared, histograms),
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        IoBackend::IoUring => {
+            random_uring_worker(config, uring_file(handle)?, kind, block, shared, histograms)
+        }
+        #[cfg(not(all(target_os = "linux", feature = "io-uring")))]
+        IoBackend::IoUring => Err(BenchmarkError::ConfigurationError(
+            "The io_uring backend is not available in this build".to_string(),
+        )),
+    }
+}
+
+/// Run a random I/O test across the configured workers sharing one file
+///
+/// Workers count bytes and record request latencies into the histograms of their
+/// thread; this thread samples their throughput every sampling interval and merges
+/// the latencies at the end, so all workers feed one set of results. Reads, writes and their sum
+/// are tracked separately; pure read or write tests only use the combined figure.
+/// Tests that write flush the file before their results are finalized, so the
+/// writeback counts toward their throughput as it does for sequential write.
+fn run_random_io_workers(
+    config: &BenchmarkConfig,
+    handle: &dyn IoHandle,
+    test_file_path: &Path,
+    kind: RandomIoKind,
+    test_name: &str,
+    progress_callback: Option<&dyn ProgressCallback>,
+    verification: Option<Verification>,
+) -> Result<MixedTestResult, BenchmarkError> {
+    use crate::core::RealTimeStatsTracker;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    // Create buffers with the configured block size (aligned for direct I/O if needed)
+    let requested_block_size = match kind {
+        RandomIoKind::Mixed { .. } => config.mixed_block_size,
+        RandomIoKind::Read | RandomIoKind::Write => config.random_block_size,
+    };
+    let block = IoBlock::new(requested_block_size, io_alignment(config, test_file_path));
+    let worker_count = config.random_worker_count();
+
+    // Initialize statistics tracking, discarding the warm-up
+    let mut progress = TestProgress::start(config, test_name, progress_callback);
+    let warmup = progress.warmup();
+    let mut read_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_warmup(warmup)
+        .with_time_series(config.record_time_series);
+    let mut write_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_warmup(warmup)
+        .with_time_series(config.record_time_series);
+    let mut combined_tracker = RealTimeStatsTracker::new()
+        .with_pauses(&config.cancellation)
+        .with_warmup(warmup)
+        .with_time_series(config.record_time_series);
+    let shared = RandomIoShared {
+        budget: RandomIoBudget::new(progress.rule.clone()),
+        stop: AtomicBool::new(false),
+        counters: RandomIoCounters::default(),
+        test_name,
+        progress_callback,
+        verification,
+    };
+    let budget = &shared.budget;
+    let deadline = budget.rule.deadline();
+    // One set of histograms per thread, shared by the workers emulating its queue depth
+    let histograms: Vec<std::sync::Mutex<RandomIoLatencies>> = (0..config.thread_count.max(1))
+        .map(|_| Default::default())
+        .collect();
+
+    let worker_results = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..worker_count)
+            .map(|worker| {
+                let (shared, histograms) = (&shared, &histograms[worker % histograms.len()]);
+                scope.spawn(move || {
+                    let result = run_random_worker(config, handle, kind, block, shared, histograms);
+                    if result.is_err() {
+                        // Stop the remaining workers so the error surfaces promptly
+                        shared.stop.store(true, Ordering::Relaxed);
+                    }
+                    result
+                })
+            })
+            .collect();
+
+        // Sample throughput while the workers run, waking up when the warm-up ends
+        while deadline.map_or(true, |deadline| budget.rule.now() < deadline)
+            && !config.cancellation.is_cancelled()
+            && !handles.iter().all(|h| h.is_finished())
+        {
+            let next_event = if combined_tracker.is_warming_up() {
+                Some(budget.measure_from())
+            } else {
+                deadline
+            };
+            let remaining = next_event.map_or(Duration::MAX, |next_event| {
+                next_event.saturating_duration_since(budget.rule.now())
+            });
+            std::thread::sleep(remaining.min(Duration::from_millis(10)));
+            progress.poll_phase();
+
+            shared.counters.update_trackers(
+                &mut read_tracker,
+                &mut write_tracker,
+                &mut combined_tracker,
+            );
+            let (read, written) = shared.counters.load();
+            read_tracker.update_progress(read);
+            write_tracker.update_progress(written);
+            if let Some(current_speed) = combined_tracker.update_progress(read + written) {
+                let (bytes, _) = combined_tracker.measured_totals();
+                progress.report(current_speed, bytes, budget.operations());
+            }
+        }
+
+        shared.stop.store(true, Ordering::Relaxed);
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle.join().unwrap_or_else(|_| {
+                    Err(BenchmarkError::TestInterrupted(format!(
+                        "{} worker panicked",
+                        test_name
+                    )))
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+    config.cancellation.check(test_name)?;
+
+    for result in worker_results {
+        result?;
+    }
+    for histograms in histograms {
+        let latencies = histograms
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        read_tracker.merge_latency(&latencies.read);
+        write_tracker.merge_latency(&latencies.write);
+        combined_tracker.merge_latency(&latencies.read);
+        combined_tracker.merge_latency(&latencies.write);
+    }
+
+    // Account for I/O completed since the last sampling interval
+    shared
+        .counters
+        .update_trackers(&mut read_tracker, &mut write_tracker, &mut combined_tracker);
+    let (read, written) = shared.counters.load();
+    read_tracker.flush_progress(read);
+    write_tracker.flush_progress(written);
+    combined_tracker.flush_progress(read + written);
+
+    // Ensure written data is on disk, counting the writeback in the test's wall time
+    if kind.writes() {
+        report_phase(progress_callback, test_name, TestPhase::Flush);
+        handle.flush()?;
+        if config.disable_os_cache {
+            crate::platform::sync_file_system(test_file_path)?;
+        }
+    }
+
+    // Finalize statistics
+    let stop_reason = budget.stop_reason();
+    let result = MixedTestResult {
+        read_percentage: match kind {
+            RandomIoKind::Read => 100,
+            RandomIoKind::Write => 0,
+            RandomIoKind::Mixed { read_percentage } => read_percentage,
+        },
+        read: read_tracker.finalize().with_stop_reason(stop_reason),
+        write: write_tracker.finalize().with_stop_reason(stop_reason),
+        combined: combined_tracker.finalize().with_stop_reason(stop_reason),
+    };
+
+    // Fallback: ensure at least one progress emission for very short tests
+    progress.finish(&result.combined);
+
+    Ok(result)
+}
+
+/// Random write test implementation
+///
+/// Issues positioned writes from `config.thread_count` threads at `config.queue_depth`
+/// requests each, so a QD1T1 configuration matches a single blocking writer.
+pub fn run_random_write_test(
+    config: &BenchmarkConfig,
+    engine: &dyn IoEngine,
+    test_file_path: &Path,
+    progress_callback: Option<&dyn ProgressCallback>,
+    verifier: Option<&TestDataVerifier>,
+) -> Result<TestResult, BenchmarkError> {
+    // Report test start
+    if let Some(callback) = progress_callback {
+        callback.on_test_start("Random Write");
+    }
+    let verification = Verification::writes(verifier, false);
+
+    // Open the test file (direct I/O or buffered I/O based on config)
+    let handle = engine.open(
+        test_file_path,
+        &IoOpenOptions::write(!config.disable_direct_io),
+    )?;
+
+    let result = run_random_io_workers(
+        config,
+        handle.as_ref(),
+        test_file_path,
+        RandomIoKind::Write,
+        "Random Write",
+        progress_callback,
+        verification,
+    )?
+    .combined;
+
+    // Report test completion
+    if let Some(callback) = progress_callback {
+        callback.on_test_complete("Random Write", &result);
+    }
+
+    Ok(result)
+}
+
+/// Random read test implementation
+///
+/// Issues positioned reads from `config.thread_count` threads at `config.queue_depth`
+/// requests each, so a QD1T1 configuration matches a single blocking reader.
+pub fn run_random_read_test(
+    config: &BenchmarkConfig,
+    engine: &dyn IoEngine,
+    test_file_path: &Path,
+    progress_callback: Option<&dyn ProgressCallback>,
+    verifier: Option<&TestDataVerifier>,
+) -> Result<TestResult, BenchmarkError> {
+    // Report test start
+    if let Some(callback) = progress_callback {
+        callback.on_test_start("Random Read");
+    }
+    let verification = Verification::reads(verifier);
+
+    // Open the test file (direct I/O or buffered I/O based on config)
+    let handle = engine.open(
+        test_file_path,
+        &IoOpenOptions::read(!config.disable_direct_io),
+    )?;
+
+    let result = run_random_io_workers(
+        config,
+        handle.as_ref(),
+        test_file_path,
+        RandomIoKind::Read,
+        "Random Read",
+        progress_callback,
+        verification,
+    )?
+    .combined;
+    let result = attach_corruption(verification, result, "Random Read", progress_callback);
+
     // Report test completion
     if let Some(callback) = progress_callback {
         callback.on_test_complete("Random Read", &result);
@@ -489,11 +2193,60 @@ pub fn run_random_read_test(
     Ok(result)
 }
 
+/// Mixed random read/write test implementation
+///
+/// Interleaves positioned reads and writes of `config.mixed_block_size` bytes, choosing
+/// each request's direction so that `config.mixed_read_percentage` percent are reads.
+/// Read, write and combined throughput are sampled over the same intervals.
+pub fn run_mixed_random_test(
+    config: &BenchmarkConfig,
+    engine: &dyn IoEngine,
+    test_file_path: &Path,
+    progress_callback: Option<&dyn ProgressCallback>,
+    verifier: Option<&TestDataVerifier>,
+) -> Result<MixedTestResult, BenchmarkError> {
+    // Report test start
+    if let Some(callback) = progress_callback {
+        callback.on_test_start("Mixed Random");
+    }
+    let verification = Verification::writes(verifier, false);
+
+    // Writes need the file opened for writing; reads go through the same handle
+    let handle = engine.open(
+        test_file_path,
+        &IoOpenOptions::write(!config.disable_direct_io),
+    )?;
+
+    let result = run_random_io_workers(
+        config,
+        handle.as_ref(),
+        test_file_path,
+        RandomIoKind::Mixed {
+            read_percentage: config.mixed_read_percentage,
+        },
+        "Mixed Random",
+        progress_callback,
+        verification,
+    )?;
+
+    // Report test completion
+    if let Some(callback) = progress_callback {
+        callback.on_test_complete("Mixed Random", &result.combined);
+    }
+
+    Ok(result)
+}
+
 /// Memory copy test implementation
+///
+/// Copies within each working set from `memory_min_size` doubling up to
+/// `memory_max_size`, smallest first. The test duration is split evenly across the
+/// working sets, while byte, operation and pass limits apply to each of them. Only
+/// the first working set is preceded by the warm-up.
 pub fn run_memory_copy_test(
     config: &BenchmarkConfig,
     progress_callback: Option<&dyn ProgressCallback>,
-) -> Result<TestResult, BenchmarkError> {
+) -> Result<Vec<MemoryBandwidthPoint>, BenchmarkError> {
     use crate::core::RealTimeStatsTracker;
     use std::time::Instant;
 
@@ -505,70 +2258,855 @@ pub fn run_memory_copy_test(
     // Use similar block size as disk tests for meaningful comparison
     // Use sequential block size as it's more appropriate for large memory operations
     let block_size = config.sequential_block_size;
-    let total_memory_size = config.file_size_bytes() as usize;
+    let sizes = working_set_sizes(config.memory_min_size, config.memory_max_size);
+    // Each working set is split evenly between the source and the destination
+    let halves: Vec<usize> = sizes.iter().map(|&size| (size / 2).max(1)).collect();
+    let largest = halves.iter().copied().max().unwrap_or(1);
+
+    // Allocate the buffers of the largest working set, which the smaller ones are
+    // prefixes of. Both are written in full so no page is first touched while measuring.
+    // The source holds alternating bytes to avoid compression optimizations.
+    let source_buffer: Vec<u8> = (0..largest).map(|i| (i % 256) as u8).collect();
+    let mut destination_buffer = vec![0xFFu8; largest];
+
+    // Progress covers the whole sweep; each working set stops on its share of the limit
+    let mut progress = TestProgress::start(config, "Memory Copy", progress_callback);
+    let pass_bytes = halves.iter().map(|&half| half as u64).sum();
+    progress.rule = StopRule::whole(config, progress.rule.measure_from, sizes.len(), pass_bytes);
+    let mut points = Vec::with_capacity(sizes.len());
+    let mut completed_bytes = 0;
+    let mut completed_operations = 0;
+
+    for (&working_set, &half) in sizes.iter().zip(&halves) {
+        let started = progress.rule.now();
+        let measure_from = progress.rule.measure_from.max(started);
+        let stop_rule = StopRule::share(config, measure_from, sizes.len(), half as u64);
+        let mut stats_tracker = RealTimeStatsTracker::new()
+            .with_pauses(&config.cancellation)
+            .with_warmup(measure_from.duration_since(started))
+            .with_time_series(config.record_time_series);
+
+        let source = &source_buffer[..half];
+        let destination = &mut destination_buffer[..half];
+        let chunk_size = block_size.min(half);
+        // A working set copied in one go is copied repeatedly, as repeating a chunk
+        // of a larger one would measure the cache holding that chunk instead
+        let repeats = if chunk_size == half {
+            (MIN_TIMED_COPY + half - 1) / half
+        } else {
+            1
+        };
+        let mut offset = 0;
 
-    // Allocate two large buffers for memory-to-memory copy operations
-    let mut source_buffer = vec![0u8; total_memory_size];
-    let mut destination_buffer = vec![0u8; total_memory_size];
+        let stop_reason = loop {
+            config.cancellation.check("Memory Copy")?;
+            let (bytes, operations) = stats_tracker.measured_totals();
+            if let Some(reason) = stop_rule.check(bytes, operations) {
+                break reason;
+            }
+            progress.poll_phase();
+            let bytes_to_copy = chunk_size.min(half - offset);
 
-    // Fill source buffer with test pattern (alternating bytes to avoid compression optimizations)
-    for (i, byte) in source_buffer.iter_mut().enumerate() {
-        *byte = (i % 256) as u8;
+            // Perform memory-to-memory copy using optimized routines
+            // This uses the standard library's optimized copy_from_slice which typically
+            // uses platform-specific optimized memory copy routines (like memcpy)
+            let copy_start = Instant::now();
+            for _ in 0..repeats {
+                destination[offset..offset + bytes_to_copy]
+                    .copy_from_slice(&source[offset..offset + bytes_to_copy]);
+                // Keep the repeated copies from being merged into one
+                std::hint::black_box(&mut destination[offset..offset + bytes_to_copy]);
+            }
+            let elapsed = copy_start.elapsed();
+            offset = (offset + bytes_to_copy) % half;
+
+            // Record per-copy speed and report progress periodically
+            if let Some(current_speed) =
+                stats_tracker.record_block(bytes_to_copy * repeats, elapsed)
+            {
+                let (bytes, operations) = stats_tracker.measured_totals();
+                progress.report(
+                    current_speed,
+                    completed_bytes + bytes,
+                    completed_operations + operations,
+                );
+            }
+        };
+
+        let (bytes, operations) = stats_tracker.measured_totals();
+        completed_bytes += bytes;
+        completed_operations += operations;
+        points.push(MemoryBandwidthPoint {
+            working_set: working_set as u64,
+            result: stats_tracker.finalize().with_stop_reason(stop_reason),
+        });
     }
 
-    // Initialize statistics tracking
-    let mut stats_tracker = RealTimeStatsTracker::new();
-    let test_start = Instant::now();
-    let test_duration = effective_test_duration(config);
-    let mut emitted_progress = false;
+    // The largest working set stands for the test as a whole
+    if let Some(point) = points.last() {
+        // Fallback: ensure at least one progress emission for very short tests
+        progress.finish(&point.result);
 
-    // No cumulative counter needed with per-block sampling
+        // Report test completion
+        if let Some(callback) = progress_callback {
+            callback.on_test_complete("Memory Copy", &point.result);
+        }
+    }
 
-    // Main memory copy loop - continue until test duration elapsed
-    while test_start.elapsed() < test_duration {
-        let mut offset = 0;
+    Ok(points)
+}
 
-        // Copy the entire buffer in block_size chunks
-        while offset < total_memory_size && test_start.elapsed() < test_duration {
-            let bytes_to_copy = std::cmp::min(block_size, total_memory_size - offset);
+/// CPUs each of `threads` memory stream threads is pinned to, if any
+fn memory_thread_cpus(
+    pinning: &ThreadPinning,
+    threads: usize,
+) -> Result<Vec<Option<Vec<usize>>>, BenchmarkError> {
+    let cpu_sets = match pinning {
+        ThreadPinning::Unpinned => return Ok(vec![None; threads]),
+        ThreadPinning::Cpus(cpus) => cpus.iter().map(|&cpu| vec![cpu]).collect(),
+        ThreadPinning::NumaNodes(nodes) => nodes
+            .iter()
+            .map(|&node| crate::platform::numa_node_cpus(node))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+    Ok((0..threads)
+        .map(|thread| cpu_sets.get(thread % cpu_sets.len().max(1)).cloned())
+        .collect())
+}
 
-            // Perform memory-to-memory copy using optimized routines
-            // This uses the standard library's optimized copy_from_slice which typically
-            // uses platform-specific optimized memory copy routines (like memcpy)
-            let copy_start = Instant::now();
-            destination_buffer[offset..offset + bytes_to_copy]
-                .copy_from_slice(&source_buffer[offset..offset + bytes_to_copy]);
+/// Memory stream test implementation
+///
+/// Runs the STREAM copy, scale, add and triad kernels one after another on
+/// `memory_threads` threads, over three arrays that together take up
+/// `memory_max_size`. Each thread allocates and initializes its own slice of the
+/// arrays after it is pinned, so the slices end up on the thread's NUMA node. A
+/// pass ends when every thread has finished its slice. The test duration is split
+/// evenly across the kernels; a pass limit counts passes of each kernel.
+pub fn run_memory_stream_test(
+    config: &BenchmarkConfig,
+    progress_callback: Option<&dyn ProgressCallback>,
+) -> Result<MemoryStreamResult, BenchmarkError> {
+    use crate::core::RealTimeStatsTracker;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Instant;
 
-            offset += bytes_to_copy;
+    /// Command that ends the worker threads instead of starting a pass
+    const STOP: usize = usize::MAX;
+
+    let worker_panicked =
+        || BenchmarkError::TestInterrupted("Memory Stream worker panicked".to_string());
+
+    let threads = config.memory_threads.max(1);
+    let elements = (config.memory_max_size / (3 * std::mem::size_of::<f64>())).max(threads);
+    let array_size = (elements * std::mem::size_of::<f64>()) as u64;
+    let thread_cpus = memory_thread_cpus(&config.memory_pinning, threads)?;
+
+    // Workers wait at the barrier for each pass and again when they finished it. A
+    // thread that panics breaks the barrier, so the others do not wait for it forever.
+    let barrier = StreamBarrier::new(threads + 1);
+    let command = AtomicUsize::new(STOP);
+    let pin_errors = Mutex::new(Vec::new());
+
+    std::thread::scope(|scope| {
+        let _guard = barrier.guard();
+        let mut workers = Vec::with_capacity(threads);
+        for (thread, cpus) in thread_cpus.into_iter().enumerate() {
+            let len = elements / threads + usize::from(thread < elements % threads);
+            let (barrier, command, pin_errors) = (&barrier, &command, &pin_errors);
+            workers.push(scope.spawn(move || {
+                let _guard = barrier.guard();
+                if let Some(cpus) = cpus {
+                    if let Err(e) = crate::platform::pin_current_thread(&cpus) {
+                        if let Ok(mut errors) = pin_errors.lock() {
+                            errors.push(e);
+                        }
+                    }
+                }
+                // Written in full after pinning, so the pages are placed near this thread
+                let mut a = vec![1.0; len];
+                let mut b = vec![2.0; len];
+                let mut c = vec![0.5; len];
+                if !barrier.wait() {
+                    return;
+                }
 
-            // Record per-block speed and report progress periodically
-            let elapsed = copy_start.elapsed();
-            if let Some(current_speed) = stats_tracker.record_block(bytes_to_copy, elapsed) {
+                while barrier.wait() {
+                    let Some(&kernel) = StreamKernel::ALL.get(command.load(Ordering::Acquire))
+                    else {
+                        break;
+                    };
+                    kernel.run(&mut a, &mut b, &mut c);
+                    std::hint::black_box((&a, &b, &c));
+                    if !barrier.wait() {
+                        break;
+                    }
+                }
+            }));
+        }
+
+        // The workers are ready once they have allocated their arrays
+        let ready = barrier.wait();
+        let measured = (|| {
+            if !ready {
+                return Err(worker_panicked());
+            }
+            if let Some(e) = pin_errors.lock().ok().and_then(|mut errors| errors.pop()) {
+                return Err(BenchmarkError::from(e));
+            }
+
+            let mut result = MemoryStreamResult {
+                threads,
+                array_size,
+                ..Default::default()
+            };
+            for (index, kernel) in StreamKernel::ALL.into_iter().enumerate() {
+                let test_name = kernel.test_name();
                 if let Some(callback) = progress_callback {
-                    callback.on_progress("Memory Copy", current_speed);
-                    emitted_progress = true;
+                    callback.on_test_start(test_name);
                 }
+
+                let pass_bytes = kernel.arrays() * array_size;
+                let mut progress = TestProgress::start(config, test_name, progress_callback);
+                progress.rule = StopRule::share(
+                    config,
+                    progress.rule.measure_from,
+                    StreamKernel::ALL.len(),
+                    pass_bytes,
+                );
+                let mut stats_tracker = RealTimeStatsTracker::new()
+                    .with_pauses(&config.cancellation)
+                    .with_warmup(progress.warmup())
+                    .with_time_series(config.record_time_series);
+                command.store(index, Ordering::Release);
+
+                let stop_reason = loop {
+                    config.cancellation.check("Memory Stream")?;
+                    let (bytes, operations) = stats_tracker.measured_totals();
+                    if let Some(reason) = progress.rule.check(bytes, operations) {
+                        break reason;
+                    }
+                    progress.poll_phase();
+
+                    // One pass: release the workers, then wait for all of them to finish
+                    let pass_start = Instant::now();
+                    if !(barrier.wait() && barrier.wait()) {
+                        return Err(worker_panicked());
+                    }
+                    let elapsed = pass_start.elapsed();
+                    if let Some(current_speed) =
+                        stats_tracker.record_block(pass_bytes as usize, elapsed)
+                    {
+                        let (bytes, operations) = stats_tracker.measured_totals();
+                        progress.report(current_speed, bytes, operations);
+                    }
+                };
+
+                let kernel_result = stats_tracker.finalize().with_stop_reason(stop_reason);
+                progress.finish(&kernel_result);
+                if let Some(callback) = progress_callback {
+                    callback.on_test_complete(test_name, &kernel_result);
+                }
+                *result.result_mut(kernel) = kernel_result;
             }
+            Ok(result)
+        })();
+
+        // Release the workers one last time, telling them to stop
+        command.store(STOP, Ordering::Release);
+        barrier.wait();
+        for worker in workers {
+            worker.join().map_err(|_| worker_panicked())?;
         }
+        measured
+    })
+}
 
-        // If we've completed a full copy, continue with another iteration
-        // This ensures we keep the test running for the full duration
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::core::{BenchmarkTest, FileSizeShare, PreadEngine};
+    use std::time::{Duration, Instant};
+
+    fn limited_config(target: &Path, test_limit: TestLimit) -> BenchmarkConfig {
+        BenchmarkConfig {
+            target_path: target.to_path_buf(),
+            sequential_block_size: 64 * 1024,
+            random_block_size: 4 * 1024,
+            disable_direct_io: true,
+            disable_os_cache: false,
+            file_size_mb: 1,
+            test_limit,
+            time_cap_seconds: Some(30),
+            tests: vec![BenchmarkTest::SequentialWrite],
+            ..Default::default()
+        }
     }
 
-    // Finalize statistics
-    let result = stats_tracker.finalize();
+    #[test]
+    fn test_stop_rule_limits() {
+        let config = BenchmarkConfig {
+            test_limit: TestLimit::Passes(2),
+            file_size_mb: 1,
+            ..Default::default()
+        };
+        let rule = StopRule::new(&config, Instant::now());
+        assert_eq!(rule.check(2 * 1024 * 1024 - 1, 10), None);
+        assert_eq!(rule.check(2 * 1024 * 1024, 10), Some(StopReason::PassLimit));
+
+        let config = BenchmarkConfig {
+            test_limit: TestLimit::Operations(5),
+            time_cap_seconds: Some(1),
+            ..Default::default()
+        };
+        let rule = StopRule::new(&config, Instant::now());
+        assert_eq!(rule.check(u64::MAX, 4), None);
+        assert_eq!(rule.check(0, 5), Some(StopReason::OperationLimit));
+
+        let earlier = Instant::now() - Duration::from_secs(2);
+        let rule = StopRule::new(&config, earlier);
+        assert_eq!(rule.check(0, 4), Some(StopReason::TimeCap));
+    }
 
-    // Fallback: ensure at least one progress emission for very short tests
-    if !emitted_progress {
-        if let Some(callback) = progress_callback {
-            callback.on_progress("Memory Copy", result.avg_speed_mbps);
+    #[test]
+    fn test_stop_rule_share() {
+        // Passes are made over each part's own bytes
+        let config = BenchmarkConfig {
+            test_limit: TestLimit::Passes(2),
+            ..Default::default()
+        };
+        let rule = StopRule::share(&config, Instant::now(), 4, 1000);
+        assert_eq!(rule.check(1999, 1), None);
+        assert_eq!(rule.check(2000, 1), Some(StopReason::PassLimit));
+        let rule = StopRule::whole(&config, Instant::now(), 4, 4000);
+        assert_eq!(rule.byte_limit, Some(8000));
+
+        // Operation limits apply to each part, the time cap to all of them
+        let config = BenchmarkConfig {
+            test_limit: TestLimit::Operations(100),
+            time_cap_seconds: Some(8),
+            ..Default::default()
+        };
+        let rule = StopRule::share(&config, Instant::now(), 4, 1000);
+        assert_eq!(rule.check(0, 99), None);
+        assert_eq!(rule.check(0, 100), Some(StopReason::OperationLimit));
+        assert_eq!(rule.time_limit, Some(Duration::from_secs(2)));
+        let rule = StopRule::whole(&config, Instant::now(), 4, 4000);
+        assert_eq!(rule.operation_limit, Some(400));
+        assert_eq!(rule.time_limit, Some(Duration::from_secs(8)));
+    }
+
+    #[test]
+    fn test_stop_rule_ignores_warmup() {
+        let config = BenchmarkConfig {
+            test_limit: TestLimit::Bytes(1),
+            ..Default::default()
+        };
+        let rule = StopRule::new(&config, Instant::now() + Duration::from_secs(60));
+        assert_eq!(rule.check(u64::MAX, u64::MAX), None);
+        assert_eq!(rule.deadline(), None);
+    }
+
+    #[test]
+    fn test_stop_rule_fraction_and_eta() {
+        let config = BenchmarkConfig {
+            test_limit: TestLimit::Operations(100),
+            time_cap_seconds: Some(30),
+            ..Default::default()
+        };
+        let now = Instant::now();
+        let rule = StopRule::new(&config, now - Duration::from_secs(2));
+        assert_eq!(rule.fraction(0, 50, now), 0.5);
+        assert_eq!(rule.fraction(0, 500, now), 1.0);
+        assert_eq!(rule.eta(0.5, now), Some(Duration::from_secs(2)));
+
+        // A time limit bounds the estimate, and is the only estimate during the warm-up
+        let config = BenchmarkConfig {
+            test_duration_seconds: 10,
+            ..Default::default()
+        };
+        let rule = StopRule::new(&config, now - Duration::from_secs(5));
+        assert_eq!(rule.fraction(0, 0, now), 0.5);
+        assert_eq!(rule.eta(0.01, now), Some(Duration::from_secs(5)));
+        let rule = StopRule::new(&config, now + Duration::from_secs(5));
+        assert_eq!(rule.fraction(u64::MAX, u64::MAX, now), 0.0);
+        assert_eq!(rule.eta(0.0, now), Some(Duration::from_secs(15)));
+    }
+
+    /// Engine whose flush takes a noticeable time, to see whether tests count it
+    struct SlowFlushEngine;
+
+    struct SlowFlushHandle(Box<dyn IoHandle>);
+
+    const SLOW_FLUSH: Duration = Duration::from_millis(300);
+
+    impl IoEngine for SlowFlushEngine {
+        fn name(&self) -> &str {
+            "slow-flush"
+        }
+
+        fn open(
+            &self,
+            path: &Path,
+            options: &IoOpenOptions,
+        ) -> Result<Box<dyn IoHandle>, BenchmarkError> {
+            Ok(Box::new(SlowFlushHandle(PreadEngine.open(path, options)?)))
         }
     }
 
-    // Report test completion
-    if let Some(callback) = progress_callback {
-        callback.on_test_complete("Memory Copy", &result);
+    impl IoHandle for SlowFlushHandle {
+        fn read_at(&self, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+            self.0.read_at(buf, offset)
+        }
+
+        fn write_at(&self, buf: &[u8], offset: u64) -> std::io::Result<usize> {
+            self.0.write_at(buf, offset)
+        }
+
+        fn flush(&self) -> std::io::Result<()> {
+            std::thread::sleep(SLOW_FLUSH);
+            self.0.flush()
+        }
+
+        fn as_file(&self) -> Option<&std::fs::File> {
+            self.0.as_file()
+        }
     }
 
-    Ok(result)
+    #[test]
+    fn test_random_writes_count_their_flush() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Operations(8));
+        config.thread_count = 2;
+        let path = temp_dir.path().join("flush.tmp");
+        run_preparation(&config, &PreadEngine, &path, None, None).unwrap();
+
+        let write = run_random_write_test(&config, &SlowFlushEngine, &path, None, None).unwrap();
+        assert!(
+            write.test_duration >= SLOW_FLUSH,
+            "{:?}",
+            write.test_duration
+        );
+
+        let mixed = run_mixed_random_test(&config, &SlowFlushEngine, &path, None, None).unwrap();
+        assert!(
+            mixed.combined.test_duration >= SLOW_FLUSH,
+            "{:?}",
+            mixed.combined
+        );
+
+        // Reads have nothing to flush
+        let read = run_random_read_test(&config, &SlowFlushEngine, &path, None, None).unwrap();
+        assert!(read.test_duration < SLOW_FLUSH, "{:?}", read.test_duration);
+    }
+
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    #[test]
+    fn test_uring_sequential_write_counts_its_flush() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Passes(1));
+        config.io_backend = IoBackend::IoUring;
+        let path = temp_dir.path().join("flush.tmp");
+
+        let write =
+            run_sequential_write_test(&config, &SlowFlushEngine, &path, None, None).unwrap();
+        assert!(
+            write.test_duration >= SLOW_FLUSH,
+            "{:?}",
+            write.test_duration
+        );
+    }
+
+    #[test]
+    fn test_tests_report_phases_and_updates() {
+        use crate::core::TestProgressCallback;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = limited_config(temp_dir.path(), TestLimit::Operations(40));
+        let path = temp_dir.path().join("progress.tmp");
+        let callback = TestProgressCallback::new();
+
+        run_preparation(&config, &PreadEngine, &path, Some(&callback), None).unwrap();
+        run_sequential_write_test(&config, &PreadEngine, &path, Some(&callback), None).unwrap();
+
+        assert_eq!(
+            callback.phase_change_events(),
+            vec![
+                ("Preparation".to_string(), TestPhase::Prepare),
+                ("Preparation".to_string(), TestPhase::Flush),
+                ("Sequential Write".to_string(), TestPhase::Measure),
+                ("Sequential Write".to_string(), TestPhase::Flush),
+            ]
+        );
+        for test_name in ["Preparation", "Sequential Write"] {
+            let updates = callback.progress_updates_for_test(test_name);
+            let last = updates.last().unwrap();
+            assert!(last.fraction > 0.0 && last.fraction <= 1.0);
+            assert!(last.bytes > 0 && last.operations > 0);
+            assert!(last.eta.is_some());
+        }
+        let prepared = callback.progress_updates_for_test("Preparation");
+        assert_eq!(prepared.last().unwrap().phase, TestPhase::Prepare);
+        assert_eq!(prepared.last().unwrap().total, None);
+    }
+
+    #[test]
+    fn test_sequential_write_stops_at_operation_limit() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = limited_config(temp_dir.path(), TestLimit::Operations(40));
+        let path = temp_dir.path().join("limit.tmp");
+
+        let result = run_sequential_write_test(&config, &PreadEngine, &path, None, None).unwrap();
+        assert_eq!(result.stop_reason, StopReason::OperationLimit);
+        assert_eq!(result.total_operations, 40);
+        assert_eq!(result.total_bytes, 40 * 64 * 1024);
+    }
+
+    #[test]
+    fn test_sustained_write_samples_throughput_over_time() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Operations(1));
+        config.sustained_write_seconds = 1;
+        let path = temp_dir.path().join("sustained.tmp");
+        let callback = crate::core::TestProgressCallback::new();
+
+        let sustained =
+            run_sustained_write_test(&config, &PreadEngine, &path, Some(&callback), None).unwrap();
+
+        // The operation limit does not apply; the test runs for its own duration
+        assert_eq!(sustained.result.stop_reason, StopReason::Duration);
+        assert!(sustained.result.total_operations > 1);
+        assert!(sustained.samples.len() >= 20);
+        assert!(sustained
+            .samples
+            .windows(2)
+            .all(|pair| pair[0].elapsed < pair[1].elapsed && pair[0].bytes <= pair[1].bytes));
+        assert!(!sustained.phases.is_empty());
+        assert!(sustained.fast_speed_mbps > 0.0);
+        assert!(sustained.steady_speed_mbps > 0.0);
+        assert!(!callback
+            .progress_updates_for_test("Sustained Write")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sync_write_commits_every_record() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Operations(50));
+        config.sync_write_size = 512;
+        config.sync_method = SyncMethod::Fsync;
+        let path = temp_dir.path().join("sync.tmp");
+
+        let result = run_sync_write_test(&config, &path, None).unwrap();
+        assert_eq!(result.stop_reason, StopReason::OperationLimit);
+        assert_eq!(result.total_operations, 50);
+        assert_eq!(result.latency.operations, 50);
+        assert_eq!(result.total_bytes, 50 * 512);
+        // Commits overwrite the prefilled log instead of growing it
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().len(),
+            SYNC_WRITE_LOG_RECORDS * 512
+        );
+    }
+
+    #[test]
+    fn test_sync_write_appends_records() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Operations(30));
+        config.sync_write_size = 1024;
+        config.sync_write_append = true;
+        let path = temp_dir.path().join("sync.tmp");
+
+        let result = run_sync_write_test(&config, &path, None).unwrap();
+        assert_eq!(result.total_operations, 30);
+        assert!(std::fs::metadata(&path).unwrap().len() >= 30 * 1024);
+    }
+
+    #[test]
+    fn test_metadata_times_every_operation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Duration);
+        config.metadata_file_count = 50;
+        config.metadata_fanout = 4;
+        let dir = temp_dir.path().join("metadata");
+
+        let metadata = run_metadata_test(&config, &dir, None).unwrap();
+        assert_eq!(metadata.file_count, 50);
+        assert_eq!(metadata.directory_count, 4);
+        for (operation, result) in metadata.results() {
+            let expected = if operation == MetadataOperation::List {
+                4
+            } else {
+                50
+            };
+            assert_eq!(result.total_operations, expected, "{}", operation);
+            assert_eq!(result.latency.operations, expected, "{}", operation);
+            assert!(result.latency.iops > 0.0, "{}", operation);
+            assert_eq!(result.total_bytes, 0);
+        }
+
+        // The files are gone; the emptied directories are left for the cleanup
+        let directories: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(directories.len(), 4);
+        for directory in directories {
+            let path = directory.unwrap().path();
+            assert_eq!(std::fs::read_dir(path).unwrap().count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_small_files_writes_and_reads_every_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Duration);
+        config.small_file_count = 150;
+        config.small_file_sizes = vec![FileSizeShare::new(4096, 3), FileSizeShare::new(16384, 1)];
+        let dir = temp_dir.path().join("small_files");
+
+        let result = run_small_files_test(&config, &PreadEngine, &dir, None).unwrap();
+        let expected_bytes: u64 = SmallFilePlan::new(&config.small_file_sizes, 150)
+            .sizes
+            .iter()
+            .map(|&size| size as u64)
+            .sum();
+        assert_eq!(result.file_count, 150);
+        assert_eq!(result.directory_count, 2);
+        for test in [&result.write, &result.read] {
+            assert_eq!(test.total_operations, 150);
+            assert_eq!(test.total_bytes, expected_bytes);
+            assert_eq!(test.stop_reason, StopReason::PassLimit);
+        }
+        assert!(result.write_files_per_second() > 0.0);
+        assert!(result.read_files_per_second() > 0.0);
+        assert_eq!(result.open_latency.operations, 150);
+
+        // The tree is left for the benchmark's cleanup
+        let files: usize = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|directory| {
+                std::fs::read_dir(directory.unwrap().path())
+                    .unwrap()
+                    .count()
+            })
+            .sum();
+        assert_eq!(files, 150);
+    }
+
+    #[test]
+    fn test_memory_copy_stops_at_pass_limit() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Passes(3));
+        config.memory_min_size = 256 * 1024;
+        config.memory_max_size = 1024 * 1024;
+
+        // Each working set is copied three times, half of it being the source
+        let points = run_memory_copy_test(&config, None).unwrap();
+        let working_sets: Vec<u64> = points.iter().map(|point| point.working_set).collect();
+        assert_eq!(working_sets, [256 * 1024, 512 * 1024, 1024 * 1024]);
+        for point in &points {
+            assert_eq!(point.result.stop_reason, StopReason::PassLimit);
+            assert_eq!(point.result.total_bytes, 3 * point.working_set / 2);
+        }
+    }
+
+    #[test]
+    fn test_memory_copy_sweeps_working_sets_within_duration() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Duration);
+        config.test_duration_seconds = 1;
+        config.memory_min_size = 4 * 1024;
+        config.memory_max_size = 4 * 1024 * 1024;
+
+        let start = Instant::now();
+        let points = run_memory_copy_test(&config, None).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(3));
+        assert_eq!(points.len(), 11);
+        for point in &points {
+            assert_eq!(point.result.stop_reason, StopReason::Duration);
+            assert!(point.result.avg_speed_mbps > 0.0, "{}", point.working_set);
+        }
+    }
+
+    #[test]
+    fn test_memory_stream_runs_every_kernel() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Passes(2));
+        config.memory_max_size = 3 * 1024 * 1024;
+        config.memory_threads = 2;
+
+        let stream = run_memory_stream_test(&config, None).unwrap();
+        assert_eq!(stream.threads, 2);
+        assert_eq!(stream.array_size, 1024 * 1024);
+        for (kernel, result) in stream.results() {
+            assert_eq!(result.stop_reason, StopReason::PassLimit, "{}", kernel);
+            assert_eq!(result.total_operations, 2, "{}", kernel);
+            assert_eq!(result.total_bytes, 2 * kernel.arrays() * stream.array_size);
+            assert!(result.avg_speed_mbps > 0.0, "{}", kernel);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_memory_stream_pins_threads() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Passes(1));
+        config.memory_max_size = 3 * 1024 * 1024;
+        config.memory_threads = 2;
+        // Pin to the CPU this thread runs on, which is allowed even where CPU 0 is not
+        // SAFETY: sched_getcpu has no preconditions
+        let cpu = unsafe { libc::sched_getcpu() };
+        assert!(cpu >= 0);
+        config.memory_pinning = ThreadPinning::Cpus(vec![cpu as usize]);
+
+        let stream = run_memory_stream_test(&config, None).unwrap();
+        assert_eq!(stream.triad.total_operations, 1);
+
+        config.memory_pinning = ThreadPinning::Cpus(vec![usize::MAX]);
+        assert!(run_memory_stream_test(&config, None).is_err());
+    }
+
+    #[test]
+    fn test_verified_round_trip_finds_no_corruption() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Passes(1));
+        config.thread_count = 2;
+        config.verify = true;
+        let path = temp_dir.path().join("verify.tmp");
+        let verifier =
+            TestDataVerifier::new(config.data_pattern).with_file_size(config.file_size_bytes());
+
+        run_preparation(&config, &PreadEngine, &path, None, Some(&verifier)).unwrap();
+        run_random_write_test(&config, &PreadEngine, &path, None, Some(&verifier)).unwrap();
+        let sequential =
+            run_sequential_read_test(&config, &PreadEngine, &path, None, Some(&verifier)).unwrap();
+        let random =
+            run_random_read_test(&config, &PreadEngine, &path, None, Some(&verifier)).unwrap();
+
+        for result in [sequential, random] {
+            assert!(result.total_bytes > 0);
+            assert!(!result.has_corruption());
+            assert!(result.corruption_events.is_empty());
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_verified_read_reports_corrupted_and_stale_sectors() {
+        use crate::core::{CorruptionKind, TestProgressCallback};
+        use std::os::unix::fs::FileExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Passes(1));
+        config.verify = true;
+        let path = temp_dir.path().join("verify.tmp");
+        let verifier =
+            TestDataVerifier::new(config.data_pattern).with_file_size(config.file_size_bytes());
+
+        // Keep a sector of the first generation, then overwrite the whole file
+        run_preparation(&config, &PreadEngine, &path, None, Some(&verifier)).unwrap();
+        let mut stale = vec![0u8; 512];
+        std::fs::File::open(&path)
+            .unwrap()
+            .read_exact_at(&mut stale, 4096)
+            .unwrap();
+        run_sequential_write_test(&config, &PreadEngine, &path, None, Some(&verifier)).unwrap();
+
+        // Put the old sector back and flip a bit in another one
+        let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+        file.write_all_at(&stale, 4096).unwrap();
+        let mut byte = [0u8; 1];
+        std::fs::File::open(&path)
+            .unwrap()
+            .read_exact_at(&mut byte, 8192 + 100)
+            .unwrap();
+        file.write_all_at(&[byte[0] ^ 0x01], 8192 + 100).unwrap();
+        drop(file);
+
+        let callback = TestProgressCallback::new();
+        let result = run_sequential_read_test(
+            &config,
+            &PreadEngine,
+            &path,
+            Some(&callback),
+            Some(&verifier),
+        )
+        .unwrap();
+
+        assert_eq!(result.corrupted_sectors, 2);
+        assert_eq!(result.corruption_events[0].offset, 4096);
+        assert_eq!(
+            result.corruption_events[0].kind,
+            CorruptionKind::WrongGeneration {
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(result.corruption_events[1].offset, 8192);
+        assert_eq!(
+            result.corruption_events[1].kind,
+            CorruptionKind::ChecksumMismatch
+        );
+        assert_eq!(callback.warning_events().len(), 1);
+    }
+
+    #[test]
+    fn test_random_read_stops_at_byte_limit_across_workers() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Bytes(400 * 4 * 1024));
+        config.thread_count = 4;
+        let path = temp_dir.path().join("limit.tmp");
+        run_preparation(&config, &PreadEngine, &path, None, None).unwrap();
+
+        let result = run_random_read_test(&config, &PreadEngine, &path, None, None).unwrap();
+        assert_eq!(result.stop_reason, StopReason::ByteLimit);
+        assert_eq!(result.latency.operations, 400);
+    }
+
+    #[test]
+    fn test_random_queue_slots_share_their_thread_histograms() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Operations(1000));
+        config.thread_count = 2;
+        config.queue_depth = 4;
+        let path = temp_dir.path().join("slots.tmp");
+        run_preparation(&config, &PreadEngine, &path, None, None).unwrap();
+
+        // Eight workers batch into two sets of histograms, losing no request
+        let result = run_random_read_test(&config, &PreadEngine, &path, None, None).unwrap();
+        assert_eq!(result.stop_reason, StopReason::OperationLimit);
+        assert_eq!(result.latency.operations, 1000);
+    }
+
+    #[test]
+    fn test_mixed_random_time_series_counts_every_request() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut config = limited_config(temp_dir.path(), TestLimit::Operations(600));
+        config.thread_count = 2;
+        config.record_time_series = true;
+        let path = temp_dir.path().join("series.tmp");
+        run_preparation(&config, &PreadEngine, &path, None, None).unwrap();
+
+        let mixed = run_mixed_random_test(&config, &PreadEngine, &path, None, None).unwrap();
+        let total = |result: &TestResult| {
+            result
+                .time_series
+                .iter()
+                .fold((0, 0), |(bytes, operations), sample| {
+                    (bytes + sample.bytes, operations + sample.operations)
+                })
+        };
+        assert!(!mixed.combined.time_series.is_empty());
+        assert_eq!(
+            total(&mixed.combined),
+            (
+                mixed.combined.total_bytes,
+                mixed.combined.latency.operations
+            )
+        );
+        assert_eq!(total(&mixed.read).1 + total(&mixed.write).1, 600);
+        assert!(mixed
+            .combined
+            .time_series
+            .iter()
+            .all(|sample| sample.operations == 0 || sample.mean_latency_us.is_some()));
+    }
 }
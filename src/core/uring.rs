@@ -0,0 +1,329 @@
+//! io_uring backend for Linux benchmarks
+//!
+//! A [`UringQueue`] keeps up to `depth` positioned reads or writes in flight on a
+//! single ring. Each in-flight slot owns one block-sized buffer that is registered
+//! with the kernel up front, so requests skip the per-call page pinning done for
+//! regular reads and writes.
+
+use crate::core::buffer::AlignedBuffer;
+use crate::core::BenchmarkError;
+use io_uring::{opcode, squeue, types, IoUring};
+use std::fs::File;
+use std::os::unix::io::AsRawFd;
+use std::time::{Duration, Instant};
+
+/// Direction of the requests submitted to a ring
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UringOp {
+    Read,
+    Write,
+}
+
+/// A single io_uring instance with one buffer per in-flight slot
+pub(crate) struct UringQueue {
+    // Declared before `storage` so the ring is torn down before the buffers it references
+    ring: IoUring,
+    storage: AlignedBuffer,
+    slot_offsets: Vec<usize>,
+    block_size: usize,
+    registered: bool,
+}
+
+impl UringQueue {
+    /// Create a ring with `depth` slots of `block_size` bytes, each aligned to `alignment`
+    ///
+    /// Buffer registration is attempted but not required: when the kernel refuses it
+    /// (e.g. a low `RLIMIT_MEMLOCK`), plain read/write opcodes are used instead.
+    pub(crate) fn new(
+        depth: usize,
+        block_size: usize,
+        alignment: usize,
+    ) -> Result<Self, BenchmarkError> {
+        let depth = depth.max(1);
+        let ring = IoUring::new(depth as u32)?;
+
+        // Carve aligned slots out of one aligned allocation
+        let alignment = alignment.max(1);
+        let stride = (block_size + alignment - 1) / alignment * alignment;
+        let mut storage = AlignedBuffer::new(stride * depth, alignment);
+        let slot_offsets: Vec<usize> = (0..depth).map(|slot| slot * stride).collect();
+
+        let iovecs: Vec<libc::iovec> = slot_offsets
+            .iter()
+            .map(|&offset| libc::iovec {
+                iov_base: storage[offset..].as_mut_ptr().cast(),
+                iov_len: block_size,
+            })
+            .collect();
+
+        // SAFETY: the iovecs point into `storage`, which lives as long as the ring
+        let registered = unsafe { ring.submitter().register_buffers(&iovecs) }.is_ok();
+
+        Ok(Self {
+            ring,
+            storage,
+            slot_offsets,
+            block_size,
+            registered,
+        })
+    }
+
+    /// Number of requests this queue keeps in flight
+    pub(crate) fn depth(&self) -> usize {
+        self.slot_offsets.len()
+    }
+
+    /// Fill every slot buffer, e.g. with the write test pattern
+    pub(crate) fn fill_buffers(&mut self, fill: impl Fn(&mut [u8])) {
+        for &offset in &self.slot_offsets {
+            fill(&mut self.storage[offset..offset + self.block_size]);
+        }
+    }
+
+    /// Submit requests until `next_request` runs dry or `keep_going` returns false
+    ///
+    /// `next_request` receives the buffer of the slot it is filling and yields
+    /// `(op, file_offset, length)` with `length <= block_size`, so reads and writes
+    /// can be mixed on one ring and writes can update their data before submission.
+    /// `on_complete` receives the direction, file offset, transferred bytes (the
+    /// slot buffer, cut to the byte count) and latency (submission to completion)
+    /// of every finished request.
+    /// All in-flight requests are drained before returning, even when one of them
+    /// failed; the first failure is then returned.
+    pub(crate) fn run(
+        &mut self,
+        file: &File,
+        mut next_request: impl FnMut(&mut [u8]) -> Option<(UringOp, u64, usize)>,
+        mut keep_going: impl FnMut() -> bool,
+        mut on_complete: impl FnMut(UringOp, u64, &[u8], Duration),
+    ) -> Result<(), BenchmarkError> {
+        let fd = types::Fd(file.as_raw_fd());
+        let mut free_slots: Vec<usize> = (0..self.depth()).rev().collect();
+        let mut slot_ops = vec![UringOp::Read; self.depth()];
+        let mut slot_file_offsets = vec![0u64; self.depth()];
+        let mut slot_started = vec![Instant::now(); self.depth()];
+        let mut in_flight = 0usize;
+        let mut submitting = true;
+        let mut first_error: Option<std::io::Error> = None;
+
+        loop {
+            if submitting && !keep_going() {
+                submitting = false;
+            }
+
+            while submitting {
+                let Some(slot) = free_slots.pop() else {
+                    break;
+                };
+                let start = self.slot_offsets[slot];
+                let buffer = &mut self.storage[start..start + self.block_size];
+                let Some((op, offset, len)) = next_request(buffer) else {
+                    free_slots.push(slot);
+                    submitting = false;
+                    break;
+                };
+
+                slot_ops[slot] = op;
+                slot_file_offsets[slot] = offset;
+                slot_started[slot] = Instant::now();
+                let entry = self.build_entry(fd, op, slot, offset, len.min(self.block_size));
+                // SAFETY: the slot buffer stays untouched until its completion is reaped
+                unsafe { self.ring.submission().push(&entry) }.map_err(|_| {
+                    BenchmarkError::IoError(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        "io_uring submission queue full",
+                    ))
+                })?;
+                in_flight += 1;
+            }
+
+            if in_flight == 0 {
+                break;
+            }
+
+            self.ring.submit_and_wait(1)?;
+
+            for cqe in self.ring.completion() {
+                in_flight -= 1;
+                let slot = cqe.user_data() as usize;
+                free_slots.push(slot);
+
+                let result = cqe.result();
+                if result < 0 {
+                    if first_error.is_none() {
+                        first_error = Some(std::io::Error::from_raw_os_error(-result));
+                    }
+                    submitting = false;
+                } else {
+                    let start = self.slot_offsets[slot];
+                    let len = (result as usize).min(self.block_size);
+                    on_complete(
+                        slot_ops[slot],
+                        slot_file_offsets[slot],
+                        &self.storage[start..start + len],
+                        slot_started[slot].elapsed(),
+                    );
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(BenchmarkError::IoError(e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Build the submission entry for one slot
+    fn build_entry(
+        &mut self,
+        fd: types::Fd,
+        op: UringOp,
+        slot: usize,
+        offset: u64,
+        len: usize,
+    ) -> squeue::Entry {
+        let buffer = self.storage[self.slot_offsets[slot]..].as_mut_ptr();
+        let len = len as u32;
+
+        let entry = match (op, self.registered) {
+            (UringOp::Read, true) => opcode::ReadFixed::new(fd, buffer, len, slot as u16)
+                .offset(offset)
+                .build(),
+            (UringOp::Write, true) => opcode::WriteFixed::new(fd, buffer, len, slot as u16)
+                .offset(offset)
+                .build(),
+            (UringOp::Read, false) => opcode::Read::new(fd, buffer, len).offset(offset).build(),
+            (UringOp::Write, false) => opcode::Write::new(fd, buffer, len).offset(offset).build(),
+        };
+
+        entry.user_data(slot as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::OpenOptions;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_uring_write_then_read_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("uring.dat");
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+
+        let block_size = 4096;
+        let block_count = 16u64;
+
+        let mut queue = UringQueue::new(4, block_size, 4096).unwrap();
+        assert_eq!(queue.depth(), 4);
+        queue.fill_buffers(|buf| buf.fill(0xAB));
+
+        let mut next_block = 0u64;
+        let mut written = 0usize;
+        queue
+            .run(
+                &file,
+                |_| {
+                    (next_block < block_count).then(|| {
+                        next_block += 1;
+                        (
+                            UringOp::Write,
+                            (next_block - 1) * block_size as u64,
+                            block_size,
+                        )
+                    })
+                },
+                || true,
+                |_, _, data, _| written += data.len(),
+            )
+            .unwrap();
+        assert_eq!(written as u64, block_count * block_size as u64);
+
+        let mut next_block = 0u64;
+        let mut read = 0usize;
+        let mut queue = UringQueue::new(4, block_size, 4096).unwrap();
+        queue
+            .run(
+                &file,
+                |_| {
+                    (next_block < block_count).then(|| {
+                        next_block += 1;
+                        (
+                            UringOp::Read,
+                            (next_block - 1) * block_size as u64,
+                            block_size,
+                        )
+                    })
+                },
+                || true,
+                |_, _, data, _| read += data.len(),
+            )
+            .unwrap();
+        assert_eq!(read, written);
+        assert!(std::fs::read(&path).unwrap().iter().all(|&b| b == 0xAB));
+    }
+
+    #[test]
+    fn test_uring_stops_when_told() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = File::create(temp_dir.path().join("stop.dat")).unwrap();
+
+        let mut queue = UringQueue::new(2, 512, 512).unwrap();
+        let mut completions = 0;
+        queue
+            .run(
+                &file,
+                |_| Some((UringOp::Write, 0, 512)),
+                || false,
+                |_, _, _, _| completions += 1,
+            )
+            .unwrap();
+        assert_eq!(completions, 0);
+    }
+
+    #[test]
+    fn test_uring_mixed_ops_report_direction() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mixed.dat");
+        std::fs::write(&path, vec![0u8; 8 * 4096]).unwrap();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+
+        let mut queue = UringQueue::new(4, 4096, 4096).unwrap();
+        let mut issued = 0u64;
+        let (mut reads, mut writes) = (0usize, 0usize);
+        queue
+            .run(
+                &file,
+                |_| {
+                    (issued < 8).then(|| {
+                        issued += 1;
+                        let op = if issued % 2 == 0 {
+                            UringOp::Read
+                        } else {
+                            UringOp::Write
+                        };
+                        (op, (issued - 1) * 4096, 4096)
+                    })
+                },
+                || true,
+                |op, _, data, _| match op {
+                    UringOp::Read => reads += data.len(),
+                    UringOp::Write => writes += data.len(),
+                },
+            )
+            .unwrap();
+        assert_eq!(reads, 4 * 4096);
+        assert_eq!(writes, 4 * 4096);
+    }
+}
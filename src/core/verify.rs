@@ -0,0 +1,448 @@
+//! Data integrity verification of the test file
+//!
+//! In verify mode every 512-byte sector the write tests put on disk starts with a
+//! header holding the sector's file offset, the generation of the test that wrote
+//! it and a checksum over the whole sector. The read tests check every sector they
+//! read against the generation last written there, which catches corrupted data,
+//! blocks returned from the wrong location and stale blocks left over from earlier
+//! writes, e.g. by a misbehaving RAID controller.
+
+use crate::core::pattern::{TestDataPattern, STAMP_SECTOR_SIZE};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Granularity at which written data is sealed and checked
+pub const VERIFY_SECTOR_SIZE: usize = STAMP_SECTOR_SIZE;
+
+/// Corruption events kept per test; further corrupted sectors are only counted
+pub const MAX_CORRUPTION_EVENTS: usize = 1000;
+
+/// Position of the checksum in the sector header, which holds the file offset,
+/// generation and checksum as little-endian 64-bit values
+const CHECKSUM_RANGE: std::ops::Range<usize> = 16..24;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// What was wrong with a sector read back from the test file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CorruptionKind {
+    /// The sector's contents do not match its checksum
+    ChecksumMismatch,
+    /// The sector is intact but was written at another offset
+    Misplaced { found_offset: u64 },
+    /// The sector is intact but was written by another generation; an older
+    /// generation means the device returned stale data
+    WrongGeneration { expected: u64, found: u64 },
+    /// The sector could not be read back at all
+    Unreadable,
+}
+
+impl std::fmt::Display for CorruptionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CorruptionKind::ChecksumMismatch => write!(f, "checksum mismatch"),
+            CorruptionKind::Misplaced { found_offset } => {
+                write!(f, "sector written at offset {}", found_offset)
+            }
+            CorruptionKind::WrongGeneration { expected, found } => {
+                write!(f, "generation {} instead of {}", found, expected)
+            }
+            CorruptionKind::Unreadable => write!(f, "unreadable"),
+        }
+    }
+}
+
+/// A corrupted sector found by a read test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorruptionEvent {
+    /// File offset of the sector
+    pub offset: u64,
+    pub kind: CorruptionKind,
+}
+
+/// Corrupted sectors found since the log was last taken
+#[derive(Debug, Default)]
+struct CorruptionLog {
+    events: Vec<CorruptionEvent>,
+    total: u64,
+}
+
+/// Verify test data integrity
+///
+/// Besides checking whole files against a plain [`TestDataPattern`], a verifier
+/// created [`with_file_size`](Self::with_file_size) remembers the generation last
+/// written to each sector of the test file. Write tests [`seal`](Self::seal) their
+/// blocks and [`record_write`](Self::record_write) them; read tests
+/// [`check`](Self::check) what they read and collect the corruption found with
+/// [`take_corruption`](Self::take_corruption). Its methods take `&self`, so one
+/// verifier can be shared by the workers of a test.
+#[derive(Debug)]
+pub struct TestDataVerifier {
+    pattern: TestDataPattern,
+    /// Generation last written to each sector; zero while its contents are unknown
+    generations: Vec<AtomicU64>,
+    /// Last generation handed out by `begin_generation`
+    generation: AtomicU64,
+    log: Mutex<CorruptionLog>,
+}
+
+impl TestDataVerifier {
+    pub fn new(pattern: TestDataPattern) -> Self {
+        Self {
+            pattern,
+            generations: Vec::new(),
+            generation: AtomicU64::new(0),
+            log: Mutex::new(CorruptionLog::default()),
+        }
+    }
+
+    /// Track the generations of a test file of `file_size` bytes
+    pub fn with_file_size(mut self, file_size: u64) -> Self {
+        let sector_size = VERIFY_SECTOR_SIZE as u64;
+        let sectors = ((file_size + sector_size - 1) / sector_size) as usize;
+        self.generations = (0..sectors).map(|_| AtomicU64::new(0)).collect();
+        self
+    }
+
+    /// Start a new generation of writes, returning its number
+    ///
+    /// Every write test uses its own generation, so sectors it did not overwrite
+    /// can be told apart from the ones it did.
+    pub fn begin_generation(&self) -> u64 {
+        self.generation.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Forget what was written, e.g. because the test file was recreated
+    pub fn reset(&self) {
+        for generation in &self.generations {
+            generation.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Seal every sector of a buffer about to be written at `offset`
+    ///
+    /// Each sector starts with its file offset, `generation` and a checksum over
+    /// the whole sector. Sectors the buffer only partly covers are left alone.
+    pub fn seal(buffer: &mut [u8], offset: u64, generation: u64) {
+        for (start, sector_offset) in whole_sectors(offset, buffer.len()) {
+            let sector = &mut buffer[start..start + VERIFY_SECTOR_SIZE];
+            sector[..8].copy_from_slice(&sector_offset.to_le_bytes());
+            sector[8..16].copy_from_slice(&generation.to_le_bytes());
+            let checksum = sector_checksum(sector);
+            sector[CHECKSUM_RANGE].copy_from_slice(&checksum.to_le_bytes());
+        }
+    }
+
+    /// Remember that `len` sealed bytes of `generation` were written at `offset`
+    ///
+    /// Sectors the write only partly covered hold a mix of data afterwards and are
+    /// no longer checked.
+    pub fn record_write(&self, offset: u64, len: usize, generation: u64) {
+        let sector_size = VERIFY_SECTOR_SIZE as u64;
+        let end = offset + len as u64;
+        let first = offset / sector_size;
+        let last = (end + sector_size - 1) / sector_size;
+        for index in first..last {
+            let Some(slot) = self.generations.get(index as usize) else {
+                break;
+            };
+            let sector_start = index * sector_size;
+            let whole = sector_start >= offset && sector_start + sector_size <= end;
+            slot.store(if whole { generation } else { 0 }, Ordering::Relaxed);
+        }
+    }
+
+    /// Check the sectors of `data`, read from `offset`, against what was written there
+    ///
+    /// Sectors whose contents are unknown are skipped. Corruption found is logged
+    /// for [`take_corruption`](Self::take_corruption); the number of corrupted
+    /// sectors is returned.
+    pub fn check(&self, data: &[u8], offset: u64) -> u64 {
+        let mut corrupted = 0;
+        for (start, sector_offset) in whole_sectors(offset, data.len()) {
+            let expected = self
+                .generations
+                .get((sector_offset / VERIFY_SECTOR_SIZE as u64) as usize)
+                .map_or(0, |generation| generation.load(Ordering::Relaxed));
+            if expected == 0 {
+                continue;
+            }
+
+            let sector = &data[start..start + VERIFY_SECTOR_SIZE];
+            if let Some(kind) = check_sector(sector, sector_offset, expected) {
+                corrupted += 1;
+                self.log_corruption(CorruptionEvent {
+                    offset: sector_offset,
+                    kind,
+                });
+            }
+        }
+        corrupted
+    }
+
+    /// Check sealed sectors that should all carry `generation`, without tracking writes
+    ///
+    /// For checks too large to remember a generation per sector, such as filling a
+    /// whole device. Returns the number of whole sectors of `data`, read from
+    /// `offset`, that were checked, and the corrupted ones.
+    pub fn check_sealed(data: &[u8], offset: u64, generation: u64) -> (u64, Vec<CorruptionEvent>) {
+        let mut checked = 0;
+        let mut events = Vec::new();
+        for (start, sector_offset) in whole_sectors(offset, data.len()) {
+            checked += 1;
+            let sector = &data[start..start + VERIFY_SECTOR_SIZE];
+            if let Some(kind) = check_sector(sector, sector_offset, generation) {
+                events.push(CorruptionEvent {
+                    offset: sector_offset,
+                    kind,
+                });
+            }
+        }
+        (checked, events)
+    }
+
+    /// Take the corrupted sector count and events logged since the last call
+    pub fn take_corruption(&self) -> (u64, Vec<CorruptionEvent>) {
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        let log = std::mem::take(&mut *log);
+        (log.total, log.events)
+    }
+
+    fn log_corruption(&self, event: CorruptionEvent) {
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        log.total += 1;
+        if log.events.len() < MAX_CORRUPTION_EVENTS {
+            log.events.push(event);
+        }
+    }
+
+    /// Verify that file contains expected pattern
+    pub fn verify_file(&self, file_path: &Path) -> std::io::Result<bool> {
+        use std::io::Read;
+
+        let mut file = File::open(file_path)?;
+        let mut buffer = vec![0u8; 64 * 1024]; // 64KB buffer
+        let mut byte_counter = 0u8;
+
+        loop {
+            let bytes_read = file.read(&mut buffer)?;
+            if bytes_read == 0 {
+                break;
+            }
+
+            for &byte in &buffer[..bytes_read] {
+                let expected = match &self.pattern {
+                    TestDataPattern::Zeros => 0x00,
+                    TestDataPattern::Ones => 0xFF,
+                    TestDataPattern::Alternating => {
+                        if byte_counter % 2 == 0 {
+                            0xAA
+                        } else {
+                            0x55
+                        }
+                    }
+                    TestDataPattern::Sequential => byte_counter,
+                    TestDataPattern::RandomSeeded(_)
+                    | TestDataPattern::Random
+                    | TestDataPattern::Compressible(_)
+                    | TestDataPattern::Unique => {
+                        // Can't verify random data without regenerating
+                        byte_counter = byte_counter.wrapping_add(1);
+                        continue;
+                    }
+                };
+
+                if byte != expected {
+                    return Ok(false);
+                }
+                byte_counter = byte_counter.wrapping_add(1);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// Sectors wholly inside `len` bytes at file `offset`, as buffer index and file offset
+fn whole_sectors(offset: u64, len: usize) -> impl Iterator<Item = (usize, u64)> {
+    let sector_size = VERIFY_SECTOR_SIZE as u64;
+    let first = (offset + sector_size - 1) / sector_size * sector_size;
+    let end = offset + len as u64;
+    (first..end)
+        .step_by(VERIFY_SECTOR_SIZE)
+        .take_while(move |&sector_offset| sector_offset + sector_size <= end)
+        .map(move |sector_offset| ((sector_offset - offset) as usize, sector_offset))
+}
+
+/// Checksum of a sector, skipping the checksum field itself
+fn sector_checksum(sector: &[u8]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    for (index, word) in sector.chunks_exact(8).enumerate() {
+        if index * 8 == CHECKSUM_RANGE.start {
+            continue;
+        }
+        let word = u64::from_le_bytes(word.try_into().unwrap_or_default());
+        hash = (hash ^ word).wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Check one sector read from `offset` that should hold `expected_generation`
+fn check_sector(sector: &[u8], offset: u64, expected_generation: u64) -> Option<CorruptionKind> {
+    let field = |at: usize| u64::from_le_bytes(sector[at..at + 8].try_into().unwrap_or_default());
+    if field(CHECKSUM_RANGE.start) != sector_checksum(sector) {
+        return Some(CorruptionKind::ChecksumMismatch);
+    }
+    let found_offset = field(0);
+    if found_offset != offset {
+        return Some(CorruptionKind::Misplaced { found_offset });
+    }
+    let found = field(8);
+    (found != expected_generation).then_some(CorruptionKind::WrongGeneration {
+        expected: expected_generation,
+        found,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECTOR: usize = VERIFY_SECTOR_SIZE;
+
+    fn sealed_block(offset: u64, generation: u64) -> Vec<u8> {
+        let mut block = vec![0u8; 4 * SECTOR];
+        TestDataPattern::Sequential.fill(&mut block);
+        TestDataVerifier::seal(&mut block, offset, generation);
+        block
+    }
+
+    fn verifier() -> TestDataVerifier {
+        TestDataVerifier::new(TestDataPattern::Sequential).with_file_size(64 * SECTOR as u64)
+    }
+
+    #[test]
+    fn test_sealed_blocks_pass_verification() {
+        let verifier = verifier();
+        let generation = verifier.begin_generation();
+        let block = sealed_block(8 * SECTOR as u64, generation);
+        verifier.record_write(8 * SECTOR as u64, block.len(), generation);
+
+        assert_eq!(verifier.check(&block, 8 * SECTOR as u64), 0);
+        assert_eq!(verifier.take_corruption(), (0, Vec::new()));
+    }
+
+    #[test]
+    fn test_detects_corrupted_misplaced_and_stale_sectors() {
+        let verifier = verifier();
+        let old = verifier.begin_generation();
+        let stale = sealed_block(0, old);
+        verifier.record_write(0, stale.len(), old);
+        let new = verifier.begin_generation();
+        verifier.record_write(0, stale.len(), new);
+
+        // Flipped bit, a block read from elsewhere and the previous generation
+        let mut flipped = sealed_block(0, new);
+        flipped[SECTOR + 100] ^= 0x01;
+        assert_eq!(verifier.check(&flipped, 0), 1);
+        let misplaced = sealed_block(16 * SECTOR as u64, new);
+        verifier.record_write(16 * SECTOR as u64, misplaced.len(), new);
+        assert_eq!(verifier.check(&misplaced[..SECTOR], 0), 1);
+        assert_eq!(verifier.check(&stale[SECTOR..2 * SECTOR], SECTOR as u64), 1);
+
+        let (total, events) = verifier.take_corruption();
+        assert_eq!(total, 3);
+        assert_eq!(
+            events,
+            vec![
+                CorruptionEvent {
+                    offset: SECTOR as u64,
+                    kind: CorruptionKind::ChecksumMismatch,
+                },
+                CorruptionEvent {
+                    offset: 0,
+                    kind: CorruptionKind::Misplaced {
+                        found_offset: 16 * SECTOR as u64
+                    },
+                },
+                CorruptionEvent {
+                    offset: SECTOR as u64,
+                    kind: CorruptionKind::WrongGeneration {
+                        expected: new,
+                        found: old
+                    },
+                },
+            ]
+        );
+        assert_eq!(verifier.take_corruption().0, 0);
+    }
+
+    #[test]
+    fn test_check_sealed_without_tracking() {
+        let mut block = sealed_block(4 * SECTOR as u64, 7);
+        assert_eq!(
+            TestDataVerifier::check_sealed(&block, 4 * SECTOR as u64, 7),
+            (4, Vec::new())
+        );
+
+        // A device that wraps around returns the block for another offset
+        let (checked, events) = TestDataVerifier::check_sealed(&block, 0, 7);
+        assert_eq!(checked, 4);
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events[0].kind,
+            CorruptionKind::Misplaced {
+                found_offset: 4 * SECTOR as u64
+            }
+        );
+
+        block[3 * SECTOR] ^= 0xFF;
+        let (_, events) = TestDataVerifier::check_sealed(&block, 4 * SECTOR as u64, 7);
+        assert_eq!(
+            events,
+            vec![CorruptionEvent {
+                offset: 7 * SECTOR as u64,
+                kind: CorruptionKind::ChecksumMismatch
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unknown_sectors_are_skipped() {
+        let verifier = verifier();
+        let zeros = vec![0u8; 4 * SECTOR];
+        assert_eq!(verifier.check(&zeros, 0), 0);
+
+        // A write covering half a sector leaves it unknown
+        let generation = verifier.begin_generation();
+        verifier.record_write(0, 4 * SECTOR, generation);
+        verifier.record_write(SECTOR as u64 / 2, SECTOR, generation);
+        assert_eq!(verifier.check(&zeros[..SECTOR], 0), 0);
+        assert_eq!(verifier.check(&zeros[..SECTOR], SECTOR as u64), 0);
+        assert_eq!(verifier.check(&zeros[..SECTOR], 2 * SECTOR as u64), 1);
+
+        verifier.reset();
+        assert_eq!(verifier.check(&zeros, 0), 0);
+    }
+
+    #[test]
+    fn test_corruption_events_are_capped() {
+        let verifier = TestDataVerifier::new(TestDataPattern::Zeros)
+            .with_file_size(2 * MAX_CORRUPTION_EVENTS as u64 * SECTOR as u64);
+        let len = 2 * MAX_CORRUPTION_EVENTS * SECTOR;
+        verifier.record_write(0, len, verifier.begin_generation());
+
+        assert_eq!(
+            verifier.check(&vec![0xFFu8; len], 0),
+            2 * MAX_CORRUPTION_EVENTS as u64
+        );
+        let (total, events) = verifier.take_corruption();
+        assert_eq!(total, 2 * MAX_CORRUPTION_EVENTS as u64);
+        assert_eq!(events.len(), MAX_CORRUPTION_EVENTS);
+    }
+}
@@ -12,18 +12,26 @@ pub mod test_utils;
 
 // Re-export core types for library consumers
 pub use core::{
-    run_benchmark, BenchmarkConfig, BenchmarkError, BenchmarkResults, NoOpProgressCallback,
-    ProgressCallback, ProgressEvent, ProgressReporter, RealTimeStatsTracker, StatisticsCollector,
-    TestProgressCallback, TestResult,
+    run_benchmark, run_benchmark_repeated, run_benchmark_repeated_with_engine,
+    run_benchmark_with_engine, run_capacity_check, AggregatedResults, BenchmarkConfig,
+    BenchmarkError, BenchmarkHandle, BenchmarkResults, BenchmarkTest, CancellationToken,
+    CapacityCheckConfig, CapacityCheckResults, CapacitySample, ChannelProgressCallback,
+    CorruptionEvent, CorruptionKind, FileSizeShare, IntervalSample, IoBackend, IoEngine,
+    IoEngineKind, IoHandle, IoOpenOptions, LatencyStats, MemoryBandwidthPoint, MemoryStreamResult,
+    MetadataOperation, MetadataResult, MixedRunStatistics, MixedTestResult, NoOpProgressCallback,
+    ProgressCallback, ProgressEvent, ProgressReporter, ProgressUpdate, RealTimeStatsTracker,
+    RunStatistics, SmallFilesResult, StatisticsCollector, StopReason, StreamKernel,
+    SustainedWriteResult, SyncMethod, TestDataPattern, TestDataVerifier, TestLimit, TestPhase,
+    TestProgressCallback, TestResult, ThreadPinning, ThroughputPhase, ThroughputSample,
 };
 
-pub use platform::{DeviceType, PlatformError, PlatformOps, StorageDevice};
+pub use platform::{DeviceType, DirectIoAlignment, PlatformError, PlatformOps, StorageDevice};
 
 /// Main library interface for running disk speed benchmarks
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,no_run
 /// use disk_speed_test::{BenchmarkConfig, run_benchmark};
 /// use std::path::PathBuf;
 ///
@@ -36,10 +44,14 @@ pub use platform::{DeviceType, PlatformError, PlatformOps, StorageDevice};
 ///     disable_os_cache: true,
 ///     disable_direct_io: false, // Enable direct I/O for performance
 ///     file_size_mb: 1024, // 1GB
+///     queue_depth: 32,    // QD32 per thread for random tests
+///     ..Default::default()
 /// };
 ///
 /// let results = run_benchmark(config, None)?;
-/// println!("Sequential Write: {:.2} MB/s", results.sequential_write.avg_speed_mbps);
+/// if let Some(result) = &results.sequential_write {
+///     println!("Sequential Write: {:.2} MB/s", result.avg_speed_mbps);
+/// }
 /// # Ok(())
 /// # }
 /// ```
@@ -26,6 +26,7 @@ use disk_speed_test::BenchmarkError;
 /// - 3: Permission denied
 /// - 4: Insufficient disk space
 /// - 5: Test interrupted
+/// - 6: Data corruption detected by --verify or capacity-check
 fn main() {
     // Set up panic handler for better error reporting
     std::panic::set_hook(Box::new(|panic_info| {
@@ -34,19 +35,6 @@ fn main() {
         process::exit(128);
     }));
 
-    // Handle SIGINT (Ctrl+C) gracefully
-    #[cfg(unix)]
-    {
-        extern "C" fn handle_sigint(_: i32) {
-            eprintln!("\nBenchmark interrupted by user");
-            process::exit(130); // Standard exit code for SIGINT
-        }
-
-        unsafe {
-            libc::signal(libc::SIGINT, handle_sigint as usize);
-        }
-    }
-
     // Check for help or version flags early
     let args: Vec<String> = env::args().collect();
     if args.len() > 1 {
@@ -77,6 +65,7 @@ fn main() {
                 Some(BenchmarkError::PermissionDenied(_)) => 3,
                 Some(BenchmarkError::InsufficientSpace { .. }) => 4,
                 Some(BenchmarkError::TestInterrupted(_)) => 5,
+                Some(BenchmarkError::DataCorruption(_)) => 6,
                 Some(BenchmarkError::ConfigurationError(_)) => 1,
                 Some(BenchmarkError::IoError(_)) => 1,
                 None => 1, // General error
@@ -1,6 +1,6 @@
 //! Linux-specific platform operations
 
-use super::{DeviceType, PlatformError, PlatformOps, StorageDevice};
+use super::{DeviceType, DirectIoAlignment, PlatformError, PlatformOps, StorageDevice};
 use libc::{fsync, sync, O_DIRECT, O_SYNC};
 use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
@@ -12,22 +12,73 @@ use std::path::{Path, PathBuf};
 pub struct LinuxPlatform;
 
 impl LinuxPlatform {
-    /// Get the logical sector size for alignment (typically 512 bytes)
-    const SECTOR_SIZE: u64 = 512;
-
-    /// Align a size to sector boundaries for direct I/O compatibility
-    fn align_to_sector_size(size: u64) -> u64 {
-        let remainder = size % Self::SECTOR_SIZE;
-        if remainder == 0 {
-            size
-        } else {
-            size + (Self::SECTOR_SIZE - remainder)
+    /// Query direct I/O alignment with `statx`, relative to `dirfd` as in `statx(2)`
+    ///
+    /// Kernels since 6.1 report the exact buffer and offset alignment for regular
+    /// files (`STATX_DIOALIGN`). Older kernels, and directories, fall back to the
+    /// logical block size of the underlying device from sysfs.
+    fn statx_direct_io_alignment(
+        dirfd: libc::c_int,
+        path: &std::ffi::CStr,
+        flags: libc::c_int,
+    ) -> Result<DirectIoAlignment, PlatformError> {
+        use std::mem::MaybeUninit;
+
+        let mut stx = MaybeUninit::<libc::statx>::zeroed();
+        let result = unsafe {
+            libc::statx(
+                dirfd,
+                path.as_ptr(),
+                flags,
+                libc::STATX_DIOALIGN,
+                stx.as_mut_ptr(),
+            )
+        };
+
+        if result != 0 {
+            return Err(PlatformError::IoError(std::io::Error::last_os_error()));
         }
+
+        let stx = unsafe { stx.assume_init() };
+
+        if stx.stx_mask & libc::STATX_DIOALIGN != 0 && stx.stx_dio_offset_align != 0 {
+            return Ok(DirectIoAlignment {
+                memory: stx.stx_dio_mem_align as usize,
+                offset: stx.stx_dio_offset_align as usize,
+            });
+        }
+
+        Ok(
+            Self::logical_block_size(stx.stx_dev_major, stx.stx_dev_minor)
+                .map(|size| DirectIoAlignment {
+                    memory: size,
+                    offset: size,
+                })
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Query direct I/O alignment for an open file
+    fn file_direct_io_alignment(file: &File) -> Result<DirectIoAlignment, PlatformError> {
+        let fd = std::os::unix::io::AsRawFd::as_raw_fd(file);
+        Self::statx_direct_io_alignment(fd, &std::ffi::CString::default(), libc::AT_EMPTY_PATH)
     }
 
-    /// Ensure block size is compatible with direct I/O (multiple of sector size)
-    pub fn align_block_size_for_direct_io(block_size: usize) -> usize {
-        Self::align_to_sector_size(block_size as u64) as usize
+    /// Logical block size of the block device with the given device number
+    ///
+    /// Partitions have no `queue` directory of their own, so the parent disk is
+    /// checked as well.
+    pub(crate) fn logical_block_size(major: u32, minor: u32) -> Option<usize> {
+        let sys_dev_path = format!("/sys/dev/block/{}:{}", major, minor);
+
+        [
+            format!("{}/queue/logical_block_size", sys_dev_path),
+            format!("{}/../queue/logical_block_size", sys_dev_path),
+        ]
+        .iter()
+        .find_map(|path| Self::read_sys_file(path).ok())
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .filter(|size| size.is_power_of_two())
     }
 
     /// Parse /proc/mounts to get mounted filesystems
@@ -223,7 +274,7 @@ impl PlatformOps for LinuxPlatform {
             let fs_stats = Self::get_filesystem_stats(&mount.mount_point)?;
 
             // Create a human-readable name
-            let name = if mount.mount_point == PathBuf::from("/") {
+            let name = if mount.mount_point == Path::new("/") {
                 format!("Root Filesystem ({})", mount.device)
             } else if let Some(mount_name) = mount.mount_point.file_name() {
                 format!("{} ({})", mount_name.to_string_lossy(), mount.device)
@@ -287,7 +338,8 @@ impl PlatformOps for LinuxPlatform {
             .write(true)
             .truncate(true)
             .custom_flags(O_DIRECT | O_SYNC)
-            .open(path) {
+            .open(path)
+        {
             Ok(file) => file,
             Err(e) if e.raw_os_error() == Some(libc::EINVAL) => {
                 // O_DIRECT not supported on this filesystem
@@ -296,8 +348,9 @@ impl PlatformOps for LinuxPlatform {
             Err(e) => return Err(PlatformError::IoError(e)),
         };
 
-        // Set file size - ensure it's aligned to sector boundaries for direct I/O
-        let aligned_size = Self::align_to_sector_size(size);
+        // Set file size - ensure it's aligned to the device's direct I/O offset alignment
+        let alignment = Self::file_direct_io_alignment(&file).unwrap_or_default();
+        let aligned_size = alignment.align_len(size as usize) as u64;
         file.set_len(aligned_size).map_err(PlatformError::IoError)?;
 
         Ok(file)
@@ -306,13 +359,10 @@ impl PlatformOps for LinuxPlatform {
     fn open_direct_io_file(path: &Path, write: bool) -> Result<File, PlatformError> {
         let mut options = OpenOptions::new();
 
-        if write {
-            options.write(true);
-        } else {
-            options.read(true);
-        }
+        // Write handles stay readable so mixed workloads can share one descriptor
+        options.read(true).write(write);
 
-        // Try to open file with O_DIRECT 
+        // Try to open file with O_DIRECT
         let file = match options.custom_flags(O_DIRECT | O_SYNC).open(path) {
             Ok(file) => file,
             Err(e) if e.raw_os_error() == Some(libc::EINVAL) => {
@@ -345,6 +395,50 @@ impl PlatformOps for LinuxPlatform {
 
         Ok(())
     }
+
+    fn direct_io_alignment(path: &Path) -> Result<DirectIoAlignment, PlatformError> {
+        use std::ffi::CString;
+
+        let path_cstr = CString::new(path.to_string_lossy().as_bytes()).map_err(|e| {
+            PlatformError::IoError(std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
+        })?;
+
+        Self::statx_direct_io_alignment(libc::AT_FDCWD, &path_cstr, 0)
+    }
+
+    fn pin_current_thread(cpus: &[usize]) -> Result<(), PlatformError> {
+        // SAFETY: cpu_set_t is a plain bit mask, for which all zeros is the empty set
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        for &cpu in cpus {
+            if cpu >= libc::CPU_SETSIZE as usize {
+                return Err(PlatformError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("CPU {} is out of range", cpu),
+                )));
+            }
+            // SAFETY: cpu was checked to be within the set
+            unsafe { libc::CPU_SET(cpu, &mut set) };
+        }
+
+        // SAFETY: set is a valid cpu_set_t of the size passed; pid 0 is the calling thread
+        let result =
+            unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) };
+        if result != 0 {
+            return Err(PlatformError::IoError(std::io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+
+    fn numa_node_cpus(node: usize) -> Result<Vec<usize>, PlatformError> {
+        let path = format!("/sys/devices/system/node/node{}/cpulist", node);
+        let list = Self::read_sys_file(&path).map_err(|e| {
+            PlatformError::IoError(std::io::Error::new(
+                e.kind(),
+                format!("NUMA node {} not found: {}", node, e),
+            ))
+        })?;
+        super::parse_cpu_list(&list)
+    }
 }
 
 /// Information about a mounted filesystem
@@ -449,6 +543,30 @@ mod tests {
         assert!(write_result.is_ok());
     }
 
+    #[test]
+    fn test_direct_io_alignment() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("test_alignment.dat");
+        std::fs::write(&test_file, vec![0u8; 4096]).unwrap();
+
+        // Regular files and directories both report usable power-of-two alignments
+        for path in [test_file.as_path(), temp_dir.path()] {
+            let alignment = LinuxPlatform::direct_io_alignment(path).unwrap();
+            assert!(alignment.memory.is_power_of_two());
+            assert!(alignment.offset.is_power_of_two());
+        }
+
+        let file = File::open(&test_file).unwrap();
+        let from_fd = LinuxPlatform::file_direct_io_alignment(&file).unwrap();
+        assert_eq!(
+            from_fd,
+            LinuxPlatform::direct_io_alignment(&test_file).unwrap()
+        );
+
+        // Missing paths are reported as errors
+        assert!(LinuxPlatform::direct_io_alignment(&temp_dir.path().join("missing")).is_err());
+    }
+
     #[test]
     fn test_sync_file_system() {
         let temp_dir = TempDir::new().unwrap();
@@ -519,6 +637,30 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_pin_current_thread() {
+        // Run on a thread of its own so the test runner's threads keep their affinity
+        std::thread::spawn(|| {
+            // SAFETY: sched_getcpu has no preconditions
+            let cpu = unsafe { libc::sched_getcpu() };
+            assert!(cpu >= 0);
+            LinuxPlatform::pin_current_thread(&[cpu as usize]).unwrap();
+            assert_eq!(unsafe { libc::sched_getcpu() }, cpu);
+        })
+        .join()
+        .unwrap();
+
+        assert!(LinuxPlatform::pin_current_thread(&[usize::MAX]).is_err());
+    }
+
+    #[test]
+    fn test_numa_node_cpus() {
+        if std::path::Path::new("/sys/devices/system/node/node0").exists() {
+            assert!(!LinuxPlatform::numa_node_cpus(0).unwrap().is_empty());
+        }
+        assert!(LinuxPlatform::numa_node_cpus(100_000).is_err());
+    }
+
     #[test]
     fn test_platform_integration() {
         // Test that the Linux platform can be called through the platform abstraction
@@ -11,6 +11,8 @@ mod tests {
     #[cfg(target_os = "linux")]
     use crate::platform::PlatformOps;
     #[cfg(target_os = "linux")]
+    use std::fs;
+    #[cfg(target_os = "linux")]
     use std::path::Path;
 
     #[test]
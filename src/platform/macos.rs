@@ -100,17 +100,12 @@ impl PlatformOps for MacOsPlatform {
     }
 
     fn open_direct_io_file(path: &Path, write: bool) -> Result<File, PlatformError> {
-        let file = if write {
-            OpenOptions::new()
-                .write(true)
-                .open(path)
-                .map_err(PlatformError::IoError)?
-        } else {
-            OpenOptions::new()
-                .read(true)
-                .open(path)
-                .map_err(PlatformError::IoError)?
-        };
+        // Write handles stay readable so mixed workloads can share one descriptor
+        let file = OpenOptions::new()
+            .read(true)
+            .write(write)
+            .open(path)
+            .map_err(PlatformError::IoError)?;
 
         // Apply F_NOCACHE flag to disable OS caching
         let fd = file.as_raw_fd();
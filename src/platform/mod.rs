@@ -120,6 +120,41 @@ pub struct StorageDevice {
     pub device_type: DeviceType,
 }
 
+/// Alignment that direct I/O requires for buffers and file offsets
+///
+/// Both values are powers of two. Transfer lengths must be multiples of `offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectIoAlignment {
+    /// Required alignment of the memory address of I/O buffers, in bytes
+    pub memory: usize,
+    /// Required alignment of file offsets and transfer lengths, in bytes
+    pub offset: usize,
+}
+
+impl DirectIoAlignment {
+    /// No alignment requirement, as for buffered I/O
+    pub const NONE: Self = Self {
+        memory: 1,
+        offset: 1,
+    };
+
+    /// Round `len` up to the next multiple of the offset alignment
+    pub fn align_len(&self, len: usize) -> usize {
+        let offset = self.offset.max(1);
+        (len + offset - 1) / offset * offset
+    }
+}
+
+impl Default for DirectIoAlignment {
+    /// Conservative alignment that satisfies 512e and 4Kn devices alike
+    fn default() -> Self {
+        Self {
+            memory: 4096,
+            offset: 4096,
+        }
+    }
+}
+
 /// Platform-specific operations trait
 pub trait PlatformOps {
     /// List all available storage devices
@@ -146,6 +181,36 @@ pub trait PlatformOps {
     fn sync_file_system(path: &Path) -> Result<(), PlatformError>
     where
         Self: Sized;
+
+    /// Query the buffer and offset alignment direct I/O needs for `path`
+    ///
+    /// Platforms that cannot query the device fall back to a conservative 4 KiB.
+    fn direct_io_alignment(_path: &Path) -> Result<DirectIoAlignment, PlatformError>
+    where
+        Self: Sized,
+    {
+        Ok(DirectIoAlignment::default())
+    }
+
+    /// Restrict the calling thread to run only on the given CPUs
+    fn pin_current_thread(_cpus: &[usize]) -> Result<(), PlatformError>
+    where
+        Self: Sized,
+    {
+        Err(PlatformError::UnsupportedPlatform(
+            "Pinning threads to CPUs is not available on this platform".to_string(),
+        ))
+    }
+
+    /// List the CPUs of a NUMA node
+    fn numa_node_cpus(_node: usize) -> Result<Vec<usize>, PlatformError>
+    where
+        Self: Sized,
+    {
+        Err(PlatformError::UnsupportedPlatform(
+            "NUMA nodes are not available on this platform".to_string(),
+        ))
+    }
 }
 
 /// Convenience function to list storage devices
@@ -175,6 +240,19 @@ pub fn list_storage_devices() -> Result<Vec<StorageDevice>, PlatformError> {
     compile_error!("Unsupported platform");
 }
 
+/// Find the listed storage device whose mount point contains `path`
+///
+/// When mount points are nested, the innermost one wins. Returns `None` if `path`
+/// is not on any device [`list_storage_devices`] reports.
+pub fn storage_device_for_path(path: &Path) -> Result<Option<StorageDevice>, PlatformError> {
+    let path = path.canonicalize()?;
+    let device = list_storage_devices()?
+        .into_iter()
+        .filter(|device| path.starts_with(&device.mount_point))
+        .max_by_key(|device| device.mount_point.components().count());
+    Ok(device)
+}
+
 /// Convenience function to get app data directory
 pub fn get_app_data_dir() -> Result<PathBuf, PlatformError> {
     #[cfg(target_os = "windows")]
@@ -283,12 +361,115 @@ pub fn sync_file_system(path: &Path) -> Result<(), PlatformError> {
     compile_error!("Unsupported platform");
 }
 
-/// Convenience function to align block size for direct I/O compatibility
-pub fn align_block_size_for_direct_io(block_size: usize) -> usize {
+/// Convenience function to query the direct I/O alignment for a file or directory
+pub fn direct_io_alignment(path: &Path) -> Result<DirectIoAlignment, PlatformError> {
+    #[cfg(target_os = "windows")]
+    return windows::WindowsPlatform::direct_io_alignment(path);
+
+    #[cfg(target_os = "macos")]
+    return macos::MacOsPlatform::direct_io_alignment(path);
+
+    #[cfg(target_os = "linux")]
+    return linux::LinuxPlatform::direct_io_alignment(path);
+
+    #[cfg(target_os = "android")]
+    return android::AndroidPlatform::direct_io_alignment(path);
+
+    #[cfg(target_os = "ios")]
+    return ios::IosPlatform::direct_io_alignment(path);
+
+    #[cfg(not(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "android",
+        target_os = "ios"
+    )))]
+    compile_error!("Unsupported platform");
+}
+
+/// Convenience function to pin the calling thread to a set of CPUs
+pub fn pin_current_thread(cpus: &[usize]) -> Result<(), PlatformError> {
+    #[cfg(target_os = "windows")]
+    return windows::WindowsPlatform::pin_current_thread(cpus);
+
+    #[cfg(target_os = "macos")]
+    return macos::MacOsPlatform::pin_current_thread(cpus);
+
+    #[cfg(target_os = "linux")]
+    return linux::LinuxPlatform::pin_current_thread(cpus);
+
+    #[cfg(target_os = "android")]
+    return android::AndroidPlatform::pin_current_thread(cpus);
+
+    #[cfg(target_os = "ios")]
+    return ios::IosPlatform::pin_current_thread(cpus);
+
+    #[cfg(not(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "android",
+        target_os = "ios"
+    )))]
+    compile_error!("Unsupported platform");
+}
+
+/// Convenience function to list the CPUs of a NUMA node
+pub fn numa_node_cpus(node: usize) -> Result<Vec<usize>, PlatformError> {
+    #[cfg(target_os = "windows")]
+    return windows::WindowsPlatform::numa_node_cpus(node);
+
+    #[cfg(target_os = "macos")]
+    return macos::MacOsPlatform::numa_node_cpus(node);
+
     #[cfg(target_os = "linux")]
-    return linux::LinuxPlatform::align_block_size_for_direct_io(block_size);
+    return linux::LinuxPlatform::numa_node_cpus(node);
+
+    #[cfg(target_os = "android")]
+    return android::AndroidPlatform::numa_node_cpus(node);
+
+    #[cfg(target_os = "ios")]
+    return ios::IosPlatform::numa_node_cpus(node);
 
-    // For other platforms, return the original size for now
-    #[cfg(not(target_os = "linux"))]
-    return block_size;
+    #[cfg(not(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "android",
+        target_os = "ios"
+    )))]
+    compile_error!("Unsupported platform");
+}
+
+/// Parse a CPU list such as `0-3,8,10-11`, the format Linux uses in sysfs
+///
+/// The CPUs are returned in the order listed.
+pub fn parse_cpu_list(list: &str) -> Result<Vec<usize>, PlatformError> {
+    let invalid = |entry: &str| {
+        PlatformError::IoError(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("invalid CPU list entry '{}'", entry),
+        ))
+    };
+    let mut cpus = Vec::new();
+    for entry in list
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (first, last) = entry.split_once('-').unwrap_or((entry, entry));
+        let first: usize = first.trim().parse().map_err(|_| invalid(entry))?;
+        let last: usize = last.trim().parse().map_err(|_| invalid(entry))?;
+        if first > last {
+            return Err(invalid(entry));
+        }
+        cpus.extend(first..=last);
+    }
+    Ok(cpus)
+}
+
+/// Convenience function to align block size for direct I/O compatibility
+pub fn align_block_size_for_direct_io(block_size: usize, alignment: DirectIoAlignment) -> usize {
+    alignment.align_len(block_size)
 }
@@ -3,7 +3,10 @@
 #[cfg(test)]
 mod tests {
     use super::super::mock_platform::{MockFileResult, MockPlatform};
-    use super::super::{DeviceType, PlatformError, PlatformOps, StorageDevice};
+    use super::super::{
+        align_block_size_for_direct_io, parse_cpu_list, storage_device_for_path, DeviceType,
+        DirectIoAlignment, PlatformError, PlatformOps, StorageDevice,
+    };
     use std::io;
     use std::path::PathBuf;
 
@@ -151,6 +154,51 @@ mod tests {
         assert!(debug_str.contains("Test"));
     }
 
+    #[test]
+    fn test_direct_io_alignment_align_len() {
+        let alignment = DirectIoAlignment {
+            memory: 512,
+            offset: 4096,
+        };
+        assert_eq!(alignment.align_len(0), 0);
+        assert_eq!(alignment.align_len(1), 4096);
+        assert_eq!(alignment.align_len(4096), 4096);
+        assert_eq!(alignment.align_len(4097), 8192);
+        assert_eq!(align_block_size_for_direct_io(1000, alignment), 4096);
+
+        // Buffered I/O leaves sizes untouched
+        assert_eq!(DirectIoAlignment::NONE.align_len(1000), 1000);
+        assert_eq!(
+            align_block_size_for_direct_io(1000, DirectIoAlignment::NONE),
+            1000
+        );
+    }
+
+    #[test]
+    fn test_direct_io_alignment_default_is_conservative() {
+        let alignment = DirectIoAlignment::default();
+        assert_eq!(alignment.memory, 4096);
+        assert_eq!(alignment.offset, 4096);
+
+        // Platforms without a query use the default
+        assert_eq!(
+            MockPlatform::direct_io_alignment(&PathBuf::from("/test")).unwrap(),
+            alignment
+        );
+    }
+
+    #[test]
+    fn test_storage_device_for_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().canonicalize().unwrap();
+
+        // Sandboxes may not list the device the temp directory lives on
+        if let Some(device) = storage_device_for_path(&path).unwrap() {
+            assert!(path.starts_with(&device.mount_point));
+        }
+        assert!(storage_device_for_path(&path.join("missing")).is_err());
+    }
+
     #[test]
     fn test_mock_platform_ops_trait() {
         // Test that MockPlatform implements PlatformOps
@@ -483,4 +531,16 @@ mod tests {
         assert_eq!(devices2.len(), 3); // Should have default devices
         assert!(!mock2.is_simulating_errors());
     }
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(
+            parse_cpu_list("0-3,8,10-11\n").unwrap(),
+            [0, 1, 2, 3, 8, 10, 11]
+        );
+        assert_eq!(parse_cpu_list("5").unwrap(), [5]);
+        assert!(parse_cpu_list("").unwrap().is_empty());
+        assert!(parse_cpu_list("3-1").is_err());
+        assert!(parse_cpu_list("a-b").is_err());
+    }
 }
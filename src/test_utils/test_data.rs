@@ -7,22 +7,8 @@ use std::fs::File;
 use std::io::{Seek, SeekFrom, Write};
 use std::path::Path;
 
-/// Patterns for test data generation
-#[derive(Debug, Clone)]
-pub enum TestDataPattern {
-    /// All zeros
-    Zeros,
-    /// All ones (0xFF)
-    Ones,
-    /// Random data with fixed seed for reproducibility
-    RandomSeeded(u64),
-    /// Truly random data
-    Random,
-    /// Alternating pattern (0xAA, 0x55, ...)
-    Alternating,
-    /// Sequential bytes (0x00, 0x01, 0x02, ...)
-    Sequential,
-}
+pub use crate::core::pattern::TestDataPattern;
+pub use crate::core::verify::TestDataVerifier;
 
 /// Test data generator for creating controlled test files
 pub struct TestDataGenerator {
@@ -83,6 +69,10 @@ impl TestDataGenerator {
                         byte_counter = byte_counter.wrapping_add(1);
                     }
                 }
+                TestDataPattern::Compressible(_) | TestDataPattern::Unique => {
+                    self.pattern.fill(&mut chunk);
+                    self.pattern.stamp(&mut chunk, size_bytes - remaining, 0);
+                }
             }
 
             file.write_all(&chunk)?;
@@ -119,60 +109,6 @@ impl TestDataGenerator {
     }
 }
 
-/// Verify test data integrity
-pub struct TestDataVerifier {
-    pattern: TestDataPattern,
-}
-
-impl TestDataVerifier {
-    pub fn new(pattern: TestDataPattern) -> Self {
-        Self { pattern }
-    }
-
-    /// Verify that file contains expected pattern
-    pub fn verify_file(&self, file_path: &Path) -> Result<bool> {
-        use std::io::Read;
-
-        let mut file = File::open(file_path)?;
-        let mut buffer = vec![0u8; 64 * 1024]; // 64KB buffer
-        let mut byte_counter = 0u8;
-
-        loop {
-            let bytes_read = file.read(&mut buffer)?;
-            if bytes_read == 0 {
-                break;
-            }
-
-            for &byte in &buffer[..bytes_read] {
-                let expected = match &self.pattern {
-                    TestDataPattern::Zeros => 0x00,
-                    TestDataPattern::Ones => 0xFF,
-                    TestDataPattern::Alternating => {
-                        if byte_counter % 2 == 0 {
-                            0xAA
-                        } else {
-                            0x55
-                        }
-                    }
-                    TestDataPattern::Sequential => byte_counter,
-                    TestDataPattern::RandomSeeded(_) | TestDataPattern::Random => {
-                        // Can't verify random data without regenerating
-                        byte_counter = byte_counter.wrapping_add(1);
-                        continue;
-                    }
-                };
-
-                if byte != expected {
-                    return Ok(false);
-                }
-                byte_counter = byte_counter.wrapping_add(1);
-            }
-        }
-
-        Ok(true)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
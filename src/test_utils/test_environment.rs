@@ -1,8 +1,12 @@
 //! Test environment setup and configuration utilities
 
 use super::TestDataManager;
-use crate::core::config::BenchmarkConfig;
+use crate::core::config::{
+    BenchmarkConfig, BenchmarkTest, FileSizeShare, IoBackend, IoEngineKind, SyncMethod, TestLimit,
+    ThreadPinning,
+};
 use crate::core::stats::TestResult;
+use crate::core::{CancellationToken, TestDataPattern};
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
@@ -114,9 +118,52 @@ impl TestEnvironment {
                 4 * 1024
             },
             test_duration_seconds: if self.config.use_small_files { 1 } else { 5 },
+            test_limit: TestLimit::Duration,
+            sustained_write_seconds: 1,
+            time_cap_seconds: None,
+            warmup_seconds: 0,
+            repetitions: 1,
             disable_os_cache: true,
             disable_direct_io: false, // Enable direct I/O by default for testing
             file_size_mb: if self.config.use_small_files { 1 } else { 100 },
+            queue_depth: 1,
+            thread_count: 1,
+            mixed_read_percentage: 70,
+            mixed_block_size: if self.config.use_small_files {
+                1024
+            } else {
+                8 * 1024
+            },
+            io_backend: IoBackend::Sync,
+            io_engine: IoEngineKind::Pread,
+            tests: BenchmarkTest::DEFAULT.to_vec(),
+            data_pattern: TestDataPattern::Sequential,
+            sync_write_size: if self.config.use_small_files {
+                512
+            } else {
+                4 * 1024
+            },
+            sync_method: SyncMethod::Fdatasync,
+            sync_write_append: false,
+            metadata_file_count: if self.config.use_small_files {
+                100
+            } else {
+                1000
+            },
+            metadata_fanout: 10,
+            small_file_count: if self.config.use_small_files { 50 } else { 500 },
+            small_file_sizes: FileSizeShare::DEFAULT.to_vec(),
+            memory_min_size: 4 * 1024,
+            memory_max_size: if self.config.use_small_files {
+                1024 * 1024
+            } else {
+                64 * 1024 * 1024
+            },
+            memory_threads: 2,
+            memory_pinning: ThreadPinning::Unpinned,
+            verify: false,
+            record_time_series: false,
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -317,6 +364,14 @@ mod tests {
             avg_speed_mbps: 50.0,
             test_duration: Duration::from_secs(5),
             sample_count: 100,
+            sample_avg_speed_mbps: 50.0,
+            total_bytes: 0,
+            total_operations: 0,
+            latency: Default::default(),
+            stop_reason: Default::default(),
+            corrupted_sectors: 0,
+            corruption_events: Vec::new(),
+            time_series: Vec::new(),
         };
 
         env.record_test_result("test_benchmark".to_string(), test_result.clone());
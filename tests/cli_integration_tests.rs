@@ -169,6 +169,844 @@ fn test_cli_benchmark_with_custom_parameters() {
     assert!(stdout.contains("OS caching: enabled"));
 }
 
+#[test]
+fn test_cli_benchmark_queue_depth_and_threads() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--duration")
+        .arg("1")
+        .arg("--file-size")
+        .arg("2MB")
+        .arg("--queue-depth")
+        .arg("4")
+        .arg("--threads")
+        .arg("2")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .env("DISK_SPEED_TEST_FAST_TEST_MS", "50")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Multi-worker benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Random queue depth / threads: Q4T2"));
+    assert!(stdout.contains("Random Write"));
+    assert!(stdout.contains("Random Read"));
+}
+
+#[test]
+fn test_cli_benchmark_io_engines() {
+    for engine in ["preadv", "mmap"] {
+        let temp_dir = create_temp_test_dir();
+
+        let output = Command::new(get_binary_path())
+            .arg("benchmark")
+            .arg(temp_dir.path())
+            .arg("--duration")
+            .arg("1")
+            .arg("--file-size")
+            .arg("2MB")
+            .arg("--io-engine")
+            .arg(engine)
+            .arg("--disable-direct-io") // Use buffered I/O for compatibility
+            .env("DISK_SPEED_TEST_FAST_TEST_MS", "50")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Benchmark on the {} engine should succeed. Stderr: {}",
+            engine,
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains(&format!("I/O engine: {}", engine)));
+        assert!(stdout.contains("Sequential Read"));
+        assert!(stdout.contains("Random Write"));
+    }
+}
+
+#[test]
+fn test_cli_benchmark_data_patterns() {
+    for pattern in ["zeros", "random", "compressible", "unique"] {
+        let temp_dir = create_temp_test_dir();
+
+        let output = Command::new(get_binary_path())
+            .arg("benchmark")
+            .arg(temp_dir.path())
+            .arg("--duration")
+            .arg("1")
+            .arg("--file-size")
+            .arg("1MB")
+            .arg("--data-pattern")
+            .arg(pattern)
+            .arg("--tests")
+            .arg("seq-write,rand-write")
+            .arg("--disable-direct-io") // Use buffered I/O for compatibility
+            .env("DISK_SPEED_TEST_FAST_TEST_MS", "50")
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(
+            output.status.success(),
+            "Benchmark with the {} pattern should succeed. Stderr: {}",
+            pattern,
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains(&format!("Data pattern: {}", pattern)));
+    }
+}
+
+#[test]
+fn test_cli_compression_ratio_requires_compressible_pattern() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--compression-ratio")
+        .arg("2")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--data-pattern compressible"));
+}
+
+#[test]
+fn test_cli_benchmark_verify() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--duration")
+        .arg("1")
+        .arg("--file-size")
+        .arg("1MB")
+        .arg("--tests")
+        .arg("seq-write,seq-read,rand-write,rand-read")
+        .arg("--verify")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .env("DISK_SPEED_TEST_FAST_TEST_MS", "50")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Verified benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Valid JSON output");
+    assert_eq!(json["corrupted_sectors"], 0);
+    assert_eq!(json["results"]["sequential_read"]["corrupted_sectors"], 0);
+    assert_eq!(
+        json["results"]["random_read"]["corruption_events"],
+        serde_json::json!([])
+    );
+}
+
+#[test]
+fn test_cli_capacity_check() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("capacity-check")
+        .arg(temp_dir.path())
+        .arg("--size")
+        .arg("4MB")
+        .arg("--file-size")
+        .arg("1MB")
+        .arg("--block-size")
+        .arg("256KB")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Capacity check should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Valid JSON output");
+    assert_eq!(json["written_bytes"], 4 * 1024 * 1024);
+    assert_eq!(json["usable_bytes"], 4 * 1024 * 1024);
+    assert_eq!(json["corrupted_bytes"], 0);
+    assert!(json["first_bad_offset"].is_null());
+    assert_eq!(json["samples"].as_array().map(Vec::len), Some(4));
+
+    // The fill files are removed once the check is done
+    assert!(!temp_dir.path().join("disk_speed_test_capacity").exists());
+}
+
+#[test]
+fn test_cli_verify_rejects_unaligned_block_size() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--random-block-size")
+        .arg("1000")
+        .arg("--verify")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("multiple of 512 bytes"));
+}
+
+#[test]
+fn test_cli_benchmark_mixed_workload() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--duration")
+        .arg("1")
+        .arg("--file-size")
+        .arg("2MB")
+        .arg("--mixed-read-percentage")
+        .arg("50")
+        .arg("--mixed-block-size")
+        .arg("16KB")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .env("DISK_SPEED_TEST_FAST_TEST_MS", "50")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Mixed workload benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    let mixed = &json["results"]["mixed_random"];
+    assert_eq!(mixed["read_percentage"], 50);
+    assert!(mixed["read"]["avg_speed_mbps"].is_number());
+    assert!(mixed["write"]["avg_speed_mbps"].is_number());
+    assert!(mixed["combined"]["avg_speed_mbps"].is_number());
+}
+
+#[test]
+fn test_cli_benchmark_warmup() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--duration")
+        .arg("1")
+        .arg("--warmup")
+        .arg("1")
+        .arg("--file-size")
+        .arg("1MB")
+        .arg("--tests")
+        .arg("seq-read,rand-read")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .env("DISK_SPEED_TEST_FAST_TEST_MS", "50")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Benchmark with a warm-up should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Only the measured phase after the warm-up counts towards the results
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    for test in ["sequential_read", "random_read"] {
+        let result = &json["results"][test];
+        assert_eq!(result["status"], "completed");
+        assert!(result["total_bytes"].as_u64().unwrap() > 0);
+        let duration = result["duration_seconds"].as_f64().unwrap();
+        assert!(duration < 1.0, "{} measured its warm-up", test);
+    }
+}
+
+#[test]
+fn test_cli_benchmark_operation_limit() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--file-size")
+        .arg("1MB")
+        .arg("--sequential-block-size")
+        .arg("64KB")
+        .arg("--tests")
+        .arg("seq-write,memory")
+        .arg("--operations")
+        .arg("32")
+        .arg("--time-cap")
+        .arg("30")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Operation-limited benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    for test in ["sequential_write", "memory_copy"] {
+        let result = &json["results"][test];
+        assert_eq!(result["stop_reason"], "operation_limit");
+        assert_eq!(result["total_operations"], 32);
+    }
+
+    // A time cap needs a byte, operation or pass limit
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--time-cap")
+        .arg("5")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--time-cap requires"));
+
+    // Only one limit can be given
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--bytes")
+        .arg("1MB")
+        .arg("--passes")
+        .arg("2")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cli_benchmark_interrupt_cleans_up() {
+    use std::process::Stdio;
+
+    let temp_dir = create_temp_test_dir();
+
+    let child = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--duration")
+        .arg("30")
+        .arg("--file-size")
+        .arg("1MB")
+        .arg("--tests")
+        .arg("seq-write")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+
+    // Interrupt while Sequential Write is running
+    std::thread::sleep(std::time::Duration::from_millis(1000));
+    let status = Command::new("kill")
+        .arg("-INT")
+        .arg(child.id().to_string())
+        .status()
+        .expect("Failed to send SIGINT");
+    assert!(status.success());
+
+    let output = child
+        .wait_with_output()
+        .expect("Failed to wait for command");
+    assert_eq!(
+        output.status.code(),
+        Some(5),
+        "Interrupted benchmark should exit with code 5. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Partial results should be valid JSON");
+    assert_eq!(json["interrupted"], true);
+    assert_eq!(json["results"]["sequential_write"]["status"], "not run");
+
+    // The test file is removed
+    let leftover: Vec<_> = std::fs::read_dir(temp_dir.path()).unwrap().collect();
+    assert!(leftover.is_empty(), "Test file left behind: {:?}", leftover);
+}
+
+#[test]
+fn test_cli_benchmark_repetitions() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--duration")
+        .arg("1")
+        .arg("--file-size")
+        .arg("1MB")
+        .arg("--tests")
+        .arg("rand-read,memory")
+        .arg("--repetitions")
+        .arg("3")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .env("DISK_SPEED_TEST_FAST_TEST_MS", "50")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Repeated benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    assert_eq!(json["repetitions"], 3);
+    assert_eq!(json["runs"].as_array().unwrap().len(), 3);
+
+    let random_read = &json["results"]["random_read"];
+    assert_eq!(random_read["runs"], 3);
+    let low = random_read["ci95_low_mbps"].as_f64().unwrap();
+    let mean = random_read["mean_mbps"].as_f64().unwrap();
+    let high = random_read["ci95_high_mbps"].as_f64().unwrap();
+    assert!(low <= mean && mean <= high);
+    assert!(random_read["stddev_mbps"].as_f64().unwrap() >= 0.0);
+    assert_eq!(json["results"]["sequential_write"]["status"], "not run");
+
+    // Every run cleans up its own test file
+    let leftovers: Vec<_> = std::fs::read_dir(temp_dir.path()).unwrap().collect();
+    assert!(leftovers.is_empty(), "Test file was not removed");
+}
+
+#[test]
+fn test_cli_benchmark_test_selection() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--duration")
+        .arg("1")
+        .arg("--file-size")
+        .arg("1MB")
+        .arg("--tests")
+        .arg("rand-read,memory")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .env("DISK_SPEED_TEST_FAST_TEST_MS", "50")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Benchmark with a test selection should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    let results = &json["results"];
+    assert_eq!(results["random_read"]["status"], "completed");
+    assert_eq!(results["memory_copy"]["status"], "completed");
+
+    // The file is populated before random read, so the phase is reported
+    assert!(json["preparation"]["avg_speed_mbps"].is_number());
+
+    for test in [
+        "sequential_write",
+        "sequential_read",
+        "random_write",
+        "mixed_random",
+        "sync_write",
+        "metadata",
+        "small_files",
+        "memory_stream",
+        "sustained_write",
+    ] {
+        assert_eq!(
+            results[test]["status"], "not run",
+            "{} should not run",
+            test
+        );
+    }
+
+    // The test file created for random read must be cleaned up
+    let leftovers: Vec<_> = std::fs::read_dir(temp_dir.path()).unwrap().collect();
+    assert!(leftovers.is_empty(), "Test file was not removed");
+}
+
+#[test]
+fn test_cli_benchmark_sustained_write() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--file-size")
+        .arg("1MB")
+        .arg("--tests")
+        .arg("sustained-write")
+        .arg("--sustained-duration")
+        .arg("1")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .env("DISK_SPEED_TEST_FAST_TEST_MS", "200")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Sustained write benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    let sustained = &json["results"]["sustained_write"];
+    assert_eq!(sustained["status"], "completed");
+    assert!(sustained["fast_speed_mbps"].is_number());
+    assert!(sustained["steady_speed_mbps"].is_number());
+    assert!(!sustained["samples"].as_array().unwrap().is_empty());
+    assert!(!sustained["phases"].as_array().unwrap().is_empty());
+    assert!(json["preparation"].is_null());
+}
+
+#[test]
+fn test_cli_benchmark_sync_write() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--tests")
+        .arg("sync-write")
+        .arg("--sync-size")
+        .arg("512B")
+        .arg("--sync-method")
+        .arg("fsync")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .env("DISK_SPEED_TEST_FAST_TEST_MS", "200")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Sync write benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    let sync_write = &json["results"]["sync_write"];
+    assert_eq!(sync_write["status"], "completed");
+    assert!(sync_write["commits_per_second"].as_f64().unwrap() > 0.0);
+    assert!(sync_write["latency"]["p99_us"].is_number());
+    // Only the sync log is written, and it is removed afterwards
+    assert!(json["preparation"].is_null());
+    let leftovers: Vec<_> = std::fs::read_dir(temp_dir.path()).unwrap().collect();
+    assert!(leftovers.is_empty(), "Sync write log was not removed");
+}
+
+#[test]
+fn test_cli_benchmark_metadata() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--tests")
+        .arg("metadata")
+        .arg("--metadata-files")
+        .arg("200")
+        .arg("--metadata-fanout")
+        .arg("8")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Metadata benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    let metadata = &json["results"]["metadata"];
+    assert_eq!(metadata["status"], "completed");
+    assert_eq!(metadata["file_count"], 200);
+    assert_eq!(metadata["directory_count"], 8);
+    for operation in ["create", "stat", "rename", "delete"] {
+        assert_eq!(
+            metadata[operation]["total_operations"], 200,
+            "{}",
+            operation
+        );
+        assert!(metadata[operation]["ops_per_second"].as_f64().unwrap() > 0.0);
+    }
+    assert_eq!(metadata["list"]["total_operations"], 8);
+
+    // The directory tree is removed by the benchmark's cleanup
+    let leftovers: Vec<_> = std::fs::read_dir(temp_dir.path()).unwrap().collect();
+    assert!(
+        leftovers.is_empty(),
+        "Metadata directories were not removed"
+    );
+}
+
+#[test]
+fn test_cli_benchmark_small_files() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--tests")
+        .arg("small-files")
+        .arg("--small-files")
+        .arg("150")
+        .arg("--small-file-sizes")
+        .arg("4KB:3,64KB:1")
+        .arg("--output-format")
+        .arg("json")
+        .arg("--disable-direct-io") // Use buffered I/O for compatibility
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Small file benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    let small_files = &json["results"]["small_files"];
+    assert_eq!(small_files["status"], "completed");
+    assert_eq!(small_files["file_count"], 150);
+    assert_eq!(small_files["directory_count"], 2);
+    for direction in ["write", "read"] {
+        assert_eq!(
+            small_files[direction]["total_operations"], 150,
+            "{}",
+            direction
+        );
+        assert!(small_files[direction]["files_per_second"].as_f64().unwrap() > 0.0);
+        assert!(small_files[direction]["avg_speed_mbps"].is_number());
+    }
+    assert_eq!(small_files["open_latency"]["operations"], 150);
+    assert!(json["preparation"].is_null());
+
+    // The file tree is removed by the benchmark's cleanup
+    let leftovers: Vec<_> = std::fs::read_dir(temp_dir.path()).unwrap().collect();
+    assert!(leftovers.is_empty(), "Small files were not removed");
+}
+
+#[test]
+fn test_cli_benchmark_memory_working_sets() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--tests")
+        .arg("memory")
+        .arg("--memory-min-size")
+        .arg("16KB")
+        .arg("--memory-max-size")
+        .arg("1MB")
+        .arg("--passes")
+        .arg("2")
+        .arg("--output-format")
+        .arg("json")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Memory benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    let memory_copy = &json["results"]["memory_copy"];
+    assert_eq!(memory_copy["status"], "completed");
+    let working_sets: Vec<u64> = memory_copy["working_sets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|point| point["working_set_bytes"].as_u64().unwrap())
+        .collect();
+    assert_eq!(
+        working_sets,
+        [16, 32, 64, 128, 256, 512, 1024].map(|kb| kb * 1024)
+    );
+    // The test's own result is that of the largest working set, copied twice
+    assert_eq!(memory_copy["total_bytes"], 1024 * 1024);
+
+    // The largest working set must not be smaller than the smallest
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--tests")
+        .arg("memory")
+        .arg("--memory-min-size")
+        .arg("1MB")
+        .arg("--memory-max-size")
+        .arg("64KB")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_benchmark_memory_stream() {
+    let temp_dir = create_temp_test_dir();
+
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--tests")
+        .arg("memory-stream")
+        .arg("--memory-threads")
+        .arg("2")
+        .arg("--memory-max-size")
+        .arg("3MB")
+        .arg("--passes")
+        .arg("2")
+        .arg("--output-format")
+        .arg("json")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Memory stream benchmark should succeed. Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Benchmark should print valid JSON");
+    let stream = &json["results"]["memory_stream"];
+    assert_eq!(stream["status"], "completed");
+    assert_eq!(stream["threads"], 2);
+    assert_eq!(stream["array_size"], 1024 * 1024);
+    // Copy reads one array and writes another; triad reads two and writes a third
+    assert_eq!(stream["copy"]["total_bytes"], 2 * 2 * 1024 * 1024);
+    assert_eq!(stream["triad"]["total_bytes"], 2 * 3 * 1024 * 1024);
+    assert_eq!(json["results"]["memory_copy"]["status"], "not run");
+
+    // CPUs and NUMA nodes cannot both be given
+    let output = Command::new(get_binary_path())
+        .arg("benchmark")
+        .arg(temp_dir.path())
+        .arg("--tests")
+        .arg("memory-stream")
+        .arg("--memory-cpus")
+        .arg("0-1")
+        .arg("--memory-nodes")
+        .arg("0")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_benchmark_include_samples() {
+    let temp_dir = create_temp_test_dir();
+
+    let run = |format: &str| {
+        let output = Command::new(get_binary_path())
+            .arg("benchmark")
+            .arg(temp_dir.path())
+            .arg("--file-size")
+            .arg("1MB")
+            .arg("--tests")
+            .arg("seq-write,rand-read")
+            .arg("--include-samples")
+            .arg("--output-format")
+            .arg(format)
+            .arg("--disable-direct-io") // Use buffered I/O for compatibility
+            .env("DISK_SPEED_TEST_FAST_TEST_MS", "300")
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Benchmark with samples should succeed. Stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let json: serde_json::Value =
+        serde_json::from_str(&run("json")).expect("Benchmark should print valid JSON");
+    for test in ["sequential_write", "random_read"] {
+        let series = json["results"][test]["time_series"]
+            .as_array()
+            .unwrap_or_else(|| panic!("{} should include its time series", test));
+        assert!(!series.is_empty());
+        assert!(series[0]["elapsed_seconds"].is_number());
+        assert!(series[0]["bytes"].is_number());
+        assert!(series[0]["operations"].is_number());
+        assert!(series[0]["speed_mbps"].is_number());
+    }
+    assert!(json["results"]["sequential_read"]
+        .get("time_series")
+        .is_none());
+
+    let csv = run("csv");
+    let series = csv
+        .split("# Time Series\n")
+        .nth(1)
+        .expect("CSV should include the time series");
+    assert!(series.starts_with("Test,Elapsed (s),Bytes,Operations,"));
+    assert!(series.contains("\nSequential Write,"));
+    assert!(series.contains("\nRandom Read,"));
+}
+
 #[test]
 fn test_cli_benchmark_json_output() {
     let temp_dir = create_temp_test_dir();
@@ -205,6 +1043,27 @@ fn test_cli_benchmark_json_output() {
     assert!(json["results"]["random_write"]["avg_speed_mbps"].is_number());
     assert!(json["results"]["random_read"]["avg_speed_mbps"].is_number());
     assert!(json["results"]["memory_copy"]["avg_speed_mbps"].is_number());
+
+    // Every completed test reports IOPS and latency percentiles
+    let random_read_latency = &json["results"]["random_read"]["latency"];
+    assert!(random_read_latency["operations"].as_u64().unwrap() > 0);
+    assert!(random_read_latency["iops"].as_f64().unwrap() > 0.0);
+    let p50 = random_read_latency["p50_us"].as_f64().unwrap();
+    let p99 = random_read_latency["p99_us"].as_f64().unwrap();
+    let max = random_read_latency["max_us"].as_f64().unwrap();
+    assert!(p50 <= p99 && p99 <= max);
+    assert!(json["results"]["sequential_write"]["latency"]["p999_us"].is_number());
+
+    // The headline average is the effective throughput over the whole test
+    let sequential_read = &json["results"]["sequential_read"];
+    let total_bytes = sequential_read["total_bytes"].as_u64().unwrap();
+    let duration = sequential_read["duration_seconds"].as_f64().unwrap();
+    let effective = total_bytes as f64 / (1024.0 * 1024.0) / duration;
+    let avg = sequential_read["avg_speed_mbps"].as_f64().unwrap();
+    assert!(total_bytes > 0);
+    assert!(sequential_read["total_operations"].as_u64().unwrap() > 0);
+    assert!((avg - effective).abs() <= effective * 1e-6);
+    assert!(sequential_read["sample_avg_speed_mbps"].is_number());
     assert!(json["timestamp"].is_number());
     assert!(json["version"].is_string());
 }
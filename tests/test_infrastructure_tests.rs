@@ -197,6 +197,14 @@ fn test_environment_result_recording() {
         avg_speed_mbps: 50.0,
         test_duration: Duration::from_secs(5),
         sample_count: 100,
+        sample_avg_speed_mbps: 50.0,
+        total_bytes: 0,
+        total_operations: 0,
+        latency: Default::default(),
+        stop_reason: Default::default(),
+        corrupted_sectors: 0,
+        corruption_events: Vec::new(),
+        time_series: Vec::new(),
     };
 
     env.record_test_result("test_benchmark".to_string(), test_result.clone());